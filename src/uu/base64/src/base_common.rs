@@ -33,6 +33,12 @@ pub fn execute(
             "wrap encoded lines after COLS character (default 76, 0 to disable wrapping)",
             "COLS",
         )
+        .optflag(
+            "",
+            "url-safe",
+            "use the URL and file name safe alphabet, with - and _ instead of + and /; \
+             the padding is optional when decoding",
+        )
         .parse(args);
 
     let line_wrap = matches.opt_str("wrap").map(|s| match s.parse() {
@@ -43,6 +49,11 @@ pub fn execute(
     });
     let ignore_garbage = matches.opt_present("ignore-garbage");
     let decode = matches.opt_present("decode");
+    let format = if matches.opt_present("url-safe") {
+        Format::Base64Url
+    } else {
+        format
+    };
 
     if matches.free.len() > 1 {
         show_usage_error!("extra operand ‘{}’", matches.free[0]);
@@ -124,6 +124,9 @@ struct OutputState {
 
     /// Whether the output cursor is at the beginning of a new line
     at_line_start: bool,
+
+    /// Whether a blank line was just written, so further ones are squeezed away
+    one_blank_kept: bool,
 }
 
 /// Represents an open file handle, stream, or other device
@@ -187,8 +190,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(options::NUMBER_NONBLANK)
                 .short("b")
                 .long(options::NUMBER_NONBLANK)
-                .help("number nonempty output lines, overrides -n")
-                .overrides_with(options::NUMBER),
+                .help("number nonempty output lines, overrides -n"),
         )
         .arg(
             Arg::with_name(options::SHOW_NONPRINTING_ENDS)
@@ -343,6 +345,7 @@ fn cat_files(files: Vec<String>, options: &OutputOptions) -> Result<(), u32> {
     let mut state = OutputState {
         line_number: 1,
         at_line_start: true,
+        one_blank_kept: false,
     };
 
     for path in &files {
@@ -422,7 +425,6 @@ fn write_lines<R: Read>(
     let mut in_buf = [0; 1024 * 31];
     let stdout = io::stdout();
     let mut writer = stdout.lock();
-    let mut one_blank_kept = false;
 
     while let Ok(n) = handle.reader.read(&mut in_buf) {
         if n == 0 {
@@ -433,8 +435,8 @@ fn write_lines<R: Read>(
         while pos < n {
             // skip empty line_number enumerating them if needed
             if in_buf[pos] == b'\n' {
-                if !state.at_line_start || !options.squeeze_blank || !one_blank_kept {
-                    one_blank_kept = true;
+                if !state.at_line_start || !options.squeeze_blank || !state.one_blank_kept {
+                    state.one_blank_kept = true;
                     if state.at_line_start && options.number == NumberingMode::All {
                         write!(&mut writer, "{0:6}\t", state.line_number)?;
                         state.line_number += 1;
@@ -448,7 +450,7 @@ fn write_lines<R: Read>(
                 pos += 1;
                 continue;
             }
-            one_blank_kept = false;
+            state.one_blank_kept = false;
             if state.at_line_start && options.number != NumberingMode::None {
                 write!(&mut writer, "{0:6}\t", state.line_number)?;
                 state.line_number += 1;
@@ -28,6 +28,8 @@ mod options {
     pub const COLUMN_3: &str = "3";
     pub const DELIMITER: &str = "output-delimiter";
     pub const DELIMITER_DEFAULT: &str = "\t";
+    pub const CHECK_ORDER: &str = "check-order";
+    pub const NOCHECK_ORDER: &str = "nocheck-order";
     pub const FILE_1: &str = "FILE1";
     pub const FILE_2: &str = "FILE2";
 }
@@ -38,7 +40,11 @@ fn get_usage() -> String {
 
 fn mkdelim(col: usize, opts: &ArgMatches) -> String {
     let mut s = String::new();
-    let delim = opts.value_of(options::DELIMITER).unwrap();
+    let delim = match opts.value_of(options::DELIMITER).unwrap() {
+        // like GNU comm, an empty delimiter is a NUL
+        "" => "\0",
+        delim => delim,
+    };
 
     if col > 1 && !opts.is_present(options::COLUMN_1) {
         s.push_str(delim.as_ref());
@@ -71,13 +77,60 @@ impl LineReader {
     }
 }
 
-fn comm(a: &mut LineReader, b: &mut LineReader, opts: &ArgMatches) {
+#[derive(Clone, Copy, PartialEq)]
+enum CheckOrder {
+    Default,
+    Enabled,
+    Disabled,
+}
+
+struct OrderChecker {
+    check_order: CheckOrder,
+    // whether a line of one file has been compared with a different line of the other one, after
+    // which the default is to check the order
+    seen_unpairable: bool,
+    disorder_found: [bool; 2],
+}
+
+impl OrderChecker {
+    /// Check that the line `current` of file `file` (1 or 2) comes after `prev`, warning about
+    /// it the first time it doesn't. Returns false if that's fatal.
+    fn check(&mut self, prev: &str, current: &str, file: usize) -> bool {
+        let enabled = match self.check_order {
+            CheckOrder::Default => self.seen_unpairable,
+            CheckOrder::Enabled => true,
+            CheckOrder::Disabled => false,
+        };
+        if !enabled || self.disorder_found[file - 1] || prev <= current {
+            return true;
+        }
+        show_info!("file {} is not in sorted order", file);
+        self.disorder_found[file - 1] = true;
+        self.check_order != CheckOrder::Enabled
+    }
+}
+
+fn comm(a: &mut LineReader, b: &mut LineReader, opts: &ArgMatches) -> i32 {
     let delim: Vec<String> = (0..4).map(|col| mkdelim(col, opts)).collect();
+    let check_order = if opts.is_present(options::CHECK_ORDER) {
+        CheckOrder::Enabled
+    } else if opts.is_present(options::NOCHECK_ORDER) {
+        CheckOrder::Disabled
+    } else {
+        CheckOrder::Default
+    };
+    let mut checker = OrderChecker {
+        check_order,
+        seen_unpairable: false,
+        disorder_found: [false, false],
+    };
 
     let ra = &mut String::new();
     let mut na = a.read_line(ra);
     let rb = &mut String::new();
     let mut nb = b.read_line(rb);
+    let prev_a = &mut String::new();
+    let prev_b = &mut String::new();
 
     while na.is_ok() || nb.is_ok() {
         let ord = match (na.is_ok(), nb.is_ok()) {
@@ -91,6 +144,9 @@ fn comm(a: &mut LineReader, b: &mut LineReader, opts: &ArgMatches) {
             },
             _ => unreachable!(),
         };
+        if ord != Ordering::Equal {
+            checker.seen_unpairable = true;
+        }
 
         match ord {
             Ordering::Less => {
@@ -98,28 +154,44 @@ fn comm(a: &mut LineReader, b: &mut LineReader, opts: &ArgMatches) {
                     ensure_nl(ra);
                     print!("{}{}", delim[1], ra);
                 }
-                ra.clear();
-                na = a.read_line(ra);
             }
             Ordering::Greater => {
                 if !opts.is_present(options::COLUMN_2) {
                     ensure_nl(rb);
                     print!("{}{}", delim[2], rb);
                 }
-                rb.clear();
-                nb = b.read_line(rb);
             }
             Ordering::Equal => {
                 if !opts.is_present(options::COLUMN_3) {
                     ensure_nl(ra);
                     print!("{}{}", delim[3], ra);
                 }
-                ra.clear();
-                rb.clear();
-                na = a.read_line(ra);
-                nb = b.read_line(rb);
             }
         }
+
+        if ord != Ordering::Greater {
+            std::mem::swap(ra, prev_a);
+            ra.clear();
+            na = a.read_line(ra);
+            if na.as_ref().map_or(false, |n| *n > 0) && !checker.check(prev_a, ra, 1) {
+                return 1;
+            }
+        }
+        if ord != Ordering::Less {
+            std::mem::swap(rb, prev_b);
+            rb.clear();
+            nb = b.read_line(rb);
+            if nb.as_ref().map_or(false, |n| *n > 0) && !checker.check(prev_b, rb, 2) {
+                return 1;
+            }
+        }
+    }
+
+    if checker.disorder_found[0] || checker.disorder_found[1] {
+        show_info!("input is not in sorted order");
+        1
+    } else {
+        0
     }
 }
 
@@ -167,6 +239,18 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .default_value(options::DELIMITER_DEFAULT)
                 .hide_default_value(true),
         )
+        .arg(
+            Arg::with_name(options::CHECK_ORDER)
+                .long(options::CHECK_ORDER)
+                .help("check that the input is correctly sorted, even if all input lines are pairable")
+                .overrides_with(options::NOCHECK_ORDER),
+        )
+        .arg(
+            Arg::with_name(options::NOCHECK_ORDER)
+                .long(options::NOCHECK_ORDER)
+                .help("do not check that the input is correctly sorted")
+                .overrides_with(options::CHECK_ORDER),
+        )
         .arg(Arg::with_name(options::FILE_1).required(true))
         .arg(Arg::with_name(options::FILE_2).required(true))
         .get_matches_from(args);
@@ -174,7 +258,5 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     let mut f1 = open_file(matches.value_of(options::FILE_1).unwrap()).unwrap();
     let mut f2 = open_file(matches.value_of(options::FILE_2).unwrap()).unwrap();
 
-    comm(&mut f1, &mut f2, &matches);
-
-    0
+    comm(&mut f1, &mut f2, &matches)
 }
@@ -12,6 +12,9 @@
 #[macro_use]
 extern crate uucore;
 
+mod parse;
+
+use chrono::format::{Item, StrftimeItems};
 use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
 #[cfg(windows)]
 use chrono::{Datelike, Timelike};
@@ -21,6 +24,8 @@ use libc::{clock_settime, timespec, CLOCK_REALTIME};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
+
+use crate::parse::parse_date;
 #[cfg(windows)]
 use winapi::{
     shared::minwindef::WORD,
@@ -153,7 +158,13 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .short("d")
                 .long(OPT_DATE)
                 .takes_value(true)
-                .help("display time described by STRING, not 'now'"),
+                .allow_hyphen_values(true)
+                .value_name("STRING")
+                .help(
+                    "display time described by STRING, not 'now': a date and/or time like \
+                     '2024-01-15 12:00', '@SECONDS', or relative items like 'tomorrow' or \
+                     '2 days ago'",
+                ),
         )
         .arg(
             Arg::with_name(OPT_FILE)
@@ -234,9 +245,20 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         DateSource::Now
     };
 
-    let set_to = match matches.value_of(OPT_SET).map(parse_date) {
+    let utc = matches.is_present(OPT_UNIVERSAL);
+
+    // Get the current time, either in the local time zone or UTC.
+    let now: DateTime<FixedOffset> = if utc {
+        let now = Utc::now();
+        now.with_timezone(&now.offset().fix())
+    } else {
+        let now = Local::now();
+        now.with_timezone(now.offset())
+    };
+
+    let set_to = match matches.value_of(OPT_SET).map(|s| parse_date(s, now, utc)) {
         None => None,
-        Some(Err((input, _err))) => {
+        Some(Err(input)) => {
             eprintln!("date: invalid date '{}'", input);
             return 1;
         }
@@ -244,7 +266,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     };
 
     let settings = Settings {
-        utc: matches.is_present(OPT_UNIVERSAL),
+        utc,
         format,
         date_source,
         set_to,
@@ -258,31 +280,24 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             date.into()
         };
 
-        return set_system_datetime(date);
+        set_system_datetime(date)
     } else {
         // Declare a file here because it needs to outlive the `dates` iterator.
         let file: File;
 
-        // Get the current time, either in the local time zone or UTC.
-        let now: DateTime<FixedOffset> = if settings.utc {
-            let now = Utc::now();
-            now.with_timezone(&now.offset().fix())
-        } else {
-            let now = Local::now();
-            now.with_timezone(now.offset())
-        };
-
         // Iterate over all dates - whether it's a single date or a file.
         let dates: Box<dyn Iterator<Item = _>> = match settings.date_source {
             DateSource::Custom(ref input) => {
-                let date = parse_date(input.clone());
+                let date = parse_date(input, now, utc);
                 let iter = std::iter::once(date);
                 Box::new(iter)
             }
             DateSource::File(ref path) => {
                 file = File::open(path).unwrap();
                 let lines = BufReader::new(file).lines();
-                let iter = lines.filter_map(Result::ok).map(parse_date);
+                let iter = lines
+                    .filter_map(Result::ok)
+                    .map(move |line| parse_date(&line, now, utc));
                 Box::new(iter)
             }
             DateSource::Now => {
@@ -294,20 +309,21 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         let format_string = make_format_string(&settings);
 
         // Format all the dates
+        let mut status = 0;
         for date in dates {
             match date {
                 Ok(date) => {
-                    let formatted = date.format(format_string);
+                    let formatted = format_date(&date, format_string, settings.utc);
                     println!("{}", formatted);
                 }
-                Err((input, _err)) => {
-                    println!("date: invalid date '{}'", input);
+                Err(input) => {
+                    eprintln!("date: invalid date '{}'", input);
+                    status = 1;
                 }
             }
         }
+        status
     }
-
-    0
 }
 
 /// Return the appropriate format string for the given settings.
@@ -331,13 +347,65 @@ fn make_format_string(settings: &Settings) -> &str {
     }
 }
 
-/// Parse a `String` into a `DateTime`.
-/// If it fails, return a tuple of the `String` along with its `ParseError`.
-fn parse_date<S: AsRef<str> + Clone>(
-    s: S,
-) -> Result<DateTime<FixedOffset>, (String, chrono::format::ParseError)> {
-    // TODO: The GNU date command can parse a wide variety of inputs.
-    s.as_ref().parse().map_err(|e| (s.as_ref().into(), e))
+/// Format `date` like strftime. Unlike chrono, this prints the name of the time zone for `%Z`
+/// and the nanoseconds for `%N`, and prints unknown conversions as they are.
+fn format_date(date: &DateTime<FixedOffset>, format: &str, utc: bool) -> String {
+    let mut formatted = String::new();
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+
+        // A conversion, possibly after padding flags like in `%-d`, or a `:` like in `%:z`
+        let mut conversion = String::from("%");
+        for c in &mut chars {
+            conversion.push(c);
+            if !"-_0:".contains(c) {
+                break;
+            }
+        }
+
+        match conversion.as_str() {
+            "%Z" => formatted.push_str(&time_zone_name(date, utc)),
+            "%N" => formatted.push_str(&format!("{:09}", date.timestamp_subsec_nanos())),
+            _ if StrftimeItems::new(&conversion).any(|item| item == Item::Error) => {
+                formatted.push_str(&conversion)
+            }
+            _ => formatted.push_str(&date.format(&conversion).to_string()),
+        }
+    }
+    formatted
+}
+
+/// The abbreviated name of the time zone of `date`, like `UTC` or `CET`, or its offset if the
+/// name isn't known.
+fn time_zone_name(date: &DateTime<FixedOffset>, utc: bool) -> String {
+    if utc {
+        return "UTC".to_string();
+    }
+    local_time_zone_name(date).unwrap_or_else(|| date.format("%:z").to_string())
+}
+
+/// The name of the local time zone (which respects `TZ`) at `date`, if `date` is in it.
+#[cfg(unix)]
+fn local_time_zone_name(date: &DateTime<FixedOffset>) -> Option<String> {
+    let time = date.timestamp() as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null()
+        || tm.tm_zone.is_null()
+        || tm.tm_gmtoff != date.offset().local_minus_utc() as libc::c_long
+    {
+        return None;
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(tm.tm_zone) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+fn local_time_zone_name(_date: &DateTime<FixedOffset>) -> Option<String> {
+    None
 }
 
 #[cfg(not(any(unix, windows)))]
@@ -0,0 +1,204 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Parsing of the date strings of `--date` and `--file`.
+//!
+//! Besides RFC 3339 dates with a time zone, these are understood:
+//!
+//! * `@SECONDS`, seconds since the epoch;
+//! * a date like `2024-01-15`, a time like `12:00` or `12:00:30`, or both, possibly joined with
+//!   a `T`, in the local time zone (or in UTC with `-u`); a date alone is at midnight, and a
+//!   time alone is today;
+//! * `now` and `today`, `tomorrow` and `yesterday`;
+//! * relative items like `3 days`, `-1 week` or `2 hours ago`, with the units second, minute,
+//!   hour, day, week and fortnight (months and years aren't supported, since their length
+//!   varies).
+//!
+//! Relative items are added to the date and time, or to now if there aren't any, so
+//! `2024-01-15 12:00 tomorrow` is `2024-01-16 12:00`.
+
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+
+/// The length of a relative unit like `days`, if `s` is one.
+fn parse_unit(s: &str) -> Option<Duration> {
+    match s {
+        "sec" | "secs" | "second" | "seconds" => Some(Duration::seconds(1)),
+        "min" | "mins" | "minute" | "minutes" => Some(Duration::minutes(1)),
+        "hour" | "hours" => Some(Duration::hours(1)),
+        "day" | "days" => Some(Duration::days(1)),
+        "week" | "weeks" => Some(Duration::weeks(1)),
+        "fortnight" | "fortnights" => Some(Duration::weeks(2)),
+        _ => None,
+    }
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(s, "%H:%M"))
+        .ok()
+}
+
+fn in_time_zone(utc: bool, date: NaiveDate, time: NaiveTime) -> Option<DateTime<FixedOffset>> {
+    let naive = date.and_time(time);
+    if utc {
+        Some(
+            Utc.from_utc_datetime(&naive)
+                .with_timezone(&FixedOffset::east(0)),
+        )
+    } else {
+        Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|date| date.with_timezone(date.offset()))
+    }
+}
+
+fn from_timestamp(utc: bool, seconds: i64) -> Option<DateTime<FixedOffset>> {
+    if utc {
+        Utc.timestamp_opt(seconds, 0)
+            .single()
+            .map(|date| date.with_timezone(&FixedOffset::east(0)))
+    } else {
+        Local
+            .timestamp_opt(seconds, 0)
+            .single()
+            .map(|date| date.with_timezone(date.offset()))
+    }
+}
+
+/// Parse a date string (see the module documentation), relative to `now`.
+/// If it fails, return the string.
+pub fn parse_date(
+    s: &str,
+    now: DateTime<FixedOffset>,
+    utc: bool,
+) -> Result<DateTime<FixedOffset>, String> {
+    let invalid = || s.to_string();
+
+    if let Ok(date) = s.parse::<DateTime<FixedOffset>>() {
+        return Ok(date);
+    }
+
+    let trimmed = s.trim();
+    if trimmed.starts_with('@') {
+        return trimmed[1..]
+            .parse()
+            .ok()
+            .and_then(|seconds| from_timestamp(utc, seconds))
+            .ok_or_else(invalid);
+    }
+
+    let mut date = None;
+    let mut time = None;
+    let mut relative = Duration::zero();
+    let mut last_item: Option<Duration> = None;
+
+    let mut tokens = trimmed.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let item = match token {
+            "now" | "today" => None,
+            "tomorrow" => Some(Duration::days(1)),
+            "yesterday" => Some(Duration::days(-1)),
+            // "ago" turns the previous item around
+            "ago" => match last_item.take() {
+                Some(item) => Some(-item - item),
+                None => return Err(invalid()),
+            },
+            _ => {
+                let (date_part, time_part) = match token.find('T') {
+                    Some(i) => (&token[..i], Some(&token[i + 1..])),
+                    None => (token, None),
+                };
+                if let Ok(parsed) = NaiveDate::parse_from_str(date_part, "%Y-%m-%d") {
+                    if date.is_some() {
+                        return Err(invalid());
+                    }
+                    date = Some(parsed);
+                    if let Some(time_part) = time_part {
+                        time = Some(parse_time(time_part).ok_or_else(invalid)?);
+                    }
+                    None
+                } else if let Some(parsed) = parse_time(token) {
+                    if time.is_some() {
+                        return Err(invalid());
+                    }
+                    time = Some(parsed);
+                    None
+                } else if let Some(unit) = parse_unit(token) {
+                    Some(unit)
+                } else if let Ok(n) = token.parse::<i32>() {
+                    let unit = tokens.next().and_then(parse_unit).ok_or_else(invalid)?;
+                    Some(unit * n)
+                } else {
+                    return Err(invalid());
+                }
+            }
+        };
+        if let Some(item) = item {
+            relative = relative + item;
+            last_item = if token == "ago" { None } else { Some(item) };
+        }
+    }
+
+    // An empty string is the start of today
+    if trimmed.is_empty() {
+        time = Some(NaiveTime::from_hms(0, 0, 0));
+    }
+
+    let base = if date.is_none() && time.is_none() {
+        now
+    } else {
+        let date = date.unwrap_or_else(|| now.naive_local().date());
+        let time = time.unwrap_or_else(|| NaiveTime::from_hms(0, 0, 0));
+        in_time_zone(utc, date, time).ok_or_else(invalid)?
+    };
+
+    base.checked_add_signed(relative).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339("2021-03-04T05:06:07+00:00").unwrap()
+    }
+
+    fn parse(s: &str) -> String {
+        parse_date(s, now(), true)
+            .unwrap()
+            .format("%F %T")
+            .to_string()
+    }
+
+    #[test]
+    fn relative_days() {
+        assert_eq!(parse("now"), "2021-03-04 05:06:07");
+        assert_eq!(parse("today"), "2021-03-04 05:06:07");
+        assert_eq!(parse("tomorrow"), "2021-03-05 05:06:07");
+        assert_eq!(parse("yesterday"), "2021-03-03 05:06:07");
+        assert_eq!(parse("3 days ago"), "2021-03-01 05:06:07");
+        assert_eq!(parse("-1 week"), "2021-02-25 05:06:07");
+        assert_eq!(parse("2 hours 30 minutes ago"), "2021-03-04 06:36:07");
+    }
+
+    #[test]
+    fn absolute() {
+        assert_eq!(parse("2024-01-15"), "2024-01-15 00:00:00");
+        assert_eq!(parse("2024-01-15 12:00"), "2024-01-15 12:00:00");
+        assert_eq!(parse("2024-01-15T12:30:05"), "2024-01-15 12:30:05");
+        assert_eq!(parse("12:00"), "2021-03-04 12:00:00");
+        assert_eq!(parse(""), "2021-03-04 00:00:00");
+        assert_eq!(parse("@1700000000"), "2023-11-14 22:13:20");
+        assert_eq!(parse("2024-01-15 12:00 tomorrow"), "2024-01-16 12:00:00");
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &["foo", "3", "3 months", "ago", "@x", "2024-01-15 2024-01-16"] {
+            assert_eq!(parse_date(s, now(), true), Err(s.to_string()));
+        }
+    }
+}
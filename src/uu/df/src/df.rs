@@ -22,10 +22,12 @@ use winapi::um::fileapi::{
     GetVolumePathNamesForVolumeNameW, QueryDosDeviceW,
 };
 
-use number_prefix::NumberPrefix;
 use std::cell::Cell;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+use uucore::fs::{canonicalize, CanonicalizeMode};
 
 #[cfg(unix)]
 use std::ffi::CString;
@@ -58,8 +60,6 @@ use std::os::windows::ffi::OsStrExt;
 #[cfg(windows)]
 use std::os::windows::ffi::OsStringExt;
 #[cfg(windows)]
-use std::path::Path;
-#[cfg(windows)]
 use winapi::shared::minwindef::DWORD;
 #[cfg(windows)]
 use winapi::um::fileapi::GetDiskFreeSpaceW;
@@ -116,11 +116,44 @@ struct Options {
     show_listed_fs: bool,
     show_fs_type: bool,
     show_inode_instead: bool,
+    show_output: bool,
     // block_size: usize,
     human_readable_base: i64,
     fs_selector: FsSelector,
 }
 
+/// The columns of the output, which `--output` selects by their GNU names
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Column {
+    Source,
+    Fstype,
+    Itotal,
+    Iused,
+    Iavail,
+    Ipcent,
+    Size,
+    Used,
+    Avail,
+    Pcent,
+    File,
+    Target,
+}
+
+static ALL_COLUMNS: [Column; 12] = [
+    Column::Source,
+    Column::Fstype,
+    Column::Itotal,
+    Column::Iused,
+    Column::Iavail,
+    Column::Ipcent,
+    Column::Size,
+    Column::Used,
+    Column::Avail,
+    Column::Pcent,
+    Column::File,
+    Column::Target,
+];
+
 #[derive(Debug, Clone)]
 struct MountInfo {
     // it stores `volume_name` in windows platform and `dev_id` in unix platform
@@ -277,6 +310,91 @@ impl FsSelector {
     }
 }
 
+impl Column {
+    fn parse(field: &str) -> Option<Column> {
+        ALL_COLUMNS
+            .iter()
+            .find(|column| column.name() == field)
+            .copied()
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Column::Source => "source",
+            Column::Fstype => "fstype",
+            Column::Itotal => "itotal",
+            Column::Iused => "iused",
+            Column::Iavail => "iavail",
+            Column::Ipcent => "ipcent",
+            Column::Size => "size",
+            Column::Used => "used",
+            Column::Avail => "avail",
+            Column::Pcent => "pcent",
+            Column::File => "file",
+            Column::Target => "target",
+        }
+    }
+
+    fn header(self, opt: &Options) -> &'static str {
+        match self {
+            Column::Source => "Filesystem",
+            Column::Fstype => "Type",
+            Column::Itotal => "Inodes",
+            Column::Iused => "IUsed",
+            Column::Iavail => "IFree",
+            Column::Ipcent => "IUse%",
+            Column::Size if opt.human_readable_base == -1 => "1K-blocks",
+            Column::Size => "Size",
+            Column::Used => "Used",
+            // GNU spells it out only in the default layout
+            Column::Avail if opt.human_readable_base == -1 && !opt.show_output => "Available",
+            Column::Avail => "Avail",
+            Column::Pcent => "Use%",
+            Column::File => "File",
+            Column::Target => "Mounted on",
+        }
+    }
+
+    /// Whether the column is text, which is left aligned, rather than a number
+    fn is_text(self) -> bool {
+        #[allow(clippy::match_like_matches_macro)]
+        // `matches!(...)` macro not stabilized until rust v1.42
+        match self {
+            Column::Source | Column::Fstype | Column::File | Column::Target => true,
+            _ => false,
+        }
+    }
+
+    /// The value of the column for `fs`, which was listed for the argument `file` (or `-`)
+    fn value(self, fs: &Filesystem, opt: &Options, file: &str) -> String {
+        let usage = &fs.usage;
+        let iused = usage.files.saturating_sub(usage.ffree);
+        let used = usage.blocks - usage.bfree;
+        // a negative number of available blocks (for root) is shown as 0
+        let avail = if usage.bavail_top_bit_set {
+            0
+        } else {
+            usage.bavail
+        };
+        match self {
+            Column::Source => fs.mountinfo.dev_name.clone(),
+            Column::Fstype => fs.mountinfo.fs_type.clone(),
+            // file systems without inodes report 0 of them
+            Column::Itotal | Column::Iused | Column::Iavail if usage.files == 0 => "-".to_string(),
+            Column::Itotal => human_readable(usage.files, opt.human_readable_base),
+            Column::Iused => human_readable(iused, opt.human_readable_base),
+            Column::Iavail => human_readable(usage.ffree, opt.human_readable_base),
+            Column::Ipcent => use_size(iused, usage.ffree),
+            Column::Size => size_string(usage.blocks * usage.blocksize, opt),
+            Column::Used => size_string(used * usage.blocksize, opt),
+            Column::Avail => size_string(avail * usage.blocksize, opt),
+            Column::Pcent => use_size(used, avail),
+            Column::File => file.to_string(),
+            Column::Target => fs.mountinfo.mount_dir.clone(),
+        }
+    }
+}
+
 impl Options {
     fn new() -> Options {
         Options {
@@ -285,6 +403,7 @@ impl Options {
             show_listed_fs: false,
             show_fs_type: false,
             show_inode_instead: false,
+            show_output: false,
             // block_size: match env::var("BLOCKSIZE") {
             //     Ok(size) => size.parse().unwrap(),
             //     Err(_) => 512,
@@ -639,28 +758,10 @@ fn read_fs_list() -> Vec<MountInfo> {
     }
 }
 
-fn filter_mount_list(vmi: Vec<MountInfo>, paths: &[String], opt: &Options) -> Vec<MountInfo> {
+fn filter_mount_list(vmi: Vec<MountInfo>, opt: &Options) -> Vec<MountInfo> {
     vmi.into_iter()
-        .filter_map(|mi| {
-            if (mi.remote && opt.show_local_fs)
-                || (mi.dummy && !opt.show_all_fs && !opt.show_listed_fs)
-                || !opt.fs_selector.should_select(&mi.fs_type)
-            {
-                None
-            } else {
-                if paths.is_empty() {
-                    // No path specified
-                    return Some((mi.dev_id.clone(), mi));
-                }
-                if paths.contains(&mi.mount_dir) {
-                    // One or more paths have been provided
-                    Some((mi.dev_id.clone(), mi))
-                } else {
-                    // Not a path we want to see
-                    None
-                }
-            }
-        })
+        .filter(|mi| is_selected(mi, opt))
+        .map(|mi| (mi.dev_id.clone(), mi))
         .fold(
             HashMap::<String, Cell<MountInfo>>::new(),
             |mut acc, (id, mi)| {
@@ -699,37 +800,87 @@ fn filter_mount_list(vmi: Vec<MountInfo>, paths: &[String], opt: &Options) -> Ve
         .collect::<Vec<_>>()
 }
 
+/// Whether the mount passes the file system type and locality options.
+fn is_selected(mi: &MountInfo, opt: &Options) -> bool {
+    !(mi.remote && opt.show_local_fs)
+        && (!mi.dummy || opt.show_all_fs || opt.show_listed_fs)
+        && opt.fs_selector.should_select(&mi.fs_type)
+}
+
+/// Find the mount containing `path`: the one whose mount point is the longest prefix of its
+/// canonical form, the latest mounted winning when several share it.
+fn find_mount<'a>(vmi: &'a [MountInfo], path: &str) -> io::Result<Option<&'a MountInfo>> {
+    let path = canonicalize(path, CanonicalizeMode::Existing)?;
+    Ok(vmi
+        .iter()
+        .filter(|mi| !mi.mount_dir.is_empty() && path.starts_with(&mi.mount_dir))
+        .max_by_key(|mi| Path::new(&mi.mount_dir).components().count()))
+}
+
 /// Convert `value` to a human readable string based on `base`.
-/// e.g. It returns 1G when value is 1 * 1024 * 1024 * 1024 and base is 1024.
+/// e.g. It returns 1.0G when value is 1 * 1024 * 1024 * 1024 and base is 1024.
+/// Like in GNU df, values are rounded up, to one decimal below 10 and to an integer above.
 /// Note: It returns `value` if `base` isn't positive.
 fn human_readable(value: u64, base: i64) -> String {
-    match base {
-        d if d < 0 => value.to_string(),
+    let units = match base {
+        d if d < 0 => return value.to_string(),
 
         // ref: [Binary prefix](https://en.wikipedia.org/wiki/Binary_prefix) @@ <https://archive.is/cnwmF>
         // ref: [SI/metric prefix](https://en.wikipedia.org/wiki/Metric_prefix) @@ <https://archive.is/QIuLj>
-        1000 => match NumberPrefix::decimal(value as f64) {
-            NumberPrefix::Standalone(bytes) => bytes.to_string(),
-            NumberPrefix::Prefixed(prefix, bytes) => format!("{:.1}{}", bytes, prefix.symbol()),
-        },
-
-        1024 => match NumberPrefix::binary(value as f64) {
-            NumberPrefix::Standalone(bytes) => bytes.to_string(),
-            NumberPrefix::Prefixed(prefix, bytes) => format!("{:.1}{}", bytes, prefix.symbol()),
-        },
+        1000 => ['k', 'M', 'G', 'T', 'P', 'E'],
+        1024 => ['K', 'M', 'G', 'T', 'P', 'E'],
 
         _ => crash!(EXIT_ERR, "Internal error: Unknown base value {}", base),
+    };
+    let base = base as u128;
+    let value = value as u128;
+
+    let mut power = 0;
+    let mut divisor = 1;
+    while power < units.len() && value >= divisor * base {
+        divisor *= base;
+        power += 1;
+    }
+    if power == 0 {
+        return value.to_string();
+    }
+
+    let integer = value / divisor;
+    let remainder = value % divisor;
+    if integer < 10 {
+        let tenths = integer * 10 + (remainder * 10 + divisor - 1) / divisor;
+        return if tenths < 100 {
+            format!("{}.{}{}", tenths / 10, tenths % 10, units[power - 1])
+        } else {
+            format!("10{}", units[power - 1])
+        };
+    }
+
+    let rounded = if remainder > 0 { integer + 1 } else { integer };
+    if rounded == base && power < units.len() {
+        // e.g. 1023.5K is 1.0M
+        format!("1.0{}", units[power])
+    } else {
+        format!("{}{}", rounded, units[power - 1])
     }
 }
 
-fn use_size(free_size: u64, total_size: u64) -> String {
-    if total_size == 0 {
+/// A size in bytes, in 1K blocks or human readable.
+fn size_string(size: u64, opt: &Options) -> String {
+    if opt.human_readable_base == -1 {
+        ((size + 1023) / 1024).to_string()
+    } else {
+        human_readable(size, opt.human_readable_base)
+    }
+}
+
+/// The percentage of `used` in `used + available`, rounded up like in GNU df.
+fn use_size(used: u64, available: u64) -> String {
+    let total = used as u128 + available as u128;
+    if total == 0 {
         return String::from("-");
     }
-    return format!(
-        "{:.0}%",
-        100f64 - 100f64 * (free_size as f64 / total_size as f64)
-    );
+    format!("{}%", (used as u128 * 100 + total - 1) / total)
 }
 
 pub fn uumain(args: impl uucore::Args) -> i32 {
@@ -804,8 +955,12 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .arg(
             Arg::with_name(OPT_OUTPUT)
                 .long("output")
+                .value_name("FIELD_LIST")
                 .takes_value(true)
+                .min_values(0)
+                .require_equals(true)
                 .use_delimiter(true)
+                .conflicts_with_all(&[OPT_INODES, OPT_PORTABILITY, OPT_PRINT_TYPE])
                 .help(
                     "use the output format defined by FIELD_LIST,\
                      or print all fields if FIELD_LIST is omitted.",
@@ -891,90 +1046,221 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         opt.fs_selector.exclude(fs_type.to_owned());
     }
 
-    let fs_list = filter_mount_list(read_fs_list(), &paths, &opt)
-        .into_iter()
-        .filter_map(Filesystem::new)
-        .filter(|fs| fs.usage.blocks != 0 || opt.show_all_fs || opt.show_listed_fs)
-        .collect::<Vec<_>>();
+    let columns = if matches.is_present(OPT_OUTPUT) {
+        opt.show_output = true;
+        // `--output` without a list selects all the fields
+        match matches.values_of(OPT_OUTPUT) {
+            Some(fields) if fields.len() > 0 => {
+                let mut columns = vec![];
+                for field in fields {
+                    let column = match Column::parse(field) {
+                        Some(column) => column,
+                        None => {
+                            show_error!("option --output: field '{}' unknown", field);
+                            return EXIT_ERR;
+                        }
+                    };
+                    if columns.contains(&column) {
+                        show_error!("option --output: field '{}' used more than once", field);
+                        return EXIT_ERR;
+                    }
+                    columns.push(column);
+                }
+                columns
+            }
+            _ => ALL_COLUMNS.to_vec(),
+        }
+    } else {
+        let mut columns = vec![Column::Source];
+        if opt.show_fs_type {
+            columns.push(Column::Fstype);
+        }
+        if opt.show_inode_instead {
+            columns.extend_from_slice(&[
+                Column::Itotal,
+                Column::Iused,
+                Column::Iavail,
+                Column::Ipcent,
+            ]);
+        } else {
+            columns.extend_from_slice(&[Column::Size, Column::Used, Column::Avail, Column::Pcent]);
+        }
+        columns.push(Column::Target);
+        columns
+    };
 
-    // set headers
-    let mut header = vec!["Filesystem"];
-    if opt.show_fs_type {
-        header.push("Type");
-    }
-    header.extend_from_slice(&if opt.show_inode_instead {
-        ["Inodes", "Iused", "IFree", "IUses%"]
+    let mut exit_code = EXIT_OK;
+    let fs_list: Vec<(Filesystem, &str)> = if paths.is_empty() {
+        filter_mount_list(read_fs_list(), &opt)
+            .into_iter()
+            .filter_map(Filesystem::new)
+            .filter(|fs| fs.usage.blocks != 0 || opt.show_all_fs)
+            .map(|fs| (fs, "-"))
+            .collect()
     } else {
-        [
-            if opt.human_readable_base == -1 {
-                "1k-blocks"
+        // Listed paths show their file system whatever its kind, like GNU
+        opt.show_listed_fs = true;
+        let mount_list: Vec<MountInfo> = read_fs_list()
+            .into_iter()
+            .filter(|mi| is_selected(mi, &opt))
+            .collect();
+        let mut fs_list = vec![];
+        for path in &paths {
+            match find_mount(&mount_list, path) {
+                Ok(Some(mi)) => {
+                    if let Some(fs) = Filesystem::new(mi.clone()) {
+                        fs_list.push((fs, path.as_str()));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    show_error!("{}: {}", path, e);
+                    exit_code = EXIT_ERR;
+                }
+            }
+        }
+        fs_list
+    };
+
+    let mut rows: Vec<Vec<String>> = vec![columns
+        .iter()
+        .map(|column| column.header(&opt).to_string())
+        .collect()];
+    for (fs, file) in fs_list.iter() {
+        rows.push(
+            columns
+                .iter()
+                .map(|column| column.value(fs, &opt, file))
+                .collect(),
+        );
+    }
+
+    print_table(&rows, &columns);
+
+    exit_code
+}
+
+/// Print `rows` in columns as wide as their widest cell, with text left aligned and numbers
+/// right aligned.
+fn print_table(rows: &[Vec<String>], columns: &[Column]) {
+    let mut widths = vec![0; columns.len()];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in rows {
+        let mut line = String::new();
+        for (idx, cell) in row.iter().enumerate() {
+            if idx > 0 {
+                line.push(' ');
+            }
+            if !columns[idx].is_text() {
+                line.push_str(&format!("{0: >1$}", cell, widths[idx]));
+            } else if idx < row.len() - 1 {
+                line.push_str(&format!("{0: <1$}", cell, widths[idx]));
             } else {
-                "Size"
-            },
-            "Used",
-            "Available",
-            "Use%",
-        ]
-    });
-    header.push("Mounted on");
-
-    for (idx, title) in header.iter().enumerate() {
-        if idx == 0 || idx == header.len() - 1 {
-            print!("{0: <16} ", title);
-        } else if opt.show_fs_type && idx == 1 {
-            print!("{0: <5} ", title);
-        } else if idx == header.len() - 2 {
-            print!("{0: >5} ", title);
-        } else {
-            print!("{0: >12} ", title);
+                line.push_str(cell);
+            }
         }
+        println!("{}", line);
     }
-    println!();
-    for fs in fs_list.iter() {
-        print!("{0: <16} ", fs.mountinfo.dev_name);
-        if opt.show_fs_type {
-            print!("{0: <5} ", fs.mountinfo.fs_type);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_readable() {
+        let cases = [
+            (0, "0", "0"),
+            (999, "999", "999"),
+            (1000, "1000", "1.0k"),
+            (1024, "1.0K", "1.1k"),
+            (1025, "1.1K", "1.1k"),
+            (10 * 1024 - 1, "10K", "11k"),
+            (10 * 1024 + 1, "11K", "11k"),
+            (1024 * 1024 - 1, "1.0M", "1.1M"),
+            (4 * 1024 * 1024 * 1024, "4.0G", "4.3G"),
+            (12_345_678_901, "12G", "13G"),
+            (std::u64::MAX, "16E", "19E"),
+        ];
+        for &(value, binary, decimal) in cases.iter() {
+            assert_eq!(human_readable(value, 1024), binary);
+            assert_eq!(human_readable(value, 1000), decimal);
         }
-        if opt.show_inode_instead {
-            print!(
-                "{0: >12} ",
-                human_readable(fs.usage.files, opt.human_readable_base)
-            );
-            print!(
-                "{0: >12} ",
-                human_readable(fs.usage.files - fs.usage.ffree, opt.human_readable_base)
-            );
-            print!(
-                "{0: >12} ",
-                human_readable(fs.usage.ffree, opt.human_readable_base)
-            );
-            print!(
-                "{0: >5} ",
-                format!(
-                    "{0:.1}%",
-                    100f64 - 100f64 * (fs.usage.ffree as f64 / fs.usage.files as f64)
-                )
-            );
-        } else {
-            let total_size = fs.usage.blocksize * fs.usage.blocks;
-            let free_size = fs.usage.blocksize * fs.usage.bfree;
-            print!(
-                "{0: >12} ",
-                human_readable(total_size, opt.human_readable_base)
-            );
-            print!(
-                "{0: >12} ",
-                human_readable(total_size - free_size, opt.human_readable_base)
-            );
-            print!(
-                "{0: >12} ",
-                human_readable(free_size, opt.human_readable_base)
-            );
-            print!("{0: >5} ", use_size(free_size, total_size));
+        assert_eq!(human_readable(1024, -1), "1024");
+    }
+
+    fn filesystem(files: u64, ffree: u64) -> Filesystem {
+        Filesystem {
+            mountinfo: MountInfo {
+                dev_id: "1".to_string(),
+                dev_name: "/dev/sda1".to_string(),
+                fs_type: "ext4".to_string(),
+                mount_dir: "/".to_string(),
+                mount_option: "rw".to_string(),
+                mount_root: "/".to_string(),
+                remote: false,
+                dummy: false,
+            },
+            usage: FsUsage {
+                blocksize: 4096,
+                blocks: 1000,
+                bfree: 600,
+                bavail: 500,
+                bavail_top_bit_set: false,
+                files,
+                ffree,
+            },
         }
-        print!("{0: <16}", fs.mountinfo.mount_dir);
-        println!();
     }
 
-    EXIT_OK
+    fn inode_values(fs: &Filesystem, opt: &Options) -> Vec<String> {
+        [
+            Column::Itotal,
+            Column::Iused,
+            Column::Iavail,
+            Column::Ipcent,
+        ]
+        .iter()
+        .map(|column| column.value(fs, opt, "-"))
+        .collect()
+    }
+
+    #[test]
+    fn test_inode_columns() {
+        let mut opt = Options::new();
+        let fs = filesystem(2_000_000, 1_500_000);
+        assert_eq!(
+            inode_values(&fs, &opt),
+            ["2000000", "500000", "1500000", "25%"]
+        );
+        assert_eq!(Column::Itotal.header(&opt), "Inodes");
+        assert_eq!(Column::Ipcent.header(&opt), "IUse%");
+
+        opt.human_readable_base = 1024;
+        assert_eq!(inode_values(&fs, &opt), ["2.0M", "489K", "1.5M", "25%"]);
+        opt.human_readable_base = 1000;
+        assert_eq!(inode_values(&fs, &opt), ["2.0M", "500k", "1.5M", "25%"]);
+
+        let fs = filesystem(0, 0);
+        assert_eq!(inode_values(&fs, &opt), ["-", "-", "-", "-"]);
+    }
+
+    #[test]
+    fn test_block_columns() {
+        let mut opt = Options::new();
+        let fs = filesystem(0, 0);
+        let values: Vec<String> = [Column::Size, Column::Used, Column::Avail, Column::Pcent]
+            .iter()
+            .map(|column| column.value(&fs, &opt, "-"))
+            .collect();
+        assert_eq!(values, ["4000", "1600", "2000", "45%"]);
+        assert_eq!(Column::Size.header(&opt), "1K-blocks");
+        opt.human_readable_base = 1024;
+        assert_eq!(Column::Size.header(&opt), "Size");
+    }
 }
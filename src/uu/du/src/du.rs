@@ -61,6 +61,35 @@ struct Options {
     separate_dirs: bool,
 }
 
+/// The `--threshold`: entries smaller than `Lower`, or larger than `Upper`, aren't printed.
+#[derive(Clone, Copy)]
+enum Threshold {
+    Lower(u64),
+    Upper(u64),
+}
+
+impl Threshold {
+    fn parse(s: &str) -> Option<Threshold> {
+        if s.starts_with('-') {
+            // like in GNU du, an upper threshold of 0 isn't allowed
+            match unit_string_to_number(&s[1..])? {
+                0 => None,
+                size => Some(Threshold::Upper(size)),
+            }
+        } else {
+            let s = if s.starts_with('+') { &s[1..] } else { s };
+            unit_string_to_number(s).map(Threshold::Lower)
+        }
+    }
+
+    fn should_exclude(self, size: u64) -> bool {
+        match self {
+            Threshold::Lower(threshold) => size < threshold,
+            Threshold::Upper(threshold) => size > threshold,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Clone, Copy)]
 struct FileInfo {
     file_id: u128,
@@ -292,7 +321,8 @@ fn du(
                             }
                             my_stat.size += this_stat.size;
                             my_stat.blocks += this_stat.blocks;
-                            if options.all {
+                            // files are one level below this directory
+                            if options.all && options.max_depth.map_or(true, |max| depth < max) {
                                 stats.push(this_stat);
                             }
                         }
@@ -460,6 +490,14 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             "N",
         )
         // In main
+        .optopt(
+            "t",
+            "threshold",
+            "exclude entries smaller than SIZE if positive,
+            or entries greater than SIZE if negative",
+            "SIZE",
+        )
+        // In main
         .optflagopt(
             "",
             "time",
@@ -494,6 +532,17 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         (Some(_), Some(_)) | (None, _) => { /* valid */ }
     }
 
+    let threshold = match matches.opt_str("threshold") {
+        Some(s) => match Threshold::parse(&s) {
+            Some(threshold) => Some(threshold),
+            None => {
+                show_error!("invalid --threshold argument '{}'", s);
+                return 1;
+            }
+        },
+        None => None,
+    };
+
     let options = Options {
         all: matches.opt_present("all"),
         program_name: NAME.to_owned(),
@@ -572,6 +621,8 @@ Try '{} --help' for more information.",
                         // See: http://linux.die.net/man/2/stat
                         stat.blocks * 512
                     };
+                    let print = (!summarize || index == len - 1)
+                        && !threshold.map_or(false, |threshold| threshold.should_exclude(size));
                     if matches.opt_present("time") {
                         let tm = {
                             let secs = {
@@ -596,7 +647,7 @@ Try '{} --help' for more information.",
                             };
                             DateTime::<Local>::from(UNIX_EPOCH + Duration::from_secs(secs))
                         };
-                        if !summarize || index == len - 1 {
+                        if print {
                             let time_str = tm.format(time_format_str).to_string();
                             print!(
                                 "{}\t{}\t{}{}",
@@ -606,7 +657,7 @@ Try '{} --help' for more information.",
                                 line_separator
                             );
                         }
-                    } else if !summarize || index == len - 1 {
+                    } else if print {
                         print!(
                             "{}\t{}{}",
                             convert_size(size),
@@ -12,10 +12,14 @@
 #[macro_use]
 extern crate clap;
 
+mod split_string;
+
 use clap::{App, AppSettings, Arg};
 use ini::Ini;
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::env;
+use std::ffi::OsString;
 use std::io::{self, Write};
 use std::iter::Iterator;
 use std::process::Command;
@@ -117,6 +121,60 @@ fn build_command<'a, 'b>(args: &'a mut Vec<&'b str>) -> (Cow<'b, str>, &'a [&'b
     (progname, &args[..])
 }
 
+/// Replace the `-S`/`--split-string` options in `args` with the arguments their strings split
+/// into, so that these are parsed like the others, up to the start of the command.
+fn expand_split_strings(args: impl uucore::Args) -> Result<Vec<OsString>, i32> {
+    let mut args: VecDeque<OsString> = args.collect();
+    let mut expanded: Vec<OsString> = args.pop_front().into_iter().collect();
+
+    while let Some(arg) = args.pop_front() {
+        let split = match arg.to_str() {
+            Some("-S") | Some("--split-string") => args.pop_front(),
+            Some(arg) if arg.starts_with("--split-string=") => {
+                Some(arg["--split-string=".len()..].into())
+            }
+            Some(arg) if arg.starts_with("-S") => Some(arg[2..].into()),
+            Some("-u") | Some("--unset") | Some("-f") | Some("--file") | Some("-C")
+            | Some("--chdir") => {
+                expanded.push(arg);
+                expanded.extend(args.pop_front());
+                continue;
+            }
+            Some(opt) if opt.starts_with('-') && opt != "-" && opt != "--" => {
+                expanded.push(arg);
+                continue;
+            }
+            // the rest are the variables to set and the command
+            _ => {
+                expanded.push(arg);
+                expanded.extend(args);
+                break;
+            }
+        };
+
+        let split = match split {
+            Some(split) => split,
+            // let clap complain about the missing string
+            None => {
+                expanded.push(arg);
+                continue;
+            }
+        };
+        let split_args = match split_string::split_string(&split.to_string_lossy()) {
+            Ok(split_args) => split_args,
+            Err(error) => {
+                eprintln!("env: {}", error);
+                return Err(125);
+            }
+        };
+        for split_arg in split_args.into_iter().rev() {
+            args.push_front(split_arg.into());
+        }
+    }
+
+    Ok(expanded)
+}
+
 fn create_app() -> App<'static, 'static> {
     App::new(crate_name!())
         .version(crate_version!())
@@ -130,7 +188,7 @@ fn create_app() -> App<'static, 'static> {
             .long("ignore-environment")
             .help("start with an empty environment"))
         .arg(Arg::with_name("chdir")
-            .short("c")
+            .short("C")
             .long("chdir")
             .takes_value(true)
             .number_of_values(1)
@@ -158,11 +216,18 @@ fn create_app() -> App<'static, 'static> {
             .value_name("NAME")
             .multiple(true)
             .help("remove variable from the environment"))
+        .arg(Arg::with_name("split-string")
+            .short("S")
+            .long("split-string")
+            .takes_value(true)
+            .value_name("S")
+            .help("process and split S into separate arguments; used to pass multiple arguments \
+                    on shebang lines"))
 }
 
 fn run_env(args: impl uucore::Args) -> Result<(), i32> {
     let app = create_app();
-    let matches = app.get_matches_from(args);
+    let matches = app.get_matches_from(expand_split_strings(args)?);
 
     let ignore_env = matches.is_present("ignore-environment");
     let null = matches.is_present("null");
@@ -186,17 +251,6 @@ fn run_env(args: impl uucore::Args) -> Result<(), i32> {
         program: vec![],
     };
 
-    // change directory
-    if let Some(d) = opts.running_directory {
-        match env::set_current_dir(d) {
-            Ok(()) => d,
-            Err(error) => {
-                eprintln!("env: cannot change directory to \"{}\": {}", d, error);
-                return Err(125);
-            }
-        };
-    }
-
     // we handle the name, value pairs and the program to be executed by treating them as external
     // subcommands in clap
     if let (external, Some(matches)) = matches.subcommand() {
@@ -250,6 +304,14 @@ fn run_env(args: impl uucore::Args) -> Result<(), i32> {
         env::set_var(name, val);
     }
 
+    // change directory, once any files are read
+    if let Some(d) = opts.running_directory {
+        if let Err(error) = env::set_current_dir(d) {
+            eprintln!("env: cannot change directory to \"{}\": {}", d, error);
+            return Err(125);
+        }
+    }
+
     if !opts.program.is_empty() {
         // we need to execute a command
         let (prog, args) = build_command(&mut opts.program);
@@ -0,0 +1,200 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Splitting of the string of `-S`/`--split-string` into arguments, like in GNU env.
+//!
+//! Arguments are separated by whitespace, and a `#` at the start of one begins a comment up
+//! to the end of the string. In an argument:
+//!
+//! * `'...'` is taken literally, except for `\\` and `\'`;
+//! * in `"..."`, escapes and variables are replaced, and `\_` is a space;
+//! * `\_` outside of quotes separates arguments, and `\c` ends the string;
+//! * `\f`, `\n`, `\r`, `\t` and `\v` are control characters, and `\#`, `\$`, `\"`, `\'` and
+//!   `\\` are literal;
+//! * `${NAME}` is the value of the environment variable `NAME`, or empty.
+
+use std::env;
+
+const NO_TERMINATING_QUOTE: &str = "no terminating quote in -S string";
+const BACKSLASH_AT_END: &str = "invalid backslash at end of string in -S";
+
+/// Removes the first character of `rest` and returns it.
+fn next_char(rest: &mut &str) -> Option<char> {
+    let c = rest.chars().next()?;
+    *rest = &rest[c.len_utf8()..];
+    Some(c)
+}
+
+/// The character of the escape `\c`, for the escapes that are the same in and out of quotes.
+fn escape(c: char) -> Result<char, String> {
+    match c {
+        'f' => Ok('\x0C'),
+        'n' => Ok('\n'),
+        'r' => Ok('\r'),
+        't' => Ok('\t'),
+        'v' => Ok('\x0B'),
+        '#' | '$' | '"' | '\'' | '\\' => Ok(c),
+        _ => Err(format!("invalid sequence '\\{}' in -S", c)),
+    }
+}
+
+/// The value of the variable in `${NAME}` at the start of `rest`, which is after the `$`.
+fn expand_variable(rest: &mut &str) -> Result<String, String> {
+    let invalid = || {
+        format!(
+            "only ${{VARNAME}} expansion is supported, error at: ${}",
+            rest
+        )
+    };
+    if !rest.starts_with('{') {
+        return Err(invalid());
+    }
+    let name_len = rest[1..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or_else(|| rest.len() - 1);
+    let name = &rest[1..1 + name_len];
+    if name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit())
+        || !rest[1 + name_len..].starts_with('}')
+    {
+        return Err(invalid());
+    }
+    *rest = &rest[name_len + 2..];
+    Ok(env::var(name).unwrap_or_default())
+}
+
+/// Split the string of `-S` into arguments (see the module documentation).
+pub fn split_string(s: &str) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    let mut arg = String::new();
+    // whether there's an argument, even if it's empty like `''`
+    let mut in_arg = false;
+    let mut rest = s;
+
+    while let Some(c) = next_char(&mut rest) {
+        match c {
+            ' ' | '\t' | '\n' | '\x0B' | '\x0C' | '\r' => {
+                if in_arg {
+                    args.push(std::mem::take(&mut arg));
+                    in_arg = false;
+                }
+            }
+            '#' if !in_arg => break,
+            '\'' => {
+                in_arg = true;
+                loop {
+                    match next_char(&mut rest) {
+                        None => return Err(NO_TERMINATING_QUOTE.to_string()),
+                        Some('\'') => break,
+                        Some('\\') if rest.starts_with('\\') || rest.starts_with('\'') => {
+                            arg.push(next_char(&mut rest).unwrap());
+                        }
+                        Some(c) => arg.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_arg = true;
+                loop {
+                    match next_char(&mut rest) {
+                        None => return Err(NO_TERMINATING_QUOTE.to_string()),
+                        Some('"') => break,
+                        Some('\\') => match next_char(&mut rest) {
+                            None => return Err(BACKSLASH_AT_END.to_string()),
+                            Some('_') => arg.push(' '),
+                            Some('c') => {
+                                return Err(
+                                    "'\\c' must not appear in double-quoted -S string".to_string()
+                                )
+                            }
+                            Some(c) => arg.push(escape(c)?),
+                        },
+                        Some('$') => arg.push_str(&expand_variable(&mut rest)?),
+                        Some(c) => arg.push(c),
+                    }
+                }
+            }
+            '\\' => match next_char(&mut rest) {
+                None => return Err(BACKSLASH_AT_END.to_string()),
+                Some('_') => {
+                    if in_arg {
+                        args.push(std::mem::take(&mut arg));
+                        in_arg = false;
+                    }
+                }
+                Some('c') => break,
+                Some(c) => {
+                    arg.push(escape(c)?);
+                    in_arg = true;
+                }
+            },
+            '$' => {
+                let value = expand_variable(&mut rest)?;
+                // a variable that is empty doesn't make an argument by itself
+                in_arg = in_arg || !value.is_empty();
+                arg.push_str(&value);
+            }
+            c => {
+                arg.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(arg);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(s: &str) -> Vec<String> {
+        split_string(s).unwrap()
+    }
+
+    #[test]
+    fn separators() {
+        assert_eq!(split(" a\tb\n c "), ["a", "b", "c"]);
+        assert_eq!(split("a\\_b\\_\\_c"), ["a", "b", "c"]);
+        assert_eq!(split("a #b c"), ["a"]);
+        assert_eq!(split("a#b \\#c"), ["a#b", "#c"]);
+        assert_eq!(split("a \\c b"), ["a"]);
+        assert!(split("").is_empty());
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(split("'a b' \"c d\""), ["a b", "c d"]);
+        assert_eq!(split("'' x a''b"), ["", "x", "ab"]);
+        assert_eq!(
+            split("'it\\'s' 'a\\tb' 'a\\\\b'"),
+            ["it's", "a\\tb", "a\\b"]
+        );
+        assert_eq!(split("\"q\\\"d\" \"a\\_b\\tc\""), ["q\"d", "a b\tc"]);
+        assert_eq!(split("a\\tb"), ["a\tb"]);
+    }
+
+    #[test]
+    fn variables() {
+        env::set_var("SPLIT_STRING_TEST", "val");
+        assert_eq!(
+            split("x${SPLIT_STRING_TEST}y \"${SPLIT_STRING_TEST}\" '${SPLIT_STRING_TEST}'"),
+            ["xvaly", "val", "${SPLIT_STRING_TEST}"]
+        );
+        assert_eq!(split("a ${SPLIT_STRING_NOT_SET} b"), ["a", "b"]);
+    }
+
+    #[test]
+    fn invalid() {
+        for s in &[
+            "'a", "\"a", "a\\", "\\x", "\"\\c\"", "$V", "${V", "${1V}", "${}",
+        ] {
+            assert!(split_string(s).is_err(), "{:?}", s);
+        }
+    }
+}
@@ -38,33 +38,82 @@ fn get_usage() -> String {
     format!("{0} [OPTION]... [FILE]...", executable!())
 }
 
-fn tabstops_parse(s: String) -> Vec<usize> {
-    let words = s.split(',');
-
-    let nums = words
-        .map(|sn| {
-            sn.parse::<usize>()
-                .unwrap_or_else(|_| crash!(1, "{}\n", "tab size contains invalid character(s)"))
-        })
-        .collect::<Vec<usize>>();
-
-    if nums.iter().any(|&n| n == 0) {
-        crash!(1, "{}\n", "tab size cannot be 0");
+/// What happens to tabs after the last explicit tab stop.
+#[derive(Clone, Copy)]
+enum RemainingMode {
+    /// Each tab becomes a single space
+    None,
+    /// `/N`: tab stops at the multiples of N
+    Slash(usize),
+    /// `+N`: tab stops every N columns after the last explicit one
+    Plus(usize),
+}
+
+/// Parse the `--tabs` list, which is separated by commas or blanks, and whose last value
+/// may be prefixed with `/` or `+`.
+fn tabstops_parse(s: &str) -> (Vec<usize>, RemainingMode) {
+    let words: Vec<&str> = s
+        .split(&[',', ' ', '\t'][..])
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut nums = Vec::new();
+    let mut remaining_mode = RemainingMode::None;
+    for (i, word) in words.iter().enumerate() {
+        let (specifier, digits) = match word.chars().next() {
+            Some(c) if c == '/' || c == '+' => (Some(c), &word[1..]),
+            _ => (None, *word),
+        };
+        if let Some(i) = digits.find(|c: char| !c.is_ascii_digit()) {
+            crash!(
+                1,
+                "tab size contains invalid character(s): '{}'",
+                &digits[i..]
+            );
+        }
+        let n = match digits.parse::<usize>() {
+            Ok(n) => n,
+            // a lone `/` or `+`
+            Err(_) if digits.is_empty() => 0,
+            Err(_) => crash!(1, "tab stop is too large '{}'", digits),
+        };
+
+        match specifier {
+            Some(c) => {
+                if i + 1 != words.len() {
+                    crash!(1, "'{}' specifier only allowed with the last value", c);
+                }
+                remaining_mode = match (c, n) {
+                    (_, 0) => RemainingMode::None,
+                    ('/', n) => RemainingMode::Slash(n),
+                    (_, n) => RemainingMode::Plus(n),
+                };
+            }
+            None => {
+                if n == 0 {
+                    crash!(1, "tab size cannot be 0");
+                }
+                if nums.last().map_or(false, |&last| last >= n) {
+                    crash!(1, "tab sizes must be ascending");
+                }
+                nums.push(n);
+            }
+        }
     }
 
-    if let (false, _) = nums
-        .iter()
-        .fold((true, 0), |(acc, last), &n| (acc && last <= n, n))
-    {
-        crash!(1, "{}\n", "tab sizes must be ascending");
+    if nums.is_empty() {
+        if let RemainingMode::None = remaining_mode {
+            nums.push(DEFAULT_TABSTOP);
+        }
     }
 
-    nums
+    (nums, remaining_mode)
 }
 
 struct Options {
     files: Vec<String>,
     tabstops: Vec<usize>,
+    remaining_mode: RemainingMode,
     tspaces: String,
     iflag: bool,
     uflag: bool,
@@ -72,9 +121,9 @@ struct Options {
 
 impl Options {
     fn new(matches: &ArgMatches) -> Options {
-        let tabstops = match matches.value_of(options::TABS) {
-            Some(s) => tabstops_parse(s.to_string()),
-            None => vec![DEFAULT_TABSTOP],
+        let (tabstops, remaining_mode) = match matches.value_of(options::TABS) {
+            Some(s) => tabstops_parse(s),
+            None => (vec![DEFAULT_TABSTOP], RemainingMode::None),
         };
 
         let iflag = matches.is_present(options::INITIAL);
@@ -89,8 +138,12 @@ impl Options {
                 *pr = it;
                 ret
             })
+            .chain(match remaining_mode {
+                RemainingMode::None => None,
+                RemainingMode::Slash(n) | RemainingMode::Plus(n) => Some(n),
+            })
             .max()
-            .unwrap(); // length of tabstops is guaranteed >= 1
+            .unwrap(); // there is either a tab stop or a remaining mode
         let tspaces = repeat(' ').take(nspaces).collect();
 
         let files: Vec<String> = match matches.values_of(options::FILES) {
@@ -101,6 +154,7 @@ impl Options {
         Options {
             files,
             tabstops,
+            remaining_mode,
             tspaces,
             iflag,
             uflag,
@@ -159,14 +213,21 @@ fn open(path: String) -> BufReader<Box<dyn Read + 'static>> {
     }
 }
 
-fn next_tabstop(tabstops: &[usize], col: usize) -> usize {
-    if tabstops.len() == 1 {
-        tabstops[0] - col % tabstops[0]
-    } else {
-        match tabstops.iter().find(|&&t| t > col) {
-            Some(t) => t - col,
-            None => 1,
-        }
+fn next_tabstop(tabstops: &[usize], remaining_mode: RemainingMode, col: usize) -> usize {
+    if let ([size], RemainingMode::None) = (tabstops, remaining_mode) {
+        // a single tab size repeats
+        return size - col % size;
+    }
+    match tabstops.iter().find(|&&t| t > col) {
+        Some(t) => t - col,
+        None => match remaining_mode {
+            RemainingMode::None => 1,
+            RemainingMode::Slash(n) => n - col % n,
+            RemainingMode::Plus(n) => {
+                let last = tabstops.last().map_or(0, |&last| last);
+                n - (col - last) % n
+            }
+        },
     }
 }
 
@@ -232,7 +293,7 @@ fn expand(options: Options) {
                 match ctype {
                     Tab => {
                         // figure out how many spaces to the next tabstop
-                        let nts = next_tabstop(ts, col);
+                        let nts = next_tabstop(ts, options.remaining_mode, col);
                         col += nts;
 
                         // now dump out either spaces if we're expanding, or a literal tab if we're not
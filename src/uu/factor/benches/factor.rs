@@ -0,0 +1,111 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use uu_factor::{factor, factor_with_bound};
+
+fn pm1_smooth(c: &mut Criterion) {
+    // Semiprimes p × q with p just above 2⁴⁰, where p-1 is smooth, and
+    //  q = 8389163, where q-1 = 2 × 4194581 is not.
+    const Q: u64 = 8389163;
+    let inputs = [1099511628679 * Q, 1099511631781 * Q, 1099511632867 * Q];
+
+    let mut group = c.benchmark_group("factor_pm1_smooth");
+    for n in inputs.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
+            b.iter(|| factor(n));
+        });
+    }
+    group.finish()
+}
+
+fn word_size(c: &mut Criterion) {
+    // Inputs just below 2³² use 32-bit Montgomery arithmetic, those just
+    //  above use the 64-bit one.
+    let inputs = [
+        ("prime_below", 4294967291),
+        ("prime_above", 4294967311),
+        ("semiprime_below", 65519 * 65521),
+        ("semiprime_above", 65537 * 65539),
+    ];
+
+    let mut group = c.benchmark_group("factor_word_size");
+    for (name, n) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new(*name, n), n, |b, &n| {
+            b.iter(|| factor(n));
+        });
+    }
+    group.finish()
+}
+
+fn table_bound(c: &mut Criterion) {
+    // A run of consecutive odd integers, most of which have some small
+    //  factors: a low trial-division bound leaves those to rho, a high one
+    //  wastes time on the inputs which have none.
+    let inputs: Vec<u64> = (0..256).map(|i| (1 << 50) + 2 * i + 1).collect();
+
+    let mut group = c.benchmark_group("factor_table_bound");
+    for bound in [0, 16, 64, 256, 1024, 2131].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(bound), bound, |b, &bound| {
+            b.iter(|| {
+                for n in &inputs {
+                    factor_with_bound(*n, bound);
+                }
+            });
+        });
+    }
+    group.finish()
+}
+
+fn ecm_semiprime(c: &mut Criterion) {
+    // 62-bit semiprimes with balanced factors, where p-1 and q-1 are not
+    //  smooth, so the factorisation is left to ECM (or rho, if ECM fails).
+    let inputs = [2147483629 * 2147483587, 2147483579 * 2147483563];
+
+    let mut group = c.benchmark_group("factor_ecm_semiprime");
+    for n in inputs.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
+            b.iter(|| factor(n));
+        });
+    }
+    group.finish()
+}
+
+fn rho_semiprime(c: &mut Criterion) {
+    // 54-bit semiprimes with balanced factors, where p-1 and q-1 are not
+    //  smooth; they are below the threshold for ECM, so they are left to rho.
+    let inputs = [134217613 * 134217593, 134217509 * 134217497];
+
+    let mut group = c.benchmark_group("factor_rho_semiprime");
+    for n in inputs.iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(n), n, |b, &n| {
+            b.iter(|| factor(n));
+        });
+    }
+    group.finish()
+}
+
+fn small_factors(c: &mut Criterion) {
+    // Highly composite inputs, which trial division factors completely
+    let inputs = [
+        ("factorial_20", 2432902008176640000),
+        ("primorial_47", 614889782588491410),
+        ("smooth", 4279364369280000),
+    ];
+
+    let mut group = c.benchmark_group("factor_small_factors");
+    for (name, n) in inputs.iter() {
+        group.bench_with_input(BenchmarkId::new(*name, n), n, |b, &n| {
+            b.iter(|| factor(n));
+        });
+    }
+    group.finish()
+}
+
+criterion_group!(
+    benches,
+    small_factors,
+    pm1_smooth,
+    word_size,
+    table_bound,
+    ecm_semiprime,
+    rho_semiprime
+);
+criterion_main!(benches);
@@ -6,9 +6,10 @@
 // * that was distributed with this source code.
 
 //! Generate a table of the multiplicative inverses of p_i mod 2^64
-//! for the first 1027 odd primes (all 13 bit and smaller primes).
-//! You can supply a command line argument to override the default
-//! value of 1027 for the number of entries in the table.
+//! for the first 320 odd primes (all primes up to 2131).
+//! You can supply a command line argument, or set the
+//! `UU_FACTOR_TABLE_SIZE` environment variable, to override the default
+//! value of 320 for the number of entries in the table.
 //!
 //! 2 has no multiplicative inverse mode 2^64 because 2 | 2^64,
 //! and in any case divisibility by two is trivial by checking the LSB.
@@ -41,10 +42,12 @@ fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
     let mut file = File::create(&Path::new(&out_dir).join("prime_table.rs")).unwrap();
 
-    // By default, we print the multiplicative inverses mod 2^64 of the first 1k primes
+    // By default, we print the multiplicative inverses mod 2^64 of the first 320 primes
     const DEFAULT_SIZE: usize = 320;
+    println!("cargo:rerun-if-env-changed={}", SIZE_VAR);
     let n = args()
         .nth(1)
+        .or_else(|| env::var(SIZE_VAR).ok())
         .and_then(|s| s.parse::<usize>().ok())
         .unwrap_or(DEFAULT_SIZE);
 
@@ -91,6 +94,7 @@ fn test_generator_10001() {
     assert_eq!(prime_10001, Some(104_743));
 }
 
+const SIZE_VAR: &str = "UU_FACTOR_TABLE_SIZE";
 const MAX_WIDTH: usize = 102;
 const PREAMBLE: &str = r##"/*
 * This file is part of the uutils coreutils package.
@@ -0,0 +1,251 @@
+// * This file is part of the uutils coreutils package.
+// *
+// * For the full copyright and license information, please view the LICENSE file
+// * that was distributed with this source code.
+
+// spell-checker:ignore (ToDO) Baillie Feitsma Selfridge Jacobi
+
+// Baillie-PSW primality test: a strong Fermat test to base 2, followed by a
+//  strong Lucas test with Selfridge's parameters.  No composite passing both
+//  is known, and there are none below 2⁶⁴ (Feitsma & Galway's enumeration of
+//  the base-2 pseudoprimes).
+// This is slower than the deterministic Miller-Rabin used by `factor`, and is
+//  meant as an independent, adversarially-robust alternative to it.
+
+// Small primes, used to quickly dispose of inputs with small factors
+const SMALL_PRIMES: &[u64] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+fn add_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 + b as u128) % n as u128) as u64
+}
+
+fn sub_mod(a: u64, b: u64, n: u64) -> u64 {
+    if a >= b {
+        a - b
+    } else {
+        n - (b - a)
+    }
+}
+
+// x/2 mod n, for odd n
+fn half_mod(x: u64, n: u64) -> u64 {
+    if x % 2 == 0 {
+        x / 2
+    } else {
+        ((x as u128 + n as u128) / 2) as u64
+    }
+}
+
+fn pow_mod(mut a: u64, mut b: u64, n: u64) -> u64 {
+    let mut result = 1 % n;
+    while b > 0 {
+        if b & 1 != 0 {
+            result = mul_mod(result, a, n);
+        }
+        a = mul_mod(a, a, n);
+        b >>= 1;
+    }
+    result
+}
+
+// Strong Fermat (Miller-Rabin) test of odd n > 2, to a single base
+pub(crate) fn is_strong_probable_prime(n: u64, base: u64) -> bool {
+    debug_assert!(n > 2 && n % 2 == 1);
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut x = pow_mod(base % n, d, n);
+    if x <= 1 || x == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+// Jacobi symbol (a/n), for odd n
+fn jacobi(mut a: u64, mut n: u64) -> i32 {
+    debug_assert!(n % 2 == 1);
+    let mut result = 1;
+    a %= n;
+    while a != 0 {
+        while a % 2 == 0 {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+fn is_square(n: u64) -> bool {
+    let r = (n as f64).sqrt() as u64;
+    // Correct for the rounding errors of the floating-point square root
+    (r.saturating_sub(1)..=r + 1).any(|r| r.checked_mul(r) == Some(n))
+}
+
+// Strong Lucas test of odd n > 2, with Selfridge's parameters: D is the
+//  first of 5, -7, 9, -11, … for which (D/n) = -1, P = 1 and Q = (1 - D)/4.
+pub(crate) fn is_strong_lucas_probable_prime(n: u64) -> bool {
+    debug_assert!(n > 2 && n % 2 == 1);
+
+    // There is no suitable D when n is a square
+    if is_square(n) {
+        return false;
+    }
+
+    let mut d: i64 = 5;
+    loop {
+        let d_mod_n = if d > 0 {
+            d as u64 % n
+        } else {
+            sub_mod(0, (-d) as u64 % n, n)
+        };
+        match jacobi(d_mod_n, n) {
+            -1 => break,
+            // d shares a factor with n
+            0 if d.abs() as u64 != n => return false,
+            _ => {}
+        }
+        d = if d > 0 { -(d + 2) } else { -d + 2 };
+    }
+
+    let to_mod_n = |x: i64| {
+        if x >= 0 {
+            x as u64 % n
+        } else {
+            sub_mod(0, (-x) as u64 % n, n)
+        }
+    };
+    let dd = to_mod_n(d);
+    let q = to_mod_n((1 - d) / 4);
+
+    // n + 1 = k 2ˢ, with k odd
+    let s = (n as u128 + 1).trailing_zeros();
+    let k = (n as u128 + 1) >> s;
+
+    // Compute U_k, V_k and Q^k, going through the bits of k from the top;
+    //  P = 1, so U₁ = 1 and V₁ = P = 1.
+    let (mut u, mut v, mut q_k) = (1, 1, q);
+    for bit in (0..(127 - k.leading_zeros())).rev() {
+        // U₂ₖ = Uₖ Vₖ, V₂ₖ = Vₖ² - 2 Qᵏ
+        u = mul_mod(u, v, n);
+        v = sub_mod(mul_mod(v, v, n), add_mod(q_k, q_k, n), n);
+        q_k = mul_mod(q_k, q_k, n);
+
+        if (k >> bit) & 1 == 1 {
+            // Uₖ₊₁ = (P Uₖ + Vₖ)/2, Vₖ₊₁ = (D Uₖ + P Vₖ)/2
+            let u_next = half_mod(add_mod(u, v, n), n);
+            v = half_mod(add_mod(mul_mod(dd, u, n), v, n), n);
+            u = u_next;
+            q_k = mul_mod(q_k, q, n);
+        }
+    }
+
+    if u == 0 || v == 0 {
+        return true;
+    }
+    for _ in 1..s {
+        v = sub_mod(mul_mod(v, v, n), add_mod(q_k, q_k, n), n);
+        if v == 0 {
+            return true;
+        }
+        q_k = mul_mod(q_k, q_k, n);
+    }
+    false
+}
+
+pub(crate) fn is_prime(n: u64) -> bool {
+    for p in SMALL_PRIMES {
+        if n % p == 0 {
+            return n == *p;
+        }
+    }
+    if n < 2 {
+        return false;
+    }
+
+    is_strong_probable_prime(n, 2) && is_strong_lucas_probable_prime(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::miller_rabin;
+    use quickcheck::quickcheck;
+
+    // Base-2 strong pseudoprimes (OEIS A001262), as found by Feitsma
+    const BASE_2_PSEUDOPRIMES: &[u64] = &[
+        2047, 3277, 4033, 4681, 8321, 15841, 29341, 42799, 49141, 52633, 65281, 74665, 80581,
+        85489, 88357, 90751,
+    ];
+
+    // Strong Lucas pseudoprimes (OEIS A217255)
+    const LUCAS_PSEUDOPRIMES: &[u64] = &[
+        5459, 5777, 10877, 16109, 18971, 22499, 24569, 25199, 40309, 58519, 75077, 97439,
+    ];
+
+    #[test]
+    fn base_2_pseudoprimes() {
+        for n in BASE_2_PSEUDOPRIMES {
+            assert!(is_strong_probable_prime(*n, 2), "{}", n);
+            assert!(!is_prime(*n), "{} reported prime", n);
+        }
+
+        // A strong pseudoprime to all prime bases up to 37
+        assert!(is_strong_probable_prime(3825123056546413051, 2));
+        assert!(!is_prime(3825123056546413051));
+    }
+
+    #[test]
+    fn lucas_pseudoprimes() {
+        for n in LUCAS_PSEUDOPRIMES {
+            assert!(is_strong_lucas_probable_prime(*n), "{}", n);
+            assert!(!is_prime(*n), "{} reported prime", n);
+        }
+    }
+
+    #[test]
+    fn squares() {
+        for p in &[101, 65521, 4294967291] {
+            assert!(!is_prime(p * p));
+        }
+    }
+
+    #[test]
+    fn agrees_with_miller_rabin() {
+        for n in 0..100_000 {
+            assert_eq!(is_prime(n), miller_rabin::is_prime(n), "{}", n);
+        }
+        for n in std::u64::MAX - 10_000..=std::u64::MAX {
+            assert_eq!(is_prime(n), miller_rabin::is_prime(n), "{}", n);
+        }
+    }
+
+    quickcheck! {
+        fn agrees_with_miller_rabin_random(i: u64, j: u64) -> bool {
+            let n = i.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(j);
+            is_prime(n) == miller_rabin::is_prime(n)
+        }
+    }
+}
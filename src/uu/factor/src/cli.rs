@@ -9,59 +9,358 @@
 #[macro_use]
 extern crate uucore;
 
+use std::collections::BTreeMap;
 use std::error::Error;
+use std::fmt;
 use std::io::{self, stdin, stdout, BufRead, Write};
+use std::sync::mpsc::{self, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 mod factor;
-pub(crate) use factor::*;
+pub use factor::*;
 use uucore::InvalidEncodingHandling;
 
+mod baillie_psw;
+mod ecm;
 mod miller_rabin;
 pub mod numeric;
+mod pollard_pm1;
 mod rho;
-mod table;
+pub mod table;
 
 static SYNTAX: &str = "[OPTION] [NUMBER]...";
 static SUMMARY: &str = "Print the prime factors of the given number(s).
  If none are specified, read from standard input.";
 static LONG_HELP: &str = "";
 
-fn print_factors_str(num_str: &str, w: &mut impl io::Write) -> Result<(), Box<dyn Error>> {
-    num_str
-        .parse::<u64>()
-        .map_err(|e| e.into())
-        .and_then(|x| writeln!(w, "{}:{}", x, factor(x)).map_err(|e| e.into()))
+mod options {
+    pub const COUNT: &str = "count";
+    pub const COUNT_DISTINCT: &str = "count-distinct";
+    pub const EXPONENTS: &str = "exponents";
+    pub const JOBS: &str = "jobs";
+    pub const JSON: &str = "json";
 }
 
-pub fn uumain(args: impl uucore::Args) -> i32 {
-    let matches = app!(SYNTAX, SUMMARY, LONG_HELP).parse(
-        args.collect_str(InvalidEncodingHandling::Ignore)
-            .accept_any(),
-    );
-    let stdout = stdout();
-    let mut w = io::BufWriter::new(stdout.lock());
-
-    if matches.free.is_empty() {
+// An operand which could not be parsed as a number
+#[derive(Debug)]
+struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid positive integer", self.0)
+    }
+}
+
+impl Error for ParseError {}
+
+// Parse a decimal number, or a hexadecimal, octal or binary one if prefixed
+//  with `0x`, `0o` or `0b` respectively.
+fn parse_number(num_str: &str) -> Result<u128, ParseError> {
+    let prefix = num_str.get(..2).map(|p| p.to_ascii_lowercase());
+    let result = match prefix.as_deref() {
+        Some("0x") => u128::from_str_radix(&num_str[2..], 16),
+        Some("0o") => u128::from_str_radix(&num_str[2..], 8),
+        Some("0b") => u128::from_str_radix(&num_str[2..], 2),
+        _ => num_str.parse(),
+    };
+
+    result.map_err(|_| ParseError(num_str.to_owned()))
+}
+
+// How factorisations are output
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    /// `n: p p q`, each prime repeated as many times as it divides n
+    Plain,
+    /// `n: p^2 q`
+    Exponents,
+    /// `{"n":n,"factors":[[p,2],[q,1]]}`, one object per line
+    Json,
+    /// `3`, the number of prime factors counted with multiplicity
+    Count,
+    /// `2`, the number of distinct prime factors
+    CountDistinct,
+}
+
+fn print_factors_str(
+    num_str: &str,
+    w: &mut impl io::Write,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let x = parse_number(num_str)?;
+    if x <= std::u64::MAX as u128 {
+        let factors = factor(x as u64);
+        let pairs = factors.iter().map(|(p, exp)| (p as u128, exp));
+        print_factors(x, &factors, pairs, w, format)?;
+    } else {
+        let factors = factor_u128(x);
+        print_factors(x, &factors, factors.iter(), w, format)?;
+    }
+    Ok(())
+}
+
+fn print_factors(
+    n: u128,
+    factors: impl fmt::Display,
+    pairs: impl Iterator<Item = (u128, Exponent)>,
+    w: &mut impl io::Write,
+    format: Format,
+) -> io::Result<()> {
+    match format {
+        Format::Plain => writeln!(w, "{}:{}", n, factors),
+        Format::Exponents => writeln!(w, "{}:{:#}", n, factors),
+        Format::Json => {
+            write!(w, "{{\"n\":{},\"factors\":[", n)?;
+            for (i, (p, exp)) in pairs.enumerate() {
+                if i > 0 {
+                    write!(w, ",")?;
+                }
+                write!(w, "[{},{}]", p, exp)?;
+            }
+            writeln!(w, "]}}")
+        }
+        Format::Count => writeln!(w, "{}", pairs.map(|(_, exp)| exp as u64).sum::<u64>()),
+        Format::CountDistinct => writeln!(w, "{}", pairs.count()),
+    }
+}
+
+trait Output {
+    /// Factor `number` and output the result
+    fn push(&mut self, number: &str);
+    /// Make the results output so far visible
+    fn flush(&mut self) -> io::Result<()>;
+    /// Output all the remaining results; returns whether any number was invalid
+    fn finish(self) -> io::Result<bool>;
+}
+
+struct Serial<W: io::Write> {
+    w: W,
+    format: Format,
+    invalid: bool,
+}
+
+impl<W: io::Write> Output for Serial<W> {
+    fn push(&mut self, number: &str) {
+        if let Err(e) = print_factors_str(number, &mut self.w, self.format) {
+            show_info!("{}", e);
+            self.invalid = true;
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.w.flush()
+    }
+
+    fn finish(mut self) -> io::Result<bool> {
+        self.flush()?;
+        Ok(self.invalid)
+    }
+}
+
+// Factors numbers on a pool of worker threads; a separate thread writes the
+//  results out, in the same order as the corresponding inputs.
+struct Parallel {
+    jobs: mpsc::SyncSender<(usize, String)>,
+    count: usize,
+    writer: thread::JoinHandle<io::Result<bool>>,
+}
+
+impl Parallel {
+    fn new(n_jobs: usize, format: Format) -> Parallel {
+        let (jobs, job_rx) = mpsc::sync_channel::<(usize, String)>(2 * n_jobs);
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        let (result_tx, results) = mpsc::channel();
+
+        for _ in 0..n_jobs {
+            let job_rx = Arc::clone(&job_rx);
+            let result_tx = result_tx.clone();
+            thread::spawn(move || loop {
+                // Don't hold the lock while factoring.
+                let job = job_rx.lock().unwrap().recv();
+                let (i, number) = match job {
+                    Ok(job) => job,
+                    Err(_) => break,
+                };
+
+                let mut out = Vec::new();
+                let result = print_factors_str(&number, &mut out, format)
+                    .map(|()| out)
+                    .map_err(|e| e.to_string());
+                if result_tx.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        let writer = thread::spawn(move || {
+            let stdout = stdout();
+            let mut w = io::BufWriter::new(stdout.lock());
+
+            // Results arrive out of order, and are held back until all the
+            //  preceding ones were written.
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            let mut invalid = false;
+            loop {
+                let (i, result) = match results.try_recv() {
+                    Ok(r) => r,
+                    Err(TryRecvError::Empty) => {
+                        // Nothing to write for now; make the output so far visible.
+                        w.flush()?;
+                        match results.recv() {
+                            Ok(r) => r,
+                            Err(_) => break,
+                        }
+                    }
+                    Err(TryRecvError::Disconnected) => break,
+                };
+
+                pending.insert(i, result);
+                while let Some(result) = pending.remove(&next) {
+                    match result {
+                        Ok(out) => w.write_all(&out)?,
+                        Err(msg) => {
+                            show_info!("{}", msg);
+                            invalid = true;
+                        }
+                    }
+                    next += 1;
+                }
+            }
+
+            w.flush()?;
+            Ok(invalid)
+        });
+
+        Parallel {
+            jobs,
+            count: 0,
+            writer,
+        }
+    }
+}
+
+impl Output for Parallel {
+    fn push(&mut self, number: &str) {
+        // The queue is only closed once the writer failed, which `finish`
+        //  reports, so there's no point in pushing anything.
+        if self.jobs.send((self.count, number.to_owned())).is_ok() {
+            self.count += 1;
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The writer thread flushes whenever it runs out of results to output.
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<bool> {
+        let Parallel { jobs, writer, .. } = self;
+        // Closing the queue stops the workers once it is empty, which in turn
+        //  stops the writer once it has output all the results.
+        drop(jobs);
+        writer.join().unwrap()
+    }
+}
+
+// Factor the given numbers or, if there are none, the ones read from stdin.
+fn factor_numbers(numbers: &[String], out: &mut impl Output) -> io::Result<()> {
+    if numbers.is_empty() {
         let stdin = stdin();
 
         for line in stdin.lock().lines() {
-            for number in line.unwrap().split_whitespace() {
-                if let Err(e) = print_factors_str(number, &mut w) {
-                    show_warning!("{}: {}", number, e);
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    show_error!("{}", e);
+                    break;
                 }
+            };
+
+            for number in line.split_whitespace() {
+                out.push(number);
             }
+
+            // Flush after every line, so results are output as soon as their
+            //  input is available, rather than when the buffer fills up.
+            out.flush()?;
         }
     } else {
-        for number in &matches.free {
-            if let Err(e) = print_factors_str(number, &mut w) {
-                show_warning!("{}: {}", number, e);
-            }
+        for number in numbers {
+            out.push(number);
         }
     }
 
-    if let Err(e) = w.flush() {
-        show_error!("{}", e);
-    }
+    Ok(())
+}
+
+pub fn uumain(args: impl uucore::Args) -> i32 {
+    let matches = app!(SYNTAX, SUMMARY, LONG_HELP)
+        .optflag(
+            "h",
+            options::EXPONENTS,
+            "print repeated factors in the form p^e, unless e is 1",
+        )
+        .optflag("", options::JSON, "print one JSON object per number")
+        .optflag(
+            "",
+            options::COUNT,
+            "only print the number of prime factors, counted with multiplicity",
+        )
+        .optflag(
+            "",
+            options::COUNT_DISTINCT,
+            "only print the number of distinct prime factors",
+        )
+        .optopt(
+            "j",
+            options::JOBS,
+            "factor numbers on N threads; output order is preserved",
+            "N",
+        )
+        .parse(
+            args.collect_str(InvalidEncodingHandling::Ignore)
+                .accept_any(),
+        );
+    let format = if matches.opt_present(options::COUNT) {
+        Format::Count
+    } else if matches.opt_present(options::COUNT_DISTINCT) {
+        Format::CountDistinct
+    } else if matches.opt_present(options::JSON) {
+        Format::Json
+    } else if matches.opt_present(options::EXPONENTS) {
+        Format::Exponents
+    } else {
+        Format::Plain
+    };
+    let jobs = match matches.opt_str(options::JOBS) {
+        Some(s) => match s.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => crash!(1, "invalid number of jobs: '{}'", s),
+        },
+        None => 1,
+    };
 
-    0
+    let result = if jobs > 1 {
+        let mut pool = Parallel::new(jobs, format);
+        factor_numbers(&matches.free, &mut pool).and(pool.finish())
+    } else {
+        let stdout = stdout();
+        let mut serial = Serial {
+            w: io::BufWriter::new(stdout.lock()),
+            format,
+            invalid: false,
+        };
+        factor_numbers(&matches.free, &mut serial).and_then(|()| serial.finish())
+    };
+
+    match result {
+        Ok(false) => 0,
+        Ok(true) => 1,
+        Err(e) => {
+            show_error!("{}", e);
+            1
+        }
+    }
 }
@@ -0,0 +1,158 @@
+// * This file is part of the uutils coreutils package.
+// *
+// * For the full copyright and license information, please view the LICENSE file
+// * that was distributed with this source code.
+
+// spell-checker:ignore (ToDO) Lenstra Suyama
+
+use rand::distributions::{Distribution, Uniform};
+use rand::rngs::SmallRng;
+use rand::{thread_rng, SeedableRng};
+
+use crate::miller_rabin;
+use crate::numeric::*;
+
+// A point on a Montgomery curve B y² = x³ + A x² + x, in projective (X : Z)
+//  coordinates; the y coordinate is not needed for scalar multiplication.
+type Point<A> = (<A as Arithmetic>::ModInt, <A as Arithmetic>::ModInt);
+
+// A curve, given by (A + 2C : 4C), where A/C is the curve's A coefficient
+type Curve<A> = (<A as Arithmetic>::ModInt, <A as Arithmetic>::ModInt);
+
+// 2P
+fn double<A: Arithmetic>(n: A, (x, z): Point<A>, (a24, c24): Curve<A>) -> Point<A> {
+    let t0 = n.sub(x, z);
+    let t0 = n.mul(t0, t0);
+    let t1 = n.add(x, z);
+    let t1 = n.mul(t1, t1);
+    let z2 = n.mul(c24, t0);
+    let x2 = n.mul(z2, t1);
+    // t1 - t0 = 4 X Z
+    let t1 = n.sub(t1, t0);
+    let z2 = n.mul(n.add(z2, n.mul(a24, t1)), t1);
+    (x2, z2)
+}
+
+// P + Q, given P - Q
+fn add<A: Arithmetic>(
+    n: A,
+    (xp, zp): Point<A>,
+    (xq, zq): Point<A>,
+    (xd, zd): Point<A>,
+) -> Point<A> {
+    let u = n.mul(n.sub(xp, zp), n.add(xq, zq));
+    let v = n.mul(n.add(xp, zp), n.sub(xq, zq));
+    let s = n.add(u, v);
+    let d = n.sub(u, v);
+    (n.mul(zd, n.mul(s, s)), n.mul(xd, n.mul(d, d)))
+}
+
+// k P, computed with the Montgomery ladder
+fn multiply<A: Arithmetic>(n: A, k: u64, p: Point<A>, curve: Curve<A>) -> Point<A> {
+    debug_assert!(k > 0);
+    // Invariant: p1 - p0 = p
+    let mut p0 = p;
+    let mut p1 = double(n, p, curve);
+    for bit in (0..(63 - k.leading_zeros())).rev() {
+        if (k >> bit) & 1 == 1 {
+            p0 = add(n, p1, p0, p);
+            p1 = double(n, p1, curve);
+        } else {
+            p1 = add(n, p1, p0, p);
+            p0 = double(n, p0, curve);
+        }
+    }
+    p0
+}
+
+// The largest powers of all primes up to `bound` which are themselves ≤ `bound`
+fn prime_powers(bound: u64) -> Vec<u64> {
+    (2..=bound)
+        .filter(|p| miller_rabin::is_prime(*p))
+        .map(|p| {
+            let mut q = p;
+            while q <= bound / p {
+                q *= p;
+            }
+            q
+        })
+        .collect()
+}
+
+// Lenstra's elliptic-curve method: finds a prime divisor p of n when the
+//  order of some random curve mod p is smooth, meaning that all the prime
+//  powers dividing it are at most `bound`.
+// Only the first stage is implemented; up to `curves` curves are tried, so this
+//  always terminates, and returns `None` if no (non-trivial) divisor was found.
+pub(crate) fn find_divisor<A: Arithmetic>(n: A, curves: usize, bound: u64) -> Option<u64> {
+    #![allow(clippy::many_single_char_names)]
+    let m = n.modulus();
+    // Suyama's parametrisation needs σ ∉ {0, ±1, ±3, ±5}; n is odd and has no
+    //  small factors, so sampling from [6; n[ is enough.
+    debug_assert!(m > 6);
+    let range = Uniform::new(6, m);
+    let mut rng = SmallRng::from_rng(&mut thread_rng()).unwrap();
+    let prime_powers = prime_powers(bound);
+
+    for _ in 0..curves {
+        // Suyama's parametrisation, which ensures the curve's order is a
+        //  multiple of 12: u = σ² - 5, v = 4σ, P = (u³ : v³) and
+        //  (A + 2)/4 = (v - u)³ (3u + v) / 16u³v
+        let sigma = n.from_u64(range.sample(&mut rng));
+        let u = n.sub(n.mul(sigma, sigma), n.from_u64(5));
+        let v = n.mul(n.from_u64(4), sigma);
+        let u3 = n.mul(n.mul(u, u), u);
+        let v_u = n.sub(v, u);
+        let a24 = n.mul(n.mul(n.mul(v_u, v_u), v_u), n.add(n.add(u, n.add(u, u)), v));
+        let c24 = n.mul(n.mul(n.from_u64(16 % m), u3), v);
+        let curve = (a24, c24);
+
+        let mut p = (u3, n.mul(n.mul(v, v), v));
+        for q in &prime_powers {
+            p = multiply(n, *q, p, curve);
+        }
+
+        // P became the point at infinity mod some divisor of n iff Z ≡ 0 mod it
+        match gcd(n.to_u64(p.1), m) {
+            d if d == 1 || d == m => continue,
+            d => return Some(d),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiply_consistent() {
+        // (k₁ k₂) P = k₁ (k₂ P), compared projectively as X₁ Z₂ = X₂ Z₁
+        let n = Montgomery::<u64>::new(1099511627791);
+        let curve = (n.from_u64(123456789), n.from_u64(4));
+        let p = (n.from_u64(17), n.one());
+        for (k1, k2) in [(2, 3), (7, 11), (1024, 3), (2131, 2137)].iter() {
+            let a = multiply(n, k1 * k2, p, curve);
+            let b = multiply(n, *k1, multiply(n, *k2, p, curve), curve);
+            assert!(n.mul(a.0, b.1) == n.mul(b.0, a.1));
+        }
+    }
+
+    #[test]
+    fn finds_divisor() {
+        // Semiprimes without small factors, for which rho is at its slowest
+        for (p, q) in [(2147483659, 2147483693), (1073741827, 4294967311)].iter() {
+            let n = p * q;
+            let d = find_divisor(Montgomery::<u64>::new(n), 1000, 2000).expect("no divisor found");
+            assert!(d == *p || d == *q, "{} is not a prime factor of {}", d, n);
+        }
+    }
+
+    #[test]
+    fn bounded() {
+        // No curve can succeed when none is tried
+        let n = Montgomery::<u64>::new(2147483659 * 2147483693);
+        assert_eq!(find_divisor(n, 0, 2000), None);
+    }
+}
@@ -8,26 +8,28 @@
 use smallvec::SmallVec;
 use std::cell::RefCell;
 use std::fmt;
+use std::iter::FromIterator;
+use std::ops;
 
 use crate::numeric::{Arithmetic, Montgomery};
-use crate::{miller_rabin, rho, table};
+use crate::{baillie_psw, ecm, miller_rabin, pollard_pm1, rho, table};
 
-type Exponent = u8;
+pub(crate) type Exponent = u8;
 
 #[derive(Clone, Debug)]
-struct Decomposition(SmallVec<[(u64, Exponent); NUM_FACTORS_INLINE]>);
+struct Decomposition<T = u64>(SmallVec<[(T, Exponent); NUM_FACTORS_INLINE]>);
 
 // The number of factors to inline directly into a `Decomposition` object.
 // As a consequence of the Erdős–Kac theorem, the average number of prime factors
 // of integers < 10²⁵ ≃ 2⁸³ is 4, so we can use a slightly higher value.
 const NUM_FACTORS_INLINE: usize = 5;
 
-impl Decomposition {
-    fn one() -> Decomposition {
+impl<T: Copy + Eq> Decomposition<T> {
+    fn one() -> Decomposition<T> {
         Decomposition(SmallVec::new())
     }
 
-    fn add(&mut self, factor: u64, exp: Exponent) {
+    fn add(&mut self, factor: T, exp: Exponent) {
         debug_assert!(exp > 0);
 
         if let Some((_, e)) = self.0.iter_mut().find(|(f, _)| *f == factor) {
@@ -37,20 +39,13 @@ impl Decomposition {
         }
     }
 
-    #[cfg(test)]
-    fn product(&self) -> u64 {
-        self.0
-            .iter()
-            .fold(1, |acc, (p, exp)| acc * p.pow(*exp as u32))
-    }
-
-    fn get(&self, p: u64) -> Option<&(u64, u8)> {
+    fn get(&self, p: T) -> Option<&(T, u8)> {
         self.0.iter().find(|(q, _)| *q == p)
     }
 }
 
-impl PartialEq for Decomposition {
-    fn eq(&self, other: &Decomposition) -> bool {
+impl<T: Copy + Eq> PartialEq for Decomposition<T> {
+    fn eq(&self, other: &Decomposition<T>) -> bool {
         for p in &self.0 {
             if other.get(p.0) != Some(p) {
                 return false;
@@ -66,7 +61,7 @@ impl PartialEq for Decomposition {
         true
     }
 }
-impl Eq for Decomposition {}
+impl<T: Copy + Eq> Eq for Decomposition<T> {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Factors(RefCell<Decomposition>);
@@ -85,20 +80,129 @@ impl Factors {
         self.add(prime, 1)
     }
 
-    #[cfg(test)]
-    fn product(&self) -> u64 {
-        self.0.borrow().product()
+    /// Iterate over the distinct prime factors, in ascending order, together
+    /// with their exponents (which are always ≥ 1).
+    pub fn iter(&self) -> impl Iterator<Item = (u64, Exponent)> {
+        let mut v = self.0.borrow_mut();
+        v.0.sort_unstable();
+        v.0.clone().into_iter()
     }
-}
 
-impl fmt::Display for Factors {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let v = &mut (self.0).borrow_mut().0;
-        v.sort_unstable();
+    /// Euler's totient φ(n), where n is the number being factored.
+    ///
+    /// # Panics
+    /// Panics if the result overflows, see [`Factors::checked_totient`].
+    pub fn totient(&self) -> u64 {
+        self.checked_totient().expect("totient overflows u64")
+    }
+
+    /// Euler's totient φ(n) = ∏ pᵉ⁻¹ (p - 1), or `None` on overflow.
+    pub fn checked_totient(&self) -> Option<u64> {
+        self.0.borrow().0.iter().try_fold(1u64, |acc, (p, exp)| {
+            p.checked_pow(*exp as u32 - 1)?
+                .checked_mul(p - 1)?
+                .checked_mul(acc)
+        })
+    }
+
+    /// The number of divisors τ(n), where n is the number being factored.
+    ///
+    /// # Panics
+    /// Panics if the result overflows, see [`Factors::checked_divisor_count`].
+    pub fn divisor_count(&self) -> u64 {
+        self.checked_divisor_count()
+            .expect("divisor count overflows u64")
+    }
 
-        for (p, exp) in v.iter() {
+    /// The number of divisors τ(n) = ∏ (e + 1), or `None` on overflow.
+    pub fn checked_divisor_count(&self) -> Option<u64> {
+        self.0
+            .borrow()
+            .0
+            .iter()
+            .try_fold(1u64, |acc, (_, exp)| acc.checked_mul(*exp as u64 + 1))
+    }
+
+    /// The sum of divisors σ(n), where n is the number being factored.
+    ///
+    /// # Panics
+    /// Panics if the result overflows, see [`Factors::checked_divisor_sum`].
+    pub fn divisor_sum(&self) -> u64 {
+        self.checked_divisor_sum()
+            .expect("divisor sum overflows u64")
+    }
+
+    /// The sum of divisors σ(n) = ∏ (1 + p + … + pᵉ), or `None` on overflow.
+    pub fn checked_divisor_sum(&self) -> Option<u64> {
+        self.0.borrow().0.iter().try_fold(1u64, |acc, (p, exp)| {
+            // Sum the geometric series term by term, rather than computing
+            //  (pᵉ⁺¹ - 1)/(p - 1), as pᵉ⁺¹ may overflow even if σ(n) doesn't.
+            let mut term = 1u64;
+            let mut sum = 1u64;
             for _ in 0..*exp {
-                write!(f, " {}", p)?
+                term = term.checked_mul(*p)?;
+                sum = sum.checked_add(term)?;
+            }
+            sum.checked_mul(acc)
+        })
+    }
+
+    /// The number Ω(n) of prime factors of n, counted with multiplicity.
+    pub fn big_omega(&self) -> u64 {
+        self.0.borrow().0.iter().map(|(_, exp)| *exp as u64).sum()
+    }
+
+    /// The number ω(n) of distinct prime factors of n.
+    pub fn omega(&self) -> u64 {
+        self.0.borrow().0.len() as u64
+    }
+
+    /// The smallest prime factor of n, or `None` if n = 1.
+    pub fn smallest_prime_factor(&self) -> Option<u64> {
+        self.0.borrow().0.iter().map(|(p, _)| *p).min()
+    }
+
+    /// The largest prime factor of n, or `None` if n = 1.
+    pub fn largest_prime_factor(&self) -> Option<u64> {
+        self.0.borrow().0.iter().map(|(p, _)| *p).max()
+    }
+
+    /// Whether no prime divides the number being factored more than once.
+    pub fn is_squarefree(&self) -> bool {
+        self.0.borrow().0.iter().all(|(_, exp)| *exp == 1)
+    }
+
+    /// The radical rad(n), the product of the distinct prime factors of n.
+    ///
+    /// This can't overflow, as rad(n) ≤ n.
+    pub fn radical(&self) -> u64 {
+        self.0.borrow().0.iter().map(|(p, _)| p).product()
+    }
+
+    /// The number being factored, recomputed from its factors, or `None` if
+    /// the product overflows.
+    pub fn checked_product(&self) -> Option<u64> {
+        self.0.borrow().0.iter().try_fold(1u64, |acc, (p, exp)| {
+            p.checked_pow(*exp as u32)?.checked_mul(acc)
+        })
+    }
+}
+
+// The alternate form (`{:#}`) prints each prime once, as `p^e` when e > 1,
+//  like GNU factor's `--exponents`; the default form repeats each prime e times.
+impl<T: Copy + Ord + fmt::Display> fmt::Display for Decomposition<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (p, exp) in self.0.iter() {
+            if f.alternate() {
+                if *exp > 1 {
+                    write!(f, " {}^{}", p, exp)?
+                } else {
+                    write!(f, " {}", p)?
+                }
+            } else {
+                for _ in 0..*exp {
+                    write!(f, " {}", p)?
+                }
             }
         }
 
@@ -106,6 +210,185 @@ impl fmt::Display for Factors {
     }
 }
 
+impl fmt::Display for Factors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v = (self.0).borrow_mut();
+        v.0.sort_unstable();
+        fmt::Display::fmt(&*v, f)
+    }
+}
+
+// Multiplying factorisations adds up the exponents of each prime.
+impl ops::MulAssign<&Factors> for Factors {
+    fn mul_assign(&mut self, other: &Factors) {
+        for (prime, exp) in other.0.borrow().0.iter() {
+            self.add(*prime, *exp);
+        }
+    }
+}
+
+impl ops::Mul for &Factors {
+    type Output = Factors;
+
+    fn mul(self, other: &Factors) -> Factors {
+        let mut r = self.clone();
+        r *= other;
+        r
+    }
+}
+
+// Build a factorisation from primes, each repeated as many times as it divides
+//  the number being factored.
+impl FromIterator<u64> for Factors {
+    fn from_iter<I: IntoIterator<Item = u64>>(iter: I) -> Self {
+        let mut f = Factors::one();
+        for prime in iter {
+            f.push(prime);
+        }
+        f
+    }
+}
+
+// Build a factorisation from (prime, exponent) pairs.
+impl FromIterator<(u64, Exponent)> for Factors {
+    fn from_iter<I: IntoIterator<Item = (u64, Exponent)>>(iter: I) -> Self {
+        let mut f = Factors::one();
+        for (prime, exp) in iter {
+            f.add(prime, exp);
+        }
+        f
+    }
+}
+
+// Factorisations are serialised as a sequence of (prime, exponent) pairs, in
+//  ascending order of the primes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Factors {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Factors {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pairs = <Vec<(u64, Exponent)> as serde::Deserialize>::deserialize(deserializer)?;
+        if let Some((p, _)) = pairs.iter().find(|(_, exp)| *exp == 0) {
+            return Err(serde::de::Error::custom(format!(
+                "zero exponent for prime factor {}",
+                p
+            )));
+        }
+        Ok(pairs.into_iter().collect())
+    }
+}
+
+/// The factorisation of a 128-bit integer, as computed by [`factor_u128`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Factors128(RefCell<Decomposition<u128>>);
+
+impl Factors128 {
+    pub fn one() -> Factors128 {
+        Factors128(RefCell::new(Decomposition::one()))
+    }
+
+    pub fn add(&mut self, prime: u128, exp: Exponent) {
+        debug_assert!(miller_rabin::is_prime_u128(prime));
+        self.0.borrow_mut().add(prime, exp)
+    }
+
+    pub fn push(&mut self, prime: u128) {
+        self.add(prime, 1)
+    }
+
+    /// Iterate over the distinct prime factors, in ascending order, together
+    /// with their exponents (which are always ≥ 1).
+    pub fn iter(&self) -> impl Iterator<Item = (u128, Exponent)> {
+        let mut v = self.0.borrow_mut();
+        v.0.sort_unstable();
+        v.0.clone().into_iter()
+    }
+
+    #[cfg(test)]
+    fn product(&self) -> u128 {
+        self.0
+            .borrow()
+            .0
+            .iter()
+            .fold(1, |acc, (p, exp)| acc * p.pow(*exp as u32))
+    }
+}
+
+impl fmt::Display for Factors128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut v = (self.0).borrow_mut();
+        v.0.sort_unstable();
+        fmt::Display::fmt(&*v, f)
+    }
+}
+
+/// Deterministic primality test, valid for all 64-bit integers.
+pub fn is_prime(n: u64) -> bool {
+    miller_rabin::is_prime(n)
+}
+
+/// Primality tests which can be selected with [`is_prime_with`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrimalityTest {
+    /// Miller-Rabin with a fixed, deterministic basis; the default.
+    MillerRabin,
+    /// Baillie-PSW: a base-2 strong Fermat test followed by a strong Lucas test.
+    BailliePsw,
+}
+
+impl Default for PrimalityTest {
+    fn default() -> Self {
+        PrimalityTest::MillerRabin
+    }
+}
+
+/// Primality test, using the selected algorithm.
+pub fn is_prime_with(n: u64, test: PrimalityTest) -> bool {
+    match test {
+        PrimalityTest::MillerRabin => miller_rabin::is_prime(n),
+        PrimalityTest::BailliePsw => baillie_psw::is_prime(n),
+    }
+}
+
+/// The smallest prime ≥ `n`, or `None` if it doesn't fit in a `u64`.
+pub fn next_prime(n: u64) -> Option<u64> {
+    if n <= 2 {
+        return Some(2);
+    }
+
+    // Only odd candidates need to be tested
+    let mut candidate = n | 1;
+    while !is_prime(candidate) {
+        candidate = candidate.checked_add(2)?;
+    }
+    Some(candidate)
+}
+
+/// The largest prime ≤ `n`, or `None` if `n` < 2.
+pub fn prev_prime(n: u64) -> Option<u64> {
+    if n < 3 {
+        return if n == 2 { Some(2) } else { None };
+    }
+
+    // Only odd candidates need to be tested; 3 is prime, so this terminates.
+    let mut candidate = if n % 2 == 0 { n - 1 } else { n };
+    while !is_prime(candidate) {
+        candidate -= 2;
+    }
+    Some(candidate)
+}
+
+// Budget for the elliptic-curve method, before falling back to rho; it is
+//  only worth trying on large numbers, as rho is fast enough on the others.
+const ECM_THRESHOLD: u64 = 1 << 56;
+const ECM_CURVES: usize = 50;
+const ECM_BOUND: u64 = 500;
+
 fn _factor<A: Arithmetic + miller_rabin::Basis>(num: u64, f: Factors) -> Factors {
     use miller_rabin::Result::*;
 
@@ -131,14 +414,44 @@ fn _factor<A: Arithmetic + miller_rabin::Basis>(num: u64, f: Factors) -> Factors
         }
 
         Composite(d) => d,
-        Pseudoprime => rho::find_divisor::<A>(n),
+        Pseudoprime => pollard_pm1::find_divisor::<A>(n)
+            .or_else(|| {
+                if num >= ECM_THRESHOLD {
+                    ecm::find_divisor::<A>(n, ECM_CURVES, ECM_BOUND)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| rho::find_divisor::<A>(n)),
     };
 
     let f = _factor(divisor, f);
     _factor(num / divisor, f)
 }
 
-pub fn factor(mut n: u64) -> Factors {
+pub fn factor(n: u64) -> Factors {
+    factor_with_bound(n, std::u64::MAX)
+}
+
+/// Factor each of the numbers in `ns`, in order.
+///
+/// The trial-division table is a constant, generated at build time, so it is
+/// shared by all calls without any setup; this, like [`factor`], can be used
+/// concurrently from several threads.
+pub fn factor_all(ns: &[u64]) -> Vec<Factors> {
+    factor_iter(ns.iter().cloned()).collect()
+}
+
+/// Lazily factor each of the numbers in `ns`, in order; see [`factor_all`].
+pub fn factor_iter<I: IntoIterator<Item = u64>>(ns: I) -> impl Iterator<Item = Factors> {
+    ns.into_iter().map(factor)
+}
+
+/// Factor `n`, only using trial division for primes up to `bound`.
+///
+/// A lower bound saves time on inputs without small factors, at the expense
+/// of those which have some; see [`table::factor_with_bound`].
+pub fn factor_with_bound(mut n: u64, bound: u64) -> Factors {
     let mut factors = Factors::one();
 
     if n < 2 {
@@ -155,7 +468,7 @@ pub fn factor(mut n: u64) -> Factors {
         return factors;
     }
 
-    let (factors, n) = table::factor(n, factors);
+    let (factors, n) = table::factor_with_bound(n, factors, bound);
 
     if n < (1 << 32) {
         _factor::<Montgomery<u32>>(n, factors)
@@ -164,9 +477,62 @@ pub fn factor(mut n: u64) -> Factors {
     }
 }
 
+fn _factor_u128(num: u128, f: Factors128) -> Factors128 {
+    use miller_rabin::Result::*;
+
+    // Once the remainder fits in 64 bits, hand it over to the (much faster)
+    //  64-bit code path, which also takes care of trial division.
+    if num <= std::u64::MAX as u128 {
+        let mut r = f;
+        for (p, e) in factor(num as u64).0.borrow().0.iter() {
+            r.add(*p as u128, *e);
+        }
+        return r;
+    }
+
+    let n = Montgomery::<u128>::new(num);
+    let divisor = match miller_rabin::test_u128(n) {
+        Prime => {
+            let mut r = f;
+            r.push(num);
+            return r;
+        }
+
+        Composite(d) => d,
+        Pseudoprime => rho::find_divisor_u128(n),
+    };
+
+    let f = _factor_u128(divisor, f);
+    _factor_u128(num / divisor, f)
+}
+
+/// Factor integers which do not fit in 64 bits.
+///
+/// Inputs that do fit in a `u64` are forwarded to [`factor`]; larger ones
+///  go through 128-bit Miller-Rabin and Pollard-rho, until the remaining
+///  cofactors are small enough for the 64-bit code path.
+pub fn factor_u128(mut n: u128) -> Factors128 {
+    let mut factors = Factors128::one();
+
+    if n < 2 {
+        return factors;
+    }
+
+    let n_zeros = n.trailing_zeros();
+    if n_zeros > 0 {
+        factors.add(2, n_zeros as Exponent);
+        n >>= n_zeros;
+    }
+
+    _factor_u128(n, factors)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{factor, Decomposition, Exponent, Factors};
+    use super::{
+        factor, factor_all, factor_iter, factor_u128, factor_with_bound, is_prime, is_prime_with,
+        next_prime, prev_prime, Decomposition, Exponent, Factors, PrimalityTest,
+    };
     use quickcheck::quickcheck;
     use smallvec::smallvec;
     use std::cell::RefCell;
@@ -178,21 +544,21 @@ mod tests {
             (2423, 1),
             (40961, 2)
         ])));
-        assert_eq!(factor(f.product()), f);
+        assert_eq!(factor(f.checked_product().unwrap()), f);
     }
 
     #[test]
     fn factor_recombines_small() {
         assert!((1..10_000)
             .map(|i| 2 * i + 1)
-            .all(|i| factor(i).product() == i));
+            .all(|i| factor(i).checked_product() == Some(i)));
     }
 
     #[test]
     fn factor_recombines_overflowing() {
         assert!((0..250)
             .map(|i| 2 * i + 2u64.pow(32) + 1)
-            .all(|i| factor(i).product() == i));
+            .all(|i| factor(i).checked_product() == Some(i)));
     }
 
     #[test]
@@ -202,24 +568,302 @@ mod tests {
         //  miller_rabbin::Result::Composite
         let pseudoprime = 17179869183;
         for _ in 0..20 {
-            // Repeat the test 20 times, as it only fails some fraction
-            // of the time.
-            assert!(factor(pseudoprime).product() == pseudoprime);
+            // Repeat the test 20 times: the primality test is deterministic,
+            //  but rho picks its polynomials at random, so the buggy code path
+            //  was only taken some fraction of the time.
+            assert_eq!(factor(pseudoprime).checked_product(), Some(pseudoprime));
+        }
+    }
+
+    #[test]
+    fn is_prime_small() {
+        let primes: Vec<u64> = (0..100).filter(|n| is_prime(*n)).collect();
+        assert_eq!(
+            primes,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97
+            ]
+        );
+    }
+
+    #[test]
+    fn is_prime_carmichael() {
+        for n in &[561, 1105, 1729, 2465, 2821, 6601, 8911, 41041, 825265] {
+            assert!(!is_prime(*n), "Carmichael number {} reported prime", n);
+        }
+    }
+
+    #[test]
+    fn is_prime_strong_pseudoprime() {
+        // Strong pseudoprime to bases 2, 3, 5 and 7
+        assert!(!is_prime(3215031751));
+        assert!(is_prime(18446744073709551557));
+    }
+
+    #[test]
+    fn is_prime_with_selector() {
+        for test in &[PrimalityTest::MillerRabin, PrimalityTest::BailliePsw] {
+            assert!(is_prime_with(18446744073709551557, *test));
+            assert!(!is_prime_with(3215031751, *test));
+            assert!(!is_prime_with(2047, *test));
+        }
+    }
+
+    #[test]
+    fn next_prev_prime() {
+        assert_eq!(next_prime(0), Some(2));
+        assert_eq!(next_prime(2), Some(2));
+        assert_eq!(next_prime(3), Some(3));
+        assert_eq!(next_prime(14), Some(17));
+        assert_eq!(next_prime(2132), Some(2137));
+        assert_eq!(next_prime(18446744073709551557), Some(18446744073709551557));
+        assert_eq!(next_prime(18446744073709551558), None);
+        assert_eq!(next_prime(std::u64::MAX), None);
+
+        assert_eq!(prev_prime(0), None);
+        assert_eq!(prev_prime(1), None);
+        assert_eq!(prev_prime(2), Some(2));
+        assert_eq!(prev_prime(4), Some(3));
+        assert_eq!(prev_prime(16), Some(13));
+        assert_eq!(prev_prime(std::u64::MAX), Some(18446744073709551557));
+    }
+
+    #[test]
+    fn totient() {
+        use crate::numeric::gcd;
+
+        assert_eq!(Factors::one().totient(), 1);
+        for n in 1..3_000 {
+            let naive = (1..=n).filter(|k| gcd(*k, n) == 1).count() as u64;
+            assert_eq!(factor(n).totient(), naive, "φ({})", n);
+        }
+    }
+
+    #[test]
+    fn checked_totient_overflow() {
+        let mut f = factor(1 << 63);
+        assert_eq!(f.checked_totient(), Some(1 << 62));
+        f.push(3);
+        assert_eq!(f.checked_totient(), Some(1 << 63));
+        f.push(5);
+        assert_eq!(f.checked_totient(), None);
+    }
+
+    #[test]
+    fn divisors() {
+        assert_eq!(Factors::one().divisor_count(), 1);
+        assert_eq!(Factors::one().divisor_sum(), 1);
+        for n in 1..3_000 {
+            let divisors = (1..=n).filter(|d| n % d == 0);
+            assert_eq!(
+                factor(n).divisor_count(),
+                divisors.clone().count() as u64,
+                "τ({})",
+                n
+            );
+            assert_eq!(factor(n).divisor_sum(), divisors.sum::<u64>(), "σ({})", n);
+        }
+
+        // 6, 28 and 496 are perfect, 12 is abundant
+        for n in &[6, 28, 496] {
+            assert_eq!(factor(*n).divisor_sum(), 2 * n);
+        }
+        assert!(factor(12).divisor_sum() > 24);
+    }
+
+    #[test]
+    fn checked_divisor_sum_overflow() {
+        // σ(2⁶³) = 2⁶⁴ - 1, but computing it as (2⁶⁴ - 1)/(2 - 1) would overflow
+        assert_eq!(factor(1 << 63).checked_divisor_sum(), Some(std::u64::MAX));
+        assert_eq!(factor(1 << 63).checked_divisor_count(), Some(64));
+        assert_eq!(factor(3 << 62).checked_divisor_sum(), None);
+    }
+
+    #[test]
+    fn checked_product_overflow() {
+        let mut f = factor(1 << 63);
+        assert_eq!(f.checked_product(), Some(1 << 63));
+        f.push(2);
+        assert_eq!(f.checked_product(), None);
+        f = factor(std::u64::MAX);
+        f.push(3);
+        assert_eq!(f.checked_product(), None);
+    }
+
+    #[test]
+    fn mul_factors() {
+        let mut total = Factors::one();
+        for i in 1..=20 {
+            total *= &factor(i);
+        }
+        assert_eq!(total, factor(2432902008176640000)); // 20!
+
+        let (a, b) = (factor(720), factor(1001));
+        assert_eq!(&a * &b, factor(720 * 1001));
+        assert_eq!(&a * &Factors::one(), a);
+    }
+
+    #[test]
+    fn from_iter() {
+        let f: Factors = vec![2, 2, 3].into_iter().collect();
+        assert_eq!(f.checked_product(), Some(12));
+        assert_eq!(f, factor(12));
+
+        let f: Factors = vec![(5, 1), (2, 3), (3, 1)].into_iter().collect();
+        assert_eq!(f, factor(120));
+
+        let f: Factors = Vec::<u64>::new().into_iter().collect();
+        assert_eq!(f, Factors::one());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let f = factor(720);
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, "[[2,4],[3,2],[5,1]]");
+        assert_eq!(serde_json::from_str::<Factors>(&json).unwrap(), f);
+
+        let one: Factors = serde_json::from_str("[]").unwrap();
+        assert_eq!(one, Factors::one());
+        assert!(serde_json::from_str::<Factors>("[[2,0]]").is_err());
+    }
+
+    #[test]
+    fn omega() {
+        assert_eq!(factor(1).big_omega(), 0);
+        assert_eq!(factor(1).omega(), 0);
+        for p in &[2, 3, 65537, 18446744073709551557] {
+            assert_eq!(factor(*p).big_omega(), 1);
+            assert_eq!(factor(*p).omega(), 1);
+        }
+        assert_eq!(factor(1 << 63).big_omega(), 63);
+        assert_eq!(factor(1 << 63).omega(), 1);
+        assert_eq!(factor(3 * 3 * 3 * 3).big_omega(), 4);
+        // 720 = 2⁴ × 3² × 5
+        assert_eq!(factor(720).big_omega(), 7);
+        assert_eq!(factor(720).omega(), 3);
+    }
+
+    #[test]
+    fn extremal_prime_factors() {
+        assert_eq!(factor(1).smallest_prime_factor(), None);
+        assert_eq!(factor(1).largest_prime_factor(), None);
+
+        let p = 18446744073709551557;
+        assert_eq!(factor(p).smallest_prime_factor(), Some(p));
+        assert_eq!(factor(p).largest_prime_factor(), Some(p));
+
+        assert_eq!(factor(1 << 40).smallest_prime_factor(), Some(2));
+        assert_eq!(factor(1 << 40).largest_prime_factor(), Some(2));
+
+        // 600851475143 = 71 × 839 × 1471 × 6857
+        assert_eq!(factor(600851475143).smallest_prime_factor(), Some(71));
+        assert_eq!(factor(600851475143).largest_prime_factor(), Some(6857));
+    }
+
+    #[test]
+    fn squarefree_radical() {
+        assert!(factor(1).is_squarefree());
+        assert_eq!(factor(1).radical(), 1);
+
+        for p in &[2, 3, 65537, 18446744073709551557] {
+            assert!(factor(*p).is_squarefree());
+            assert_eq!(factor(*p).radical(), *p);
+        }
+        for p in &[2, 3, 65537, 4294967291] {
+            assert!(!factor(p * p).is_squarefree());
+            assert_eq!(factor(p * p).radical(), *p);
+        }
+
+        // 720 = 2⁴ × 3² × 5
+        assert!(!factor(720).is_squarefree());
+        assert_eq!(factor(720).radical(), 30);
+        assert!(factor(30).is_squarefree());
+    }
+
+    #[test]
+    fn iter_ascending() {
+        assert_eq!(Factors::one().iter().next(), None);
+        assert_eq!(
+            factor(2 * 3 * 5 * 120).iter().collect::<Vec<_>>(),
+            vec![(2, 4), (3, 2), (5, 2)]
+        );
+        assert_eq!(
+            factor_u128(3 << 100).iter().collect::<Vec<_>>(),
+            vec![(2, 100), (3, 1)]
+        );
+    }
+
+    #[test]
+    fn display_exponents() {
+        assert_eq!(format!("{:#}", Factors::one()), "");
+        assert_eq!(format!("{:#}", factor(7)), " 7");
+        assert_eq!(format!("{:#}", factor(120)), " 2^3 3 5");
+        assert_eq!(format!("{}", factor(120)), " 2 2 2 3 5");
+        assert_eq!(format!("{:#}", factor_u128(1 << 100)), " 2^100");
+    }
+
+    #[test]
+    fn factor_u128_prime() {
+        // 2¹²⁷ - 1 is a Mersenne prime
+        let m127 = (1u128 << 127) - 1;
+        assert_eq!(format!("{}", factor_u128(m127)), format!(" {}", m127));
+    }
+
+    #[test]
+    fn factor_u128_recombines() {
+        // (2³¹ - 1) × (2⁶¹ - 1), a product of two Mersenne primes
+        let m31: u128 = (1 << 31) - 1;
+        let m61: u128 = (1 << 61) - 1;
+        assert_eq!(factor_u128(m31 * m61).product(), m31 * m61);
+
+        assert_eq!(factor_u128(1 << 64).product(), 1 << 64);
+        assert_eq!(factor_u128(u128::MAX).product(), u128::MAX);
+    }
+
+    #[test]
+    fn factor_u128_small() {
+        assert!((1..1_000)
+            .map(|i| 2 * i + 1)
+            .all(|i| factor_u128(i).product() == i));
+    }
+
+    #[test]
+    fn factor_batch() {
+        let ns: Vec<u64> = (0..1000).map(|i| 1_000_003 * i + 7).collect();
+        let all = factor_all(&ns);
+        assert_eq!(all.len(), ns.len());
+        for (n, f) in ns.iter().zip(&all) {
+            assert_eq!(f.checked_product(), Some(*n));
+        }
+        assert!(factor_iter(ns.iter().cloned()).eq(all.into_iter()));
+        assert!(factor_all(&[]).is_empty());
+    }
+
+    #[test]
+    fn factor_with_bound_matches() {
+        let inputs: &[u64] = &[2 * 3 * 2131 * 2137, 1 << 40, 4294967291 * 3, 65521 * 65537];
+        for n in inputs {
+            for bound in &[0, 3, 100, 2131, std::u64::MAX] {
+                assert_eq!(factor_with_bound(*n, *bound), factor(*n));
+            }
         }
     }
 
     quickcheck! {
         fn factor_recombines(i: u64) -> bool {
-            i == 0 || factor(i).product() == i
+            i == 0 || factor(i).checked_product() == Some(i)
         }
 
         fn recombines_factors(f: Factors) -> () {
-            assert_eq!(factor(f.product()), f);
+            assert_eq!(factor(f.checked_product().unwrap()), f);
         }
 
         fn exponentiate_factors(f: Factors, e: Exponent) -> () {
             if e == 0 { return; }
-            if let Some(fe) = f.product().checked_pow(e.into()) {
+            if let Some(fe) = f.checked_product().unwrap().checked_pow(e.into()) {
                 assert_eq!(factor(fe), f ^ e);
             }
         }
@@ -268,7 +912,10 @@ impl std::ops::BitXor<Exponent> for Factors {
             r.add(*p, rhs * e);
         }
 
-        debug_assert_eq!(r.product(), self.product().pow(rhs.into()));
+        debug_assert_eq!(
+            r.checked_product(),
+            self.checked_product().unwrap().checked_pow(rhs.into())
+        );
         return r;
     }
 }
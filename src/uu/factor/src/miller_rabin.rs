@@ -31,15 +31,26 @@ impl Basis for Montgomery<u32> {
     ];
 }
 
+// No small deterministic basis is known for the whole 128b range; the first
+//  13 primes are a valid basis for all integers < 3.3·10²⁴ (Sorenson & Webster,
+//  2015), and the remaining ones make the test (very) probabilistic above that.
+const BASIS_U128: &[u128] = &[
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
 #[derive(Eq, PartialEq)]
 #[must_use = "Ignoring the output of a primality test."]
-pub(crate) enum Result {
+pub(crate) enum Result<T = u64> {
+    // The bases used for 32b and 64b moduli are deterministic, so `Prime`
+    //  is always correct there; see `BASIS_U128` for larger moduli.
     Prime,
+    // `n` is provably composite (a witness was found), but the test did not
+    //  yield a divisor; in particular, this never means "probably prime".
     Pseudoprime,
-    Composite(u64),
+    Composite(T),
 }
 
-impl Result {
+impl<T: Eq> Result<T> {
     pub(crate) fn is_prime(&self) -> bool {
         *self == Result::Prime
     }
@@ -95,15 +106,75 @@ pub(crate) fn test<A: Arithmetic + Basis>(m: A) -> Result {
     Prime
 }
 
-// Used by build.rs' tests and debug assertions
-#[allow(dead_code)]
+// Used by build.rs' tests, debug assertions, and the public `crate::is_prime`
 pub(crate) fn is_prime(n: u64) -> bool {
     if n < 2 {
         false
     } else if n % 2 == 0 {
         n == 2
     } else {
-        test::<Montgomery<u64>>(Montgomery::new(n)).is_prime()
+        test::<Montgomery<u64>>(Montgomery::<u64>::new(n)).is_prime()
+    }
+}
+
+// Same as `test`, for moduli which do not fit in 64 bits
+#[allow(clippy::many_single_char_names)]
+pub(crate) fn test_u128(m: Montgomery<u128>) -> Result<u128> {
+    use self::Result::*;
+
+    let n = m.modulus();
+    debug_assert!(n > 1);
+    debug_assert!(n % 2 != 0);
+
+    // n-1 = r 2ⁱ
+    let i = (n - 1).trailing_zeros();
+    let r = (n - 1) >> i;
+
+    let one = m.one();
+    let minus_one = m.minus_one();
+
+    'witness: for _a in BASIS_U128.iter() {
+        let _a = _a % n;
+        if _a == 0 {
+            continue;
+        }
+
+        let a = m.from_u128(_a);
+
+        // x = a^r mod n
+        let mut x = m.pow(a, r);
+
+        if x == one || x == minus_one {
+            continue;
+        }
+
+        for _ in 1..i {
+            let y = m.mul(x, x);
+            if y == one {
+                return Composite(gcd_u128(m.to_u128(x) - 1, m.modulus()));
+            } else if y == minus_one {
+                // This basis element is not a witness of `n` being composite.
+                // Keep looking.
+                continue 'witness;
+            }
+            x = y;
+        }
+
+        return Pseudoprime;
+    }
+
+    Prime
+}
+
+// Used by debug assertions
+#[allow(dead_code)]
+pub(crate) fn is_prime_u128(n: u128) -> bool {
+    if n <= std::u64::MAX as u128 {
+        is_prime(n as u64)
+    } else if n % 2 == 0 {
+        false
+    } else {
+        test_u128(Montgomery::<u128>::new(n)).is_prime()
     }
 }
 
@@ -177,6 +248,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn strong_pseudoprimes() {
+        // Smallest strong pseudoprimes to the bases {2}, {2, 3}, {2, 3, 5}, ...
+        // up to the first 9 primes (OEIS A014233)
+        for n in &[
+            2047,
+            1373653,
+            25326001,
+            3215031751,
+            2152302898747,
+            3474749660383,
+            341550071728321,
+            3825123056546413051,
+        ] {
+            assert!(!is_prime(*n), "{} reported prime", n);
+            assert!(
+                !test(Montgomery::<u64>::new(*n)).is_prime(),
+                "{} reported prime",
+                n
+            );
+        }
+    }
+
     #[test]
     fn issue_1556() {
         // 10 425 511 = 2441 × 4271
@@ -197,9 +291,24 @@ mod tests {
     }
     parametrized_check!(small_semiprimes);
 
+    #[test]
+    fn primes_u128() {
+        // Mersenne primes 2⁸⁹ - 1 and 2¹²⁷ - 1
+        assert!(is_prime_u128((1 << 89) - 1));
+        assert!(is_prime_u128((1 << 127) - 1));
+        // 2¹²⁸ - 1 = 3 × 5 × 17 × 257 × 641 × 65537 × ...
+        assert!(!is_prime_u128(u128::MAX));
+        // (2⁶¹ - 1)²
+        assert!(!is_prime_u128(((1 << 61) - 1) * ((1 << 61) - 1)));
+    }
+
     quickcheck! {
         fn composites(i: u64, j: u64) -> bool {
             i < 2 || j < 2 || !is_prime(i*j)
         }
+
+        fn composites_u128(i: u64, j: u64) -> bool {
+            i < 2 || j < 2 || !is_prime_u128(i as u128 * j as u128)
+        }
     }
 }
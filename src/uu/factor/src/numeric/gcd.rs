@@ -9,12 +9,33 @@
 use std::cmp::min;
 use std::mem::swap;
 
-pub fn gcd(mut u: u64, mut v: u64) -> u64 {
+use super::traits::Int;
+
+/// Greatest common divisor of `u` and `v`, with gcd(0, n) = gcd(n, 0) = n.
+pub fn gcd(u: u64, v: u64) -> u64 {
+    binary_gcd(u, v)
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it overflows.
+/// By convention, lcm(0, n) = lcm(n, 0) = 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub(crate) fn gcd_u128(u: u128, v: u128) -> u128 {
+    binary_gcd(u, v)
+}
+
+fn binary_gcd<T: Int>(mut u: T, mut v: T) -> T {
     // Stein's binary GCD algorithm
     // Base cases: gcd(n, 0) = gcd(0, n) = n
-    if u == 0 {
+    if u == T::zero() {
         return v;
-    } else if v == 0 {
+    } else if v == T::zero() {
         return u;
     }
 
@@ -23,29 +44,29 @@ pub fn gcd(mut u: u64, mut v: u64) -> u64 {
     let k = {
         let i = u.trailing_zeros();
         let j = v.trailing_zeros();
-        u >>= i;
-        v >>= j;
+        u = u >> i as usize;
+        v = v >> j as usize;
         min(i, j)
     };
 
     loop {
         // Loop invariant: u and v are odd
-        debug_assert!(u % 2 == 1, "u = {} is even", u);
-        debug_assert!(v % 2 == 1, "v = {} is even", v);
+        debug_assert!(u & T::one() == T::one(), "u = {} is even", u);
+        debug_assert!(v & T::one() == T::one(), "v = {} is even", v);
 
         // gcd(u, v) = gcd(|u - v|, min(u, v))
         if u > v {
             swap(&mut u, &mut v);
         }
-        v -= u;
+        v = v - u;
 
-        if v == 0 {
+        if v == T::zero() {
             // Reached the base case; gcd is 2ᵏ u
-            return u << k;
+            return u << k as usize;
         }
 
         // gcd(u, 2ʲ v) = gcd(u, v) as u is odd
-        v >>= v.trailing_zeros();
+        v = v >> v.trailing_zeros() as usize;
     }
 }
 
@@ -54,6 +75,21 @@ mod tests {
     use super::*;
     use quickcheck::quickcheck;
 
+    #[test]
+    fn lcm_small() {
+        assert_eq!(lcm(0, 5), Some(0));
+        assert_eq!(lcm(5, 0), Some(0));
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(7, 7), Some(7));
+        assert_eq!(lcm(1 << 63, 1 << 10), Some(1 << 63));
+    }
+
+    #[test]
+    fn lcm_overflow() {
+        assert_eq!(lcm(1 << 63, 3), None);
+        assert_eq!(lcm(std::u64::MAX, std::u64::MAX - 1), None);
+    }
+
     quickcheck! {
         fn euclidean(a: u64, b: u64) -> bool {
             // Test against the Euclidean algorithm
@@ -105,5 +141,22 @@ mod tests {
         fn linearity(a: u64, b: u64, k: u64) -> bool {
             gcd(a + k * b, b) == gcd(a, b)
         }
+
+        fn lcm_multiple(a: u64, b: u64) -> bool {
+            // lcm(a, b) is a multiple of both a and b, and lcm(a, b) gcd(a, b) = ab
+            match lcm(a, b) {
+                Some(0) => a == 0 || b == 0,
+                Some(l) => {
+                    l % a == 0
+                        && l % b == 0
+                        && l as u128 * gcd(a, b) as u128 == a as u128 * b as u128
+                }
+                None => false,
+            }
+        }
+
+        fn wide(a: u64, b: u64) -> bool {
+            gcd_u128(a as u128, b as u128) == gcd(a, b) as u128
+        }
     }
 }
@@ -6,7 +6,8 @@
 // * that was distributed with this source code.
 
 mod gcd;
-pub use gcd::gcd;
+pub(crate) use gcd::gcd_u128;
+pub use gcd::{gcd, lcm};
 
 pub(crate) mod traits;
 
@@ -19,6 +19,7 @@ pub(crate) trait Arithmetic: Copy + Sized {
     fn from_u64(&self, n: u64) -> Self::ModInt;
     fn to_u64(&self, n: Self::ModInt) -> u64;
     fn add(&self, a: Self::ModInt, b: Self::ModInt) -> Self::ModInt;
+    fn sub(&self, a: Self::ModInt, b: Self::ModInt) -> Self::ModInt;
     fn mul(&self, a: Self::ModInt, b: Self::ModInt) -> Self::ModInt;
 
     fn pow(&self, mut a: Self::ModInt, mut b: u64) -> Self::ModInt {
@@ -58,7 +59,7 @@ pub(crate) trait Arithmetic: Copy + Sized {
 }
 
 #[derive(Clone, Copy, Debug)]
-pub(crate) struct Montgomery<T: DoubleInt> {
+pub(crate) struct Montgomery<T> {
     a: T,
     n: T,
 }
@@ -158,6 +159,27 @@ impl<T: DoubleInt> Arithmetic for Montgomery<T> {
         r
     }
 
+    fn sub(&self, a: Self::ModInt, b: Self::ModInt) -> Self::ModInt {
+        // Both a and b are in [0; n[, so a + (n - b) cannot overflow if a < b
+        let r = if a >= b { a - b } else { a + (self.n - b) };
+
+        // Check that r (reduced back to the usual representation) equals
+        // a-b % n
+        #[cfg(debug_assertions)]
+        {
+            let a_r = self.to_u64(a) as u128;
+            let b_r = self.to_u64(b) as u128;
+            let r_r = self.to_u64(r);
+            let r_2 = ((a_r + self.n.as_u128() - b_r) % self.n.as_u128()) as u64;
+            debug_assert_eq!(
+                r_r, r_2,
+                "[{}] = {} ≠ {} = {} - {} = [{}] - [{}] mod {}; a = {}",
+                r, r_r, r_2, a_r, b_r, a, b, self.n, self.a
+            );
+        }
+        r
+    }
+
     fn mul(&self, a: Self::ModInt, b: Self::ModInt) -> Self::ModInt {
         let r = self.reduce(a.as_double_width() * b.as_double_width());
 
@@ -179,6 +201,114 @@ impl<T: DoubleInt> Arithmetic for Montgomery<T> {
     }
 }
 
+// Full 256b product of two u128, as a (high, low) pair
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const LOW: u128 = std::u64::MAX as u128;
+    let (a1, a0) = (a >> 64, a & LOW);
+    let (b1, b0) = (b >> 64, b & LOW);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    // Cannot overflow: each term is < 2⁶⁴
+    let mid = (p00 >> 64) + (p01 & LOW) + (p10 & LOW);
+    let low = (p00 & LOW) | (mid << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64);
+    (high, low)
+}
+
+// There is no 256b integer type to act as `DoubleWidth` for u128, so the
+//  128b Montgomery arithmetic cannot go through the `Arithmetic` trait;
+//  it mirrors its interface instead, with u128 in place of u64.
+impl Montgomery<u128> {
+    pub(crate) fn new(n: u128) -> Self {
+        debug_assert!(n % 2 == 1);
+        let a = modular_inverse(n).wrapping_neg();
+        debug_assert_eq!(n.wrapping_mul(a), std::u128::MAX);
+        Montgomery { a, n }
+    }
+
+    pub(crate) fn modulus(&self) -> u128 {
+        self.n
+    }
+
+    /// computes (high·2¹²⁸ + low)/R mod n efficiently
+    fn reduce_wide(&self, (high, low): (u128, u128)) -> u128 {
+        let Montgomery { a, n } = *self;
+        debug_assert!(high < n);
+
+        let m = low.wrapping_mul(a);
+        let (nm_high, nm_low) = mul_wide(n, m);
+
+        // The low halves sum to 0 mod 2¹²⁸, only the carry matters.
+        let carry = low.overflowing_add(nm_low).1 as u128;
+        let (y, overflow) = high.overflowing_add(nm_high + carry);
+
+        // y < 2n, and in case of overflow the true value is 2¹²⁸ + y
+        if overflow || y >= n {
+            y.wrapping_sub(n)
+        } else {
+            y
+        }
+    }
+
+    pub(crate) fn from_u128(&self, x: u128) -> u128 {
+        debug_assert!(x < self.n);
+        // Compute x·R mod n by repeated doubling, to avoid a 256b division
+        let mut r = x;
+        for _ in 0..128 {
+            let (d, overflow) = r.overflowing_add(r);
+            r = if overflow || d >= self.n {
+                d.wrapping_sub(self.n)
+            } else {
+                d
+            };
+        }
+        debug_assert_eq!(x, self.to_u128(r));
+        r
+    }
+
+    pub(crate) fn to_u128(&self, n: u128) -> u128 {
+        self.reduce_wide((0, n))
+    }
+
+    pub(crate) fn add(&self, a: u128, b: u128) -> u128 {
+        let (r, overflow) = a.overflowing_add(b);
+        if overflow || r >= self.n {
+            r.wrapping_sub(self.n)
+        } else {
+            r
+        }
+    }
+
+    pub(crate) fn mul(&self, a: u128, b: u128) -> u128 {
+        self.reduce_wide(mul_wide(a, b))
+    }
+
+    pub(crate) fn pow(&self, mut a: u128, mut b: u128) -> u128 {
+        let mut result = self.one();
+        while b > 0 {
+            if b & 1 != 0 {
+                result = self.mul(result, a);
+            }
+            a = self.mul(a, a);
+            b >>= 1;
+        }
+        result
+    }
+
+    pub(crate) fn one(&self) -> u128 {
+        // R mod n
+        self.n.wrapping_neg() % self.n
+    }
+
+    pub(crate) fn minus_one(&self) -> u128 {
+        self.n - self.one()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +330,21 @@ mod tests {
     }
     parametrized_check!(test_add);
 
+    fn test_sub<A: DoubleInt>() {
+        for n in 0..100 {
+            let n = 2 * n + 1;
+            let m = Montgomery::<A>::new(n);
+            for x in 0..n {
+                let m_x = m.from_u64(x);
+                for y in 0..n {
+                    let m_y = m.from_u64(y);
+                    assert_eq!((x + n - y) % n, m.to_u64(m.sub(m_x, m_y)));
+                }
+            }
+        }
+    }
+    parametrized_check!(test_sub);
+
     fn test_mult<A: DoubleInt>() {
         for n in 0..100 {
             let n = 2 * n + 1;
@@ -215,6 +360,26 @@ mod tests {
     }
     parametrized_check!(test_mult);
 
+    fn test_mult_random<A: DoubleInt>() {
+        use rand::rngs::SmallRng;
+        use rand::{Rng, SeedableRng};
+
+        // Random odd moduli of the full width of A, against u128 arithmetic
+        let mut rng = SmallRng::seed_from_u64(0x5eed);
+        let max = std::u64::MAX >> (64 - A::zero().count_zeros());
+        for _ in 0..1000 {
+            let n = rng.gen_range(3, max) | 1;
+            let m = Montgomery::<A>::new(n);
+            for _ in 0..10 {
+                let x = rng.gen_range(0, n);
+                let y = rng.gen_range(0, n);
+                let expected = (x as u128 * y as u128 % n as u128) as u64;
+                assert_eq!(expected, m.to_u64(m.mul(m.from_u64(x), m.from_u64(y))));
+            }
+        }
+    }
+    parametrized_check!(test_mult_random);
+
     fn test_roundtrip<A: DoubleInt>() {
         for n in 0..100 {
             let n = 2 * n + 1;
@@ -226,4 +391,22 @@ mod tests {
         }
     }
     parametrized_check!(test_roundtrip);
+
+    #[test]
+    fn test_mul_u128() {
+        // Moduli larger than 2⁶⁴ (the low half is odd), against u128 arithmetic
+        //  on operands small enough for the product not to overflow.
+        for n in [(1u128 << 64) + 1, (1 << 100) + 3, u128::MAX].iter() {
+            let m = Montgomery::<u128>::new(*n);
+            for x in (0..1u128 << 60).step_by(1 << 53).chain(0..50) {
+                let m_x = m.from_u128(x);
+                assert_eq!(x, m.to_u128(m_x));
+                for y in (0..1u128 << 60).step_by(1 << 55).chain(0..20) {
+                    let m_y = m.from_u128(y);
+                    assert_eq!((x * y) % n, m.to_u128(m.mul(m_x, m_y)));
+                    assert_eq!((x + y) % n, m.to_u128(m.add(m_x, m_y)));
+                }
+            }
+        }
+    }
 }
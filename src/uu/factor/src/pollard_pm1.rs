@@ -0,0 +1,89 @@
+// * This file is part of the uutils coreutils package.
+// *
+// * For the full copyright and license information, please view the LICENSE file
+// * that was distributed with this source code.
+
+// spell-checker:ignore (ToDO) smoothness
+
+use std::iter;
+
+use crate::numeric::*;
+use crate::table::P_INVS_U64;
+
+// Limit for the second stage, as a multiple of the first stage's bound
+const B2_FACTOR: u64 = 16;
+
+fn primes() -> impl Iterator<Item = u64> {
+    iter::once(2).chain(P_INVS_U64.iter().map(|(p, _, _)| *p))
+}
+
+// Pollard's p-1 algorithm: finds a prime divisor p of n when p-1 is smooth,
+//  meaning that all prime powers dividing it are below a bound B1, except
+//  maybe a single prime in ]B1; B2].
+// B1 is the largest prime in the trial division table, as even smaller
+//  factors were already removed from n.
+// Returns `None` when no (non-trivial) divisor was found within those bounds.
+pub(crate) fn find_divisor<A: Arithmetic>(n: A) -> Option<u64> {
+    #![allow(clippy::many_single_char_names)]
+    let m = n.modulus();
+    let b1 = primes().last().unwrap();
+    let b2 = B2_FACTOR * b1;
+
+    // Stage 1: a = 2^E mod n, where E is the product of all p^k ≤ B1
+    let mut a = n.from_u64(2);
+    for p in primes() {
+        let mut q = p;
+        while q * p <= b1 {
+            q *= p;
+        }
+        a = n.pow(a, q);
+    }
+
+    match gcd(n.to_u64(a) - 1, m) {
+        1 => {}
+        d if d == m => return None,
+        d => return Some(d),
+    }
+
+    // Stage 2: accumulate the product of all (a^q - 1), for odd q in ]B1; B2]
+    let minus_one = n.minus_one();
+    let a_2 = n.mul(a, a);
+    let mut a_q = n.pow(a, b1 + 2);
+    let mut acc = n.add(a_q, minus_one);
+    for _ in (b1 + 4..=b2).step_by(2) {
+        a_q = n.mul(a_q, a_2);
+        acc = n.mul(acc, n.add(a_q, minus_one));
+    }
+
+    match gcd(n.to_u64(acc), m) {
+        d if d == 1 || d == m => None,
+        d => Some(d),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth() {
+        // 1099511628679 - 1 = 2 × 3⁴ × 61 × 139 × 709 × 1129
+        // 8388619 - 1 = 2 × 3 × 7 × 199729
+        let n = 1099511628679 * 8388619;
+        assert_eq!(find_divisor(Montgomery::<u64>::new(n)), Some(1099511628679));
+    }
+
+    #[test]
+    fn second_stage() {
+        // 1099511627831 - 1 = 2 × 5 × 7 × 11 × 149 × 367 × 26113
+        let n = 1099511627831 * 8388619;
+        assert_eq!(find_divisor(Montgomery::<u64>::new(n)), Some(1099511627831));
+    }
+
+    #[test]
+    fn not_smooth() {
+        // 2147483659 - 1 = 2 × 3 × 149 × 2402107
+        let n = 2147483659 * 8388619;
+        assert_eq!(find_divisor(Montgomery::<u64>::new(n)), None);
+    }
+}
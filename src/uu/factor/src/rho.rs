@@ -13,6 +13,13 @@ use std::cmp::{max, min};
 
 use crate::numeric::*;
 
+// Number of steps taken between two gcd computations
+const BATCH_SIZE: usize = 128;
+
+// Pollard's rho, with Brent's cycle detection: the sequence xᵢ₊₁ = f(xᵢ) is
+//  compared against x₂ᵏ⁻¹ for 2ᵏ⁻¹ < i ≤ 2ᵏ, and the differences are
+//  accumulated in a product, whose gcd with n is only taken every BATCH_SIZE
+//  steps.
 pub(crate) fn find_divisor<A: Arithmetic>(n: A) -> u64 {
     #![allow(clippy::many_single_char_names)]
     let mut rand = {
@@ -23,6 +30,66 @@ pub(crate) fn find_divisor<A: Arithmetic>(n: A) -> u64 {
 
     let quadratic = |a, b| move |x| n.add(n.mul(a, n.mul(x, x)), b);
 
+    loop {
+        let f = quadratic(rand(), rand());
+        let mut y = rand();
+        let mut x = y;
+        // The value of y at the start of the current batch, for backtracking
+        let mut y_batch = y;
+        let mut d = 1;
+        let mut r = 1;
+
+        while d == 1 {
+            x = y;
+            for _ in 0..r {
+                y = f(y);
+            }
+
+            let mut k = 0;
+            while k < r && d == 1 {
+                y_batch = y;
+                let mut q = n.one();
+                for _ in 0..min(BATCH_SIZE, r - k) {
+                    y = f(y);
+                    q = n.mul(q, n.sub(x, y));
+                }
+                d = gcd(n.to_u64(q), n.modulus());
+                k += BATCH_SIZE;
+            }
+            r *= 2;
+        }
+
+        if d == n.modulus() {
+            // The product became 0 mod n, maybe because several factors of n
+            //  were found in the same batch: redo the batch one step at a time.
+            loop {
+                y_batch = f(y_batch);
+                d = gcd(n.to_u64(n.sub(x, y_batch)), n.modulus());
+                if d > 1 {
+                    break;
+                }
+            }
+        }
+
+        if d == n.modulus() {
+            // Failure, retry with a different quadratic
+            continue;
+        }
+        return d;
+    }
+}
+
+// Same as `find_divisor`, for moduli which do not fit in 64 bits
+pub(crate) fn find_divisor_u128(n: Montgomery<u128>) -> u128 {
+    #![allow(clippy::many_single_char_names)]
+    let mut rand = {
+        let range = Uniform::new(1, n.modulus());
+        let mut rng = SmallRng::from_rng(&mut thread_rng()).unwrap();
+        move || n.from_u128(range.sample(&mut rng))
+    };
+
+    let quadratic = |a, b| move |x| n.add(n.mul(a, n.mul(x, x)), b);
+
     loop {
         let f = quadratic(rand(), rand());
         let mut x = rand();
@@ -32,9 +99,9 @@ pub(crate) fn find_divisor<A: Arithmetic>(n: A) -> u64 {
             x = f(x);
             y = f(f(y));
             let d = {
-                let _x = n.to_u64(x);
-                let _y = n.to_u64(y);
-                gcd(n.modulus(), max(_x, _y) - min(_x, _y))
+                let _x = n.to_u128(x);
+                let _y = n.to_u128(y);
+                gcd_u128(n.modulus(), max(_x, _y) - min(_x, _y))
             };
             if d == n.modulus() {
                 // Failure, retry with a different quadratic
@@ -14,9 +14,14 @@ use crate::Factors;
 
 include!(concat!(env!("OUT_DIR"), "/prime_table.rs"));
 
-pub(crate) fn factor(mut num: u64, mut factors: Factors) -> (Factors, u64) {
+/// Trial division of odd `num` by the primes in the table which are at most
+/// `bound`, adding them to `factors`; also returns the remaining cofactor.
+///
+/// The table holds the odd primes below [`NEXT_PRIME`]; its size can be set
+/// at build time with the `UU_FACTOR_TABLE_SIZE` environment variable.
+pub fn factor_with_bound(mut num: u64, mut factors: Factors, bound: u64) -> (Factors, u64) {
     for &(prime, inv, ceil) in P_INVS_U64 {
-        if num == 1 {
+        if num == 1 || prime > bound {
             break;
         }
 
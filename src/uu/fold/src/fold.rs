@@ -121,111 +121,36 @@ fn fold(filenames: Vec<String>, bytes: bool, spaces: bool, width: usize) {
             &mut file_buf as &mut dyn Read
         });
 
-        if bytes {
-            fold_file_bytewise(buffer, spaces, width);
-        } else {
-            fold_file(buffer, spaces, width);
-        }
+        fold_file(buffer, bytes, spaces, width);
     }
 }
 
-/// Fold `file` to fit `width` (number of columns), counting all characters as
-/// one column.
+/// The column after `ch`, if it is at `col_count`.
 ///
-/// This function handles folding for the `-b`/`--bytes` option, counting
-/// tab, backspace, and carriage return as occupying one column, identically
-/// to all other characters in the stream.
-///
-///  If `spaces` is `true`, attempt to break lines at whitespace boundaries.
-fn fold_file_bytewise<T: Read>(mut file: BufReader<T>, spaces: bool, width: usize) {
-    let mut line = String::new();
-
-    loop {
-        if let Ok(0) = file.read_line(&mut line) {
-            break;
-        }
-
-        if line == "\n" {
-            println!();
-            line.truncate(0);
-            continue;
-        }
-
-        let len = line.len();
-        let mut i = 0;
-
-        while i < len {
-            let width = if len - i >= width { width } else { len - i };
-            let slice = {
-                let slice = &line[i..i + width];
-                if spaces && i + width < len {
-                    match slice.rfind(|c: char| c.is_whitespace() && c != '\r') {
-                        Some(m) => &slice[..=m],
-                        None => slice,
-                    }
-                } else {
-                    slice
-                }
-            };
-
-            // Don't duplicate trailing newlines: if the slice is "\n", the
-            // previous iteration folded just before the end of the line and
-            // has already printed this newline.
-            if slice == "\n" {
-                break;
-            }
-
-            i += slice.len();
-
-            let at_eol = i >= len;
-
-            if at_eol {
-                print!("{}", slice);
-            } else {
-                println!("{}", slice);
-            }
-        }
-
-        line.truncate(0);
+/// By default `fold` treats tab, backspace, and carriage return specially:
+/// tab characters count as 8 columns, backspace decreases the
+/// column count, and carriage return resets the column count to 0.
+/// With `-b`/`--bytes`, every byte counts as one column.
+fn next_column(col_count: usize, ch: char, bytes: bool) -> usize {
+    if bytes {
+        return col_count + ch.len_utf8();
+    }
+    match ch {
+        '\r' => 0,
+        '\t' => col_count + TAB_WIDTH - col_count % TAB_WIDTH,
+        '\x08' => col_count.saturating_sub(1),
+        _ => col_count + 1,
     }
 }
 
 /// Fold `file` to fit `width` (number of columns).
 ///
-/// By default `fold` treats tab, backspace, and carriage return specially:
-/// tab characters count as 8 columns, backspace decreases the
-/// column count, and carriage return resets the column count to 0.
-///
-/// If `spaces` is `true`, attempt to break lines at whitespace boundaries.
-#[allow(unused_assignments)]
-fn fold_file<T: Read>(mut file: BufReader<T>, spaces: bool, width: usize) {
+/// If `spaces` is `true`, lines are broken after the last blank that fits,
+/// and only cut off at `width` if there is none.
+fn fold_file<T: Read>(mut file: BufReader<T>, bytes: bool, spaces: bool, width: usize) {
     let mut line = String::new();
     let mut output = String::new();
     let mut col_count = 0;
-    let mut last_space = None;
-
-    /// Print the output line, resetting the column and character counts.
-    ///
-    /// If `spaces` is `true`, print the output line up to the last
-    /// encountered whitespace character (inclusive) and set the remaining
-    /// characters as the start of the next line.
-    macro_rules! emit_output {
-        () => {
-            let consume = match last_space {
-                Some(i) => i + 1,
-                None => output.len(),
-            };
-
-            println!("{}", &output[..consume]);
-            output.replace_range(..consume, "");
-
-            // we know there are no tabs left in output, so each char counts
-            // as 1 column
-            col_count = output.len();
-
-            last_space = None;
-        };
-    }
 
     loop {
         if let Ok(0) = file.read_line(&mut line) {
@@ -234,49 +159,41 @@ fn fold_file<T: Read>(mut file: BufReader<T>, spaces: bool, width: usize) {
 
         for ch in line.chars() {
             if ch == '\n' {
-                // make sure to _not_ split output at whitespace, since we
-                // know the entire output will fit
-                last_space = None;
-                emit_output!();
+                println!("{}", output);
+                output.truncate(0);
+                col_count = 0;
                 break;
             }
 
-            if col_count >= width {
-                emit_output!();
-            }
-
-            match ch {
-                '\r' => col_count = 0,
-                '\t' => {
-                    let next_tab_stop = col_count + TAB_WIDTH - col_count % TAB_WIDTH;
-
-                    if next_tab_stop > width && !output.is_empty() {
-                        emit_output!();
-                    }
-
-                    col_count = next_tab_stop;
-                    last_space = if spaces { Some(output.len()) } else { None };
-                }
-                '\x08' => {
-                    if col_count > 0 {
-                        col_count -= 1;
+            col_count = next_column(col_count, ch, bytes);
+            while col_count > width {
+                if spaces {
+                    if let Some(i) = output.rfind(&[' ', '\t'][..]) {
+                        println!("{}", &output[..=i]);
+                        output.replace_range(..=i, "");
+                        col_count = output
+                            .chars()
+                            .fold(0, |col_count, ch| next_column(col_count, ch, bytes));
+                        col_count = next_column(col_count, ch, bytes);
+                        continue;
                     }
                 }
-                _ if spaces && ch.is_whitespace() => {
-                    last_space = Some(output.len());
-                    col_count += 1;
+                // A character wider than the whole line has to go somewhere
+                if output.is_empty() {
+                    break;
                 }
-                _ => col_count += 1,
-            };
+                println!("{}", output);
+                output.truncate(0);
+                col_count = next_column(0, ch, bytes);
+            }
 
             output.push(ch);
         }
 
-        if !output.is_empty() {
-            print!("{}", output);
-            output.truncate(0);
-        }
-
         line.truncate(0);
     }
+
+    if !output.is_empty() {
+        print!("{}", output);
+    }
 }
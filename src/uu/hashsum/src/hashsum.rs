@@ -76,7 +76,7 @@ fn detect_algo<'a>(
         "sha256sum" => ("SHA256", Box::new(Sha256::new()) as Box<dyn Digest>, 256),
         "sha384sum" => ("SHA384", Box::new(Sha384::new()) as Box<dyn Digest>, 384),
         "sha512sum" => ("SHA512", Box::new(Sha512::new()) as Box<dyn Digest>, 512),
-        "b2sum" => ("BLAKE2", Box::new(Blake2b::new(64)) as Box<dyn Digest>, 512),
+        "b2sum" => ("BLAKE2b", Box::new(Blake2b::new(64)) as Box<dyn Digest>, 512),
         "sha3sum" => match matches.value_of("bits") {
             Some(bits_str) => match (&bits_str).parse::<usize>() {
                 Ok(224) => (
@@ -178,7 +178,7 @@ fn detect_algo<'a>(
                     set_or_crash("SHA512", Box::new(Sha512::new()), 512)
                 }
                 if matches.is_present("b2sum") {
-                    set_or_crash("BLAKE2", Box::new(Blake2b::new(64)), 512)
+                    set_or_crash("BLAKE2b", Box::new(Blake2b::new(64)), 512)
                 }
                 if matches.is_present("sha3") {
                     match matches.value_of("bits") {
@@ -442,6 +442,7 @@ where
 {
     let mut bad_format = 0;
     let mut failed = 0;
+    let mut failed_open = 0;
     let binary_marker = if options.binary { "*" } else { " " };
     for filename in files {
         let filename = Path::new(filename);
@@ -457,14 +458,15 @@ where
         });
         if options.check {
             // Set up Regexes for line validation and parsing
-            let bytes = options.digest.output_bits() / 4;
+            // The digests of SHAKE have the length given by --bits
+            let bytes = options.output_bits / 4;
             let gnu_re = safe_unwrap!(Regex::new(&format!(
                 r"^(?P<digest>[a-fA-F0-9]{{{}}}) (?P<binary>[ \*])(?P<fileName>.*)",
                 bytes
             )));
             let bsd_re = safe_unwrap!(Regex::new(&format!(
                 r"^{algorithm} \((?P<fileName>.*)\) = (?P<digest>[a-fA-F0-9]{{{digest_size}}})",
-                algorithm = options.algoname,
+                algorithm = regex::escape(options.algoname),
                 digest_size = bytes
             )));
 
@@ -500,7 +502,17 @@ where
                         }
                     },
                 };
-                let f = safe_unwrap!(File::open(ck_filename));
+                let f = match File::open(ck_filename) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        if !options.status {
+                            show_error!("{}: {}", ck_filename, e);
+                            println!("{}: FAILED open or read", ck_filename);
+                        }
+                        failed_open += 1;
+                        continue;
+                    }
+                };
                 let mut ckf = BufReader::new(Box::new(f) as Box<dyn Read>);
                 let real_sum = safe_unwrap!(digest_reader(
                     &mut *options.digest,
@@ -540,11 +552,21 @@ where
             Ordering::Greater => show_warning!("{} lines are improperly formatted", bad_format),
             _ => {}
         };
-        if failed > 0 {
-            show_warning!("{} computed checksum did NOT match", failed);
-        }
+        match failed_open.cmp(&1) {
+            Ordering::Equal => show_warning!("{} listed file could not be read", failed_open),
+            Ordering::Greater => show_warning!("{} listed files could not be read", failed_open),
+            _ => {}
+        };
+        match failed.cmp(&1) {
+            Ordering::Equal => show_warning!("{} computed checksum did NOT match", failed),
+            Ordering::Greater => show_warning!("{} computed checksums did NOT match", failed),
+            _ => {}
+        };
     }
 
+    if failed > 0 || failed_open > 0 {
+        return Err(1);
+    }
     Ok(())
 }
 
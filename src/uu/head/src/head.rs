@@ -340,7 +340,12 @@ fn rbuf_but_last_n_lines(
             }
             Ok(true)
         }
-    })
+    })?;
+    // An unterminated last line still counts as a line.
+    if !line.is_empty() && lines == n {
+        stdout.write_all(&ringbuf[0])?;
+    }
+    Ok(())
 }
 
 fn head_backwards_file(input: &mut std::fs::File, options: &HeadOptions) -> std::io::Result<()> {
@@ -361,17 +366,18 @@ fn head_backwards_file(input: &mut std::fs::File, options: &HeadOptions) -> std:
         }
         Modes::Lines(n) => {
             let mut buffer = [0u8; BUF_SIZE];
-            let buffer = &mut buffer[..BUF_SIZE.min(size)];
             let mut i = 0usize;
             let mut lines = 0usize;
 
             let found = 'o: loop {
-                // the casts here are ok, `buffer.len()` should never be above a few k
-                input.seek(SeekFrom::Current(
-                    -((buffer.len() as i64).min((size - i) as i64)),
-                ))?;
-                input.read_exact(buffer)?;
-                for byte in buffer.iter().rev() {
+                if size - i == 0 {
+                    return Ok(());
+                }
+                // read the chunk just before the `i` bytes already looked at
+                let chunk = BUF_SIZE.min(size - i);
+                input.seek(SeekFrom::Start((size - i - chunk) as u64))?;
+                input.read_exact(&mut buffer[..chunk])?;
+                for byte in buffer[..chunk].iter().rev() {
                     match byte {
                         b'\n' if !options.zeroed => {
                             lines += 1;
@@ -379,6 +385,10 @@ fn head_backwards_file(input: &mut std::fs::File, options: &HeadOptions) -> std:
                         0u8 if options.zeroed => {
                             lines += 1;
                         }
+                        // an unterminated last line still counts as a line
+                        _ if i == 0 => {
+                            lines += 1;
+                        }
                         _ => {}
                     }
                     // if it were just `n`,
@@ -387,9 +397,6 @@ fn head_backwards_file(input: &mut std::fs::File, options: &HeadOptions) -> std:
                     }
                     i += 1;
                 }
-                if size - i == 0 {
-                    return Ok(());
-                }
             };
             input.seek(SeekFrom::Start(0))?;
             rbuf_n_bytes(
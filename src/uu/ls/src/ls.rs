@@ -13,15 +13,13 @@ extern crate uucore;
 #[macro_use]
 extern crate lazy_static;
 
-mod quoting_style;
 mod version_cmp;
 
 use clap::{App, Arg};
 use globset::{self, Glob, GlobSet, GlobSetBuilder};
-use lscolors::LsColors;
+use lscolors::{Indicator, LsColors};
 use number_prefix::NumberPrefix;
 use once_cell::unsync::OnceCell;
-use quoting_style::{escape_name, QuotingStyle};
 #[cfg(windows)]
 use std::os::windows::fs::MetadataExt;
 use std::{
@@ -43,6 +41,7 @@ use time::{strftime, Timespec};
 use unicode_width::UnicodeWidthStr;
 #[cfg(unix)]
 use uucore::libc::{S_IXGRP, S_IXOTH, S_IXUSR};
+use uucore::quoting_style::{self, escape_name, QuotingStyle};
 
 static VERSION: &str = env!("CARGO_PKG_VERSION");
 static ABOUT: &str = "
@@ -158,6 +157,7 @@ enum Time {
     Change,
 }
 
+#[derive(PartialEq)]
 enum Dereference {
     None,
     DirArgs,
@@ -316,7 +316,7 @@ impl Config {
         let needs_color = match options.value_of(options::COLOR) {
             None => options.is_present(options::COLOR),
             Some(val) => match val {
-                "" | "always" | "yes" | "force" => true,
+                "always" | "yes" | "force" => true,
                 "auto" | "tty" | "if-tty" => atty::is(atty::Stream::Stdout),
                 /* "never" | "no" | "none" | */ _ => false,
             },
@@ -362,13 +362,16 @@ impl Config {
             })
             .or_else(|| termsize::get().map(|s| s.cols));
 
-        #[allow(clippy::needless_bool)]
+        // Like GNU ls, nonprintable characters are only replaced by `?` on a terminal;
+        // names are written as they are to pipes and files.
+        let is_tty = atty::is(atty::Stream::Stdout);
+
         let show_control = if options.is_present(options::HIDE_CONTROL_CHARS) {
             false
         } else if options.is_present(options::SHOW_CONTROL_CHARS) {
             true
         } else {
-            false // TODO: only if output is a terminal and the program is `ls`
+            !is_tty
         };
 
         let quoting_style = if let Some(style) = options.value_of(options::QUOTING_STYLE) {
@@ -412,13 +415,15 @@ impl Config {
             QuotingStyle::C {
                 quotes: quoting_style::Quotes::Double,
             }
-        } else {
+        } else if is_tty {
             // TODO: use environment variable if available
             QuotingStyle::Shell {
                 escape: true,
                 always_quote: false,
                 show_control,
             }
+        } else {
+            QuotingStyle::Literal { show_control }
         };
 
         let indicator_style = if let Some(field) = options.value_of(options::INDICATOR_STYLE) {
@@ -966,10 +971,13 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .arg(
             Arg::with_name(options::COLOR)
                 .long(options::COLOR)
-                .help("Color output based on file type.")
+                .help("Color output based on file type, using the colors in LS_COLORS.")
                 .takes_value(true)
                 .require_equals(true)
-                .min_values(0),
+                .min_values(0)
+                .possible_values(&[
+                    "always", "yes", "force", "auto", "tty", "if-tty", "never", "no", "none",
+                ]),
         )
         .arg(
             Arg::with_name(options::INDICATOR_STYLE)
@@ -1076,9 +1084,10 @@ impl PathData {
             }
             Dereference::None => false,
         };
+        // The file type of a directory entry is that of the symlink itself.
         let ft = match file_type {
-            Some(ft) => OnceCell::from(ft.ok()),
-            None => OnceCell::new(),
+            Some(ft) if !must_dereference => OnceCell::from(ft.ok()),
+            _ => OnceCell::new(),
         };
 
         Self {
@@ -1141,11 +1150,20 @@ fn list(locs: Vec<String>, config: Config) -> i32 {
     display_items(&files, &config, &mut out);
 
     sort_entries(&mut dirs, &config);
-    for dir in dirs {
-        if number_of_locs > 1 {
-            let _ = writeln!(out, "\n{}:", dir.p_buf.display());
+    for (pos, dir) in dirs.iter().enumerate() {
+        if number_of_locs > 1 || config.recursive {
+            if pos > 0 || !files.is_empty() {
+                let _ = writeln!(out);
+            }
+            let _ = writeln!(
+                out,
+                "{}:",
+                escape_name(&dir.p_buf.to_string_lossy(), &config.quoting_style)
+            );
+        }
+        if enter_directory(dir, &config, &mut out, &mut Vec::new()) {
+            has_failed = true;
         }
-        enter_directory(&dir, &config, &mut out);
     }
     if has_failed {
         1
@@ -1154,26 +1172,35 @@ fn list(locs: Vec<String>, config: Config) -> i32 {
     }
 }
 
+/// The key that orders entries by name. The default sort in GNU ls is case insensitive.
+fn name_sort_key(entry: &PathData) -> (String, bool) {
+    let has_dot: bool = entry.file_name.starts_with('.');
+    let filename_nodot: &str = &entry.file_name[if has_dot { 1 } else { 0 }..];
+    // We want hidden files to appear before regular files of the same
+    // name, so we need to negate the "has_dot" variable.
+    (filename_nodot.to_lowercase(), !has_dot)
+}
+
 fn sort_entries(entries: &mut Vec<PathData>, config: &Config) {
     match config.sort {
-        Sort::Time => entries.sort_by_key(|k| {
-            Reverse(
-                k.md()
-                    .and_then(|md| get_system_time(&md, config))
-                    .unwrap_or(UNIX_EPOCH),
+        // Entries with the same time or size are sorted by name.
+        Sort::Time => entries.sort_by_cached_key(|k| {
+            (
+                Reverse(
+                    k.md()
+                        .and_then(|md| get_system_time(md, config))
+                        .unwrap_or(UNIX_EPOCH),
+                ),
+                name_sort_key(k),
             )
         }),
-        Sort::Size => {
-            entries.sort_by_key(|k| Reverse(k.md().as_ref().map(|md| md.len()).unwrap_or(0)))
-        }
-        // The default sort in GNU ls is case insensitive
-        Sort::Name => entries.sort_by_cached_key(|k| {
-            let has_dot: bool = k.file_name.starts_with('.');
-            let filename_nodot: &str = &k.file_name[if has_dot { 1 } else { 0 }..];
-            // We want hidden files to appear before regular files of the same
-            // name, so we need to negate the "has_dot" variable.
-            (filename_nodot.to_lowercase(), !has_dot)
+        Sort::Size => entries.sort_by_cached_key(|k| {
+            (
+                Reverse(k.md().as_ref().map(|md| md.len()).unwrap_or(0)),
+                name_sort_key(k),
+            )
         }),
+        Sort::Name => entries.sort_by_cached_key(name_sort_key),
         Sort::Version => entries.sort_by(|k, j| version_cmp::version_cmp(&k.p_buf, &j.p_buf)),
         Sort::Extension => entries.sort_by(|a, b| {
             a.p_buf
@@ -1210,7 +1237,17 @@ fn should_display(entry: &DirEntry, config: &Config) -> bool {
     !config.ignore_patterns.is_match(&ffi_name)
 }
 
-fn enter_directory(dir: &PathData, config: &Config, out: &mut BufWriter<Stdout>) {
+/// List the contents of `dir`, and of its subdirectories with `--recursive`.
+/// When following all symlinks, `ancestors` holds the directories being listed, to detect cycles.
+/// Returns whether some directory could not be listed.
+fn enter_directory(
+    dir: &PathData,
+    config: &Config,
+    out: &mut BufWriter<Stdout>,
+    ancestors: &mut Vec<PathBuf>,
+) -> bool {
+    let mut has_failed = false;
+
     let mut entries: Vec<_> = if config.files == Files::All {
         vec![
             PathData::new(dir.p_buf.join("."), None, config, false),
@@ -1233,15 +1270,43 @@ fn enter_directory(dir: &PathData, config: &Config, out: &mut BufWriter<Stdout>)
     display_items(&entries, config, out);
 
     if config.recursive {
+        let track_ancestors = config.dereference == Dereference::All;
+        if track_ancestors {
+            ancestors.push(fs::canonicalize(&dir.p_buf).unwrap_or_else(|_| dir.p_buf.clone()));
+        }
+
         for e in entries
             .iter()
             .skip(if config.files == Files::All { 2 } else { 0 })
             .filter(|p| p.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
         {
-            let _ = writeln!(out, "\n{}:", e.p_buf.display());
-            enter_directory(&e, config, out);
+            let _ = writeln!(
+                out,
+                "\n{}:",
+                escape_name(&e.p_buf.to_string_lossy(), &config.quoting_style)
+            );
+            if track_ancestors
+                && fs::canonicalize(&e.p_buf).map_or(false, |path| ancestors.contains(&path))
+            {
+                let _ = out.flush();
+                show_error!(
+                    "{}: not listing already-listed directory",
+                    e.p_buf.display()
+                );
+                has_failed = true;
+                continue;
+            }
+            if enter_directory(e, config, out, ancestors) {
+                has_failed = true;
+            }
+        }
+
+        if track_ancestors {
+            ancestors.pop();
         }
     }
+
+    has_failed
 }
 
 fn get_metadata(entry: &Path, dereference: bool) -> std::io::Result<Metadata> {
@@ -1603,7 +1668,7 @@ fn display_file_name(path: &PathData, config: &Config) -> Option<Cell> {
     let mut width = name.width();
 
     if let Some(ls_colors) = &config.color {
-        name = color_name(&ls_colors, &path.p_buf, name, path.md()?);
+        name = color_name(ls_colors, &path.p_buf, name, path.md());
     }
 
     if config.indicator_style != IndicatorStyle::None {
@@ -1647,8 +1712,15 @@ fn display_file_name(path: &PathData, config: &Config) -> Option<Cell> {
     })
 }
 
-fn color_name(ls_colors: &LsColors, path: &Path, name: String, md: &Metadata) -> String {
-    match ls_colors.style_for_path_with_metadata(path, Some(&md)) {
+fn color_name(ls_colors: &LsColors, path: &Path, name: String, md: Option<&Metadata>) -> String {
+    // Regular files without a style of their own get the one of `fi`
+    let style = ls_colors
+        .style_for_path_with_metadata(path, md)
+        .or_else(|| match md {
+            Some(md) if md.is_file() => ls_colors.style_for_indicator(Indicator::RegularFile),
+            _ => None,
+        });
+    match style {
         Some(style) => style.to_ansi_term_style().paint(name).to_string(),
         None => name,
     }
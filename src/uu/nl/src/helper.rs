@@ -29,6 +29,17 @@ pub fn parse_options(settings: &mut crate::Settings, opts: &clap::ArgMatches) ->
     // This vector holds error messages encountered.
     let mut errs: Vec<String> = vec![];
     settings.renumber = !opts.is_present(options::NO_RENUMBER);
+    match opts.value_of(options::SECTION_DELIMITER) {
+        None => {}
+        Some(val) => {
+            // A single character is followed by the default second one.
+            settings.section_delimiter = if val.chars().count() == 1 {
+                format!("{}:", val)
+            } else {
+                val.to_owned()
+            };
+        }
+    }
     match opts.value_of(options::NUMER_SEPARATOR) {
         None => {}
         Some(val) => {
@@ -32,7 +32,7 @@ pub struct Settings {
     body_numbering: NumberingStyle,
     footer_numbering: NumberingStyle,
     // The variable corresponding to -d
-    section_delimiter: [char; 2],
+    section_delimiter: String,
     // The variables corresponding to the options -v, -i, -l, -w.
     starting_line_number: u64,
     line_increment: u64,
@@ -175,9 +175,9 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     // A mutable settings object, initialized with the defaults.
     let mut settings = Settings {
         header_numbering: NumberingStyle::NumberForNone,
-        body_numbering: NumberingStyle::NumberForAll,
+        body_numbering: NumberingStyle::NumberForNonEmpty,
         footer_numbering: NumberingStyle::NumberForNone,
-        section_delimiter: ['\\', ':'],
+        section_delimiter: String::from("\\:"),
         starting_line_number: 1,
         line_increment: 1,
         join_blank_lines: 1,
@@ -241,42 +241,31 @@ fn nl<T: Read>(reader: &mut BufReader<T>, settings: &Settings) {
         _ => ' ',
     };
     // Initially, we use the body's line counting settings
-    let mut regex_filter = match settings.body_numbering {
-        NumberingStyle::NumberForRegularExpression(ref re) => re,
-        _ => &regexp,
-    };
-    let mut line_filter: fn(&str, &regex::Regex) -> bool = pass_regex;
+    let (mut line_filter, mut regex_filter) = style_filter(&settings.body_numbering, &regexp);
+    // Lines without a number are indented like the numbered ones.
+    let no_number_fill = " ".repeat(settings.number_width + settings.number_separator.len());
+    // The lines starting a new "section" in the document
+    let header_delimiter = settings.section_delimiter.repeat(3);
+    let body_delimiter = settings.section_delimiter.repeat(2);
     for mut l in reader.lines().map(|r| r.unwrap()) {
         // Sanitize the string. We want to print the newline ourselves.
         if !l.is_empty() && l.chars().rev().next().unwrap() == '\n' {
             l.pop();
         }
-        // Next we iterate through the individual chars to see if this
-        // is one of the special lines starting a new "section" in the
-        // document.
         let line = l;
-        let mut odd = false;
         // matched_group counts how many copies of section_delimiter
         // this string consists of (0 if there's anything else)
-        let mut matched_groups = 0u8;
-        for c in line.chars() {
-            // If this is a newline character, the loop should end.
-            if c == '\n' {
-                break;
-            }
-            // If we have already seen three groups (corresponding to
-            // a header) or the current char does not form part of
-            // a new group, then this line is not a segment indicator.
-            if matched_groups >= 3 || settings.section_delimiter[if odd { 1 } else { 0 }] != c {
-                matched_groups = 0;
-                break;
-            }
-            if odd {
-                // We have seen a new group and count it.
-                matched_groups += 1;
-            }
-            odd = !odd;
-        }
+        let matched_groups = if settings.section_delimiter.is_empty() {
+            0
+        } else if line == header_delimiter {
+            3
+        } else if line == body_delimiter {
+            2
+        } else if line == settings.section_delimiter {
+            1
+        } else {
+            0
+        };
 
         // See how many groups we matched. That will tell us if this is
         // a line starting a new segment, and the number of groups
@@ -288,36 +277,22 @@ fn nl<T: Read>(reader: &mut BufReader<T>, settings: &Settings) {
             // However the line does not count as a blank line, so we
             // reset the counter used for --join-blank-lines.
             empty_line_count = 0;
-            match *match matched_groups {
-                3 => {
-                    // This is a header, so we may need to reset the
-                    // line number and the line width
-                    if settings.renumber {
-                        line_no = settings.starting_line_number;
-                        line_no_width = line_no_width_initial;
-                        line_no_threshold = 10u64.pow(line_no_width as u32);
-                    }
-                    &settings.header_numbering
-                }
+            // Each section starts counting anew, unless --no-renumber
+            if settings.renumber {
+                line_no = settings.starting_line_number;
+                line_no_width = line_no_width_initial;
+                line_no_threshold = 10u64.pow(line_no_width as u32);
+            }
+            let style = match matched_groups {
+                3 => &settings.header_numbering,
                 1 => &settings.footer_numbering,
                 // The only option left is 2, but rust wants
                 // a catch-all here.
                 _ => &settings.body_numbering,
-            } {
-                NumberingStyle::NumberForAll => {
-                    line_filter = pass_all;
-                }
-                NumberingStyle::NumberForNonEmpty => {
-                    line_filter = pass_nonempty;
-                }
-                NumberingStyle::NumberForNone => {
-                    line_filter = pass_none;
-                }
-                NumberingStyle::NumberForRegularExpression(ref re) => {
-                    line_filter = pass_regex;
-                    regex_filter = re;
-                }
-            }
+            };
+            let (filter, re) = style_filter(style, &regexp);
+            line_filter = filter;
+            regex_filter = re;
             continue;
         }
         // From this point on we format and print a "regular" line.
@@ -337,7 +312,7 @@ fn nl<T: Read>(reader: &mut BufReader<T>, settings: &Settings) {
             // want to print one in the first place, or it is a blank
             // line but we are still collecting more blank lines via
             // the option --join-blank-lines.
-            println!("{}", line);
+            println!("{}{}", no_number_fill, line);
             continue;
         }
         // If we make it here, then either we are printing a non-empty
@@ -372,6 +347,21 @@ fn nl<T: Read>(reader: &mut BufReader<T>, settings: &Settings) {
     }
 }
 
+// style_filter returns the function deciding whether a line is numbered with
+// style, and the regular expression to pass to it (regexp unless the style
+// has its own).
+fn style_filter<'a>(
+    style: &'a NumberingStyle,
+    regexp: &'a regex::Regex,
+) -> (fn(&str, &regex::Regex) -> bool, &'a regex::Regex) {
+    match style {
+        NumberingStyle::NumberForAll => (pass_all, regexp),
+        NumberingStyle::NumberForNonEmpty => (pass_nonempty, regexp),
+        NumberingStyle::NumberForNone => (pass_none, regexp),
+        NumberingStyle::NumberForRegularExpression(re) => (pass_regex, re),
+    }
+}
+
 fn pass_regex(line: &str, re: &regex::Regex) -> bool {
     re.is_match(line)
 }
@@ -1,7 +1,8 @@
-use crate::options::NumfmtOptions;
+use crate::options::{InvalidModes, NumfmtOptions};
 use crate::units::{
     DisplayableSuffix, RawSuffix, Result, Suffix, Transform, Unit, IEC_BASES, SI_BASES,
 };
+use std::io::Write;
 
 /// Iterate over a line's fields, where each field is a contiguous sequence of
 /// non-whitespace, optionally prefixed with one or more characters of leading
@@ -80,6 +81,13 @@ fn parse_suffix(s: &str) -> Result<(f64, Option<Suffix>)> {
         Some('Z') => Ok(Some((RawSuffix::Z, with_i))),
         Some('Y') => Ok(Some((RawSuffix::Y, with_i))),
         Some('0'..='9') => Ok(None),
+        // Only what starts like a number has a suffix
+        _ if !s
+            .trim_start_matches(&['-', '+'][..])
+            .starts_with(|c: char| c.is_ascii_digit() || c == '.') =>
+        {
+            Err(format!("invalid number: ‘{}’", s))
+        }
         _ => Err(format!("invalid suffix in input: ‘{}’", s)),
     }?;
 
@@ -219,14 +227,41 @@ fn format_string(
         &options.transform.to,
     )?;
 
-    Ok(match implicit_padding.unwrap_or(options.padding) {
-        p if p == 0 => number,
-        p if p > 0 => format!("{:>padding$}", number, padding = p as usize),
-        p => format!("{:<padding$}", number, padding = p.abs() as usize),
-    })
+    Ok(pad_string(number, options, implicit_padding))
+}
+
+fn pad_string(s: String, options: &NumfmtOptions, implicit_padding: Option<isize>) -> String {
+    match implicit_padding.unwrap_or(options.padding) {
+        0 => s,
+        p if p > 0 => format!("{:>padding$}", s, padding = p as usize),
+        p => format!("{:<padding$}", s, padding = p.abs() as usize),
+    }
+}
+
+/// Format a selected field; if it isn't a valid number, it is an error with
+/// `--invalid=abort`, and otherwise it is kept as it is, and `failed` is set
+/// with `--invalid=fail`.
+fn format_field(
+    field: &str,
+    options: &NumfmtOptions,
+    implicit_padding: Option<isize>,
+    failed: &mut bool,
+) -> Result<String> {
+    match format_string(field, options, implicit_padding) {
+        Err(e) if options.invalid != InvalidModes::Abort => {
+            if options.invalid != InvalidModes::Ignore {
+                std::io::stdout().flush().expect("error flushing stdout");
+                show_info!("{}", e);
+            }
+            *failed |= options.invalid == InvalidModes::Fail;
+            Ok(pad_string(field.to_string(), options, implicit_padding))
+        }
+        result => result,
+    }
 }
 
-fn format_and_print_delimited(s: &str, options: &NumfmtOptions) -> Result<()> {
+fn format_and_print_delimited(s: &str, options: &NumfmtOptions) -> Result<bool> {
+    let mut failed = false;
     let delimiter = options.delimiter.as_ref().unwrap();
 
     for (n, field) in (1..).zip(s.split(delimiter)) {
@@ -238,7 +273,10 @@ fn format_and_print_delimited(s: &str, options: &NumfmtOptions) -> Result<()> {
         }
 
         if field_selected {
-            print!("{}", format_string(&field.trim_start(), options, None)?);
+            print!(
+                "{}",
+                format_field(field.trim_start(), options, None, &mut failed)?
+            );
         } else {
             // print unselected field without conversion
             print!("{}", field);
@@ -247,10 +285,11 @@ fn format_and_print_delimited(s: &str, options: &NumfmtOptions) -> Result<()> {
 
     println!();
 
-    Ok(())
+    Ok(!failed)
 }
 
-fn format_and_print_whitespace(s: &str, options: &NumfmtOptions) -> Result<()> {
+fn format_and_print_whitespace(s: &str, options: &NumfmtOptions) -> Result<bool> {
+    let mut failed = false;
     for (n, (prefix, field)) in (1..).zip(WhitespaceSplitter { s: Some(s) }) {
         let field_selected = uucore::ranges::contain(&options.fields, n);
 
@@ -271,7 +310,10 @@ fn format_and_print_whitespace(s: &str, options: &NumfmtOptions) -> Result<()> {
                 None
             };
 
-            print!("{}", format_string(&field, options, implicit_padding)?);
+            print!(
+                "{}",
+                format_field(field, options, implicit_padding, &mut failed)?
+            );
         } else {
             // print unselected field without conversion
             print!("{}{}", prefix, field);
@@ -280,7 +322,7 @@ fn format_and_print_whitespace(s: &str, options: &NumfmtOptions) -> Result<()> {
 
     println!();
 
-    Ok(())
+    Ok(!failed)
 }
 
 /// Format a line of text according to the selected options.
@@ -288,7 +330,10 @@ fn format_and_print_whitespace(s: &str, options: &NumfmtOptions) -> Result<()> {
 /// Given a line of text `s`, split the line into fields, transform and format
 /// any selected numeric fields, and print the result to stdout. Fields not
 /// selected for conversion are passed through unmodified.
-pub fn format_and_print(s: &str, options: &NumfmtOptions) -> Result<()> {
+///
+/// Returns false if a selected field was not a valid number with
+/// `--invalid=fail`.
+pub fn format_and_print(s: &str, options: &NumfmtOptions) -> Result<bool> {
     match &options.delimiter {
         Some(_) => format_and_print_delimited(s, options),
         None => format_and_print_whitespace(s, options),
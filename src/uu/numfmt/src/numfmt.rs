@@ -53,15 +53,18 @@ fn get_usage() -> String {
     format!("{0} [OPTION]... [NUMBER]...", executable!())
 }
 
-fn handle_args<'a>(args: impl Iterator<Item = &'a str>, options: NumfmtOptions) -> Result<()> {
+/// Returns false if an invalid number was found with `--invalid=fail`.
+fn handle_args<'a>(args: impl Iterator<Item = &'a str>, options: NumfmtOptions) -> Result<bool> {
+    let mut valid = true;
     for l in args {
-        format_and_print(l, &options)?;
+        valid &= format_and_print(l, &options)?;
     }
 
-    Ok(())
+    Ok(valid)
 }
 
-fn handle_stdin(options: NumfmtOptions) -> Result<()> {
+/// Returns false if an invalid number was found with `--invalid=fail`.
+fn handle_stdin(options: NumfmtOptions) -> Result<bool> {
     let stdin = std::io::stdin();
     let locked_stdin = stdin.lock();
 
@@ -70,12 +73,14 @@ fn handle_stdin(options: NumfmtOptions) -> Result<()> {
         l.map(|s| println!("{}", s)).map_err(|e| e.to_string())?;
     }
 
+    let mut valid = true;
     for l in lines {
-        l.map_err(|e| e.to_string())
+        valid &= l
+            .map_err(|e| e.to_string())
             .and_then(|l| format_and_print(&l, &options))?;
     }
 
-    Ok(())
+    Ok(valid)
 }
 
 fn parse_unit(s: &str) -> Result<Unit> {
@@ -136,12 +141,20 @@ fn parse_options(args: &ArgMatches) -> Result<NumfmtOptions> {
         }
     })?;
 
+    let invalid = match args.value_of(options::INVALID).unwrap() {
+        "fail" => InvalidModes::Fail,
+        "warn" => InvalidModes::Warn,
+        "ignore" => InvalidModes::Ignore,
+        _ => InvalidModes::Abort,
+    };
+
     Ok(NumfmtOptions {
         transform,
         padding,
         header,
         fields,
         delimiter,
+        invalid,
     })
 }
 
@@ -204,21 +217,42 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .default_value(options::HEADER_DEFAULT)
                 .hide_default_value(true),
         )
+        .arg(
+            Arg::with_name(options::INVALID)
+                .long(options::INVALID)
+                .help(
+                    "failure mode for invalid numbers: MODE can be: \
+                     abort (default), fail, warn, ignore",
+                )
+                .value_name("MODE")
+                .possible_values(&["abort", "fail", "warn", "ignore"])
+                .default_value(options::INVALID_DEFAULT)
+                .hide_default_value(true),
+        )
         .arg(Arg::with_name(options::NUMBER).hidden(true).multiple(true))
         .get_matches_from(args);
 
-    let result =
-        parse_options(&matches).and_then(|options| match matches.values_of(options::NUMBER) {
-            Some(values) => handle_args(values, options),
-            None => handle_stdin(options),
-        });
+    let options = match parse_options(&matches) {
+        Ok(options) => options,
+        Err(e) => {
+            show_info!("{}", e);
+            return 1;
+        }
+    };
+
+    let result = match matches.values_of(options::NUMBER) {
+        Some(values) => handle_args(values, options),
+        None => handle_stdin(options),
+    };
 
+    // like GNU numfmt, invalid numbers exit with 2
     match result {
         Err(e) => {
             std::io::stdout().flush().expect("error flushing stdout");
             show_info!("{}", e);
-            1
+            2
         }
+        Ok(false) => 2,
         _ => 0,
     }
 }
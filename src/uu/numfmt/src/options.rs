@@ -8,6 +8,8 @@ pub const FROM: &str = "from";
 pub const FROM_DEFAULT: &str = "none";
 pub const HEADER: &str = "header";
 pub const HEADER_DEFAULT: &str = "1";
+pub const INVALID: &str = "invalid";
+pub const INVALID_DEFAULT: &str = "abort";
 pub const NUMBER: &str = "NUMBER";
 pub const PADDING: &str = "padding";
 pub const TO: &str = "to";
@@ -18,10 +20,24 @@ pub struct TransformOptions {
     pub to: Transform,
 }
 
+/// What to do with fields that can't be converted
+#[derive(Clone, Copy, PartialEq)]
+pub enum InvalidModes {
+    /// Stop with an error
+    Abort,
+    /// Print a warning and the unconverted field, and fail at the end
+    Fail,
+    /// Print a warning and the unconverted field
+    Warn,
+    /// Print the unconverted field
+    Ignore,
+}
+
 pub struct NumfmtOptions {
     pub transform: TransformOptions,
     pub padding: isize,
     pub header: usize,
     pub fields: Vec<Range>,
     pub delimiter: Option<String>,
+    pub invalid: InvalidModes,
 }
@@ -125,7 +125,11 @@ impl OdOptions {
             Some("little") => ByteOrder::Little,
             Some("big") => ByteOrder::Big,
             Some(s) => {
-                return Err(format!("Invalid argument --endian={}", s));
+                return Err(format!(
+                    "invalid argument '{}' for '--endian'\n\
+                     Valid arguments are:\n  - 'little'\n  - 'big'",
+                    s
+                ));
             }
         };
 
@@ -256,7 +260,6 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(options::ENDIAN)
                 .long(options::ENDIAN)
                 .help("byte order to use for multi-byte formats")
-                .possible_values(&["big", "little"])
                 .value_name("big|little"),
         )
         .arg(
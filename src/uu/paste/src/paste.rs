@@ -89,28 +89,30 @@ fn paste(filenames: Vec<String>, serial: bool, delimiters: String) {
         })
         .collect();
 
-    let delimiters: Vec<String> = unescape(delimiters)
-        .chars()
-        .map(|x| x.to_string())
-        .collect();
-    let mut delim_count = 0;
+    let delimiters = parse_delimiters(&delimiters);
 
     if serial {
         for file in &mut files {
             let mut output = String::new();
+            // Each file starts over with the first delimiter
+            let mut delim_count = 0;
+            let mut first_line = true;
             loop {
                 let mut line = String::new();
                 match read_line(file.as_mut(), &mut line) {
                     Ok(0) => break,
                     Ok(_) => {
-                        output.push_str(line.trim_end());
-                        output.push_str(&delimiters[delim_count % delimiters.len()]);
+                        if !first_line {
+                            output.push_str(&delimiters[delim_count % delimiters.len()]);
+                            delim_count += 1;
+                        }
+                        first_line = false;
+                        output.push_str(trim_newline(&line));
                     }
                     Err(e) => crash!(1, "{}", e.to_string()),
                 }
-                delim_count += 1;
             }
-            println!("{}", &output[..output.len() - 1]);
+            println!("{}", output);
         }
     } else {
         let mut eof: Vec<bool> = repeat(false).take(files.len()).collect();
@@ -118,6 +120,9 @@ fn paste(filenames: Vec<String>, serial: bool, delimiters: String) {
             let mut output = String::new();
             let mut eof_count = 0;
             for (i, file) in files.iter_mut().enumerate() {
+                if i > 0 {
+                    output.push_str(&delimiters[(i - 1) % delimiters.len()]);
+                }
                 if eof[i] {
                     eof_count += 1;
                 } else {
@@ -127,27 +132,52 @@ fn paste(filenames: Vec<String>, serial: bool, delimiters: String) {
                             eof[i] = true;
                             eof_count += 1;
                         }
-                        Ok(_) => output.push_str(line.trim_end()),
+                        Ok(_) => output.push_str(trim_newline(&line)),
                         Err(e) => crash!(1, "{}", e.to_string()),
                     }
                 }
-                output.push_str(&delimiters[delim_count % delimiters.len()]);
-                delim_count += 1;
             }
             if files.len() == eof_count {
                 break;
             }
-            println!("{}", &output[..output.len() - 1]);
-            delim_count = 0;
+            println!("{}", output);
         }
     }
 }
 
-// Unescape all special characters
-// TODO: this will need work to conform to GNU implementation
-fn unescape(s: String) -> String {
-    s.replace("\\n", "\n")
-        .replace("\\t", "\t")
-        .replace("\\\\", "\\")
-        .replace("\\", "")
+fn trim_newline(line: &str) -> &str {
+    if line.ends_with('\n') {
+        &line[..line.len() - 1]
+    } else {
+        line
+    }
+}
+
+// Split the delimiter list into the delimiters, unescaping the special characters;
+// `\0` is the empty delimiter, and so is an empty list
+fn parse_delimiters(s: &str) -> Vec<String> {
+    let mut delimiters = vec![];
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        let delimiter = if c == '\\' {
+            match chars.next() {
+                Some('0') => String::new(),
+                Some('b') => "\x08".to_owned(),
+                Some('f') => "\x0c".to_owned(),
+                Some('n') => "\n".to_owned(),
+                Some('r') => "\r".to_owned(),
+                Some('t') => "\t".to_owned(),
+                Some('v') => "\x0b".to_owned(),
+                Some(c) => c.to_string(),
+                None => crash!(1, "delimiter list ends with an unescaped backslash: {}", s),
+            }
+        } else {
+            c.to_string()
+        };
+        delimiters.push(delimiter);
+    }
+    if delimiters.is_empty() {
+        delimiters.push(String::new());
+    }
+    delimiters
 }
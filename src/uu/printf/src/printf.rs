@@ -80,6 +80,9 @@ static LONGHELP_BODY: &str = "
   %b - string parsed for literals
     second parameter is max length
 
+  %q - string quoted to be reused as shell input
+    no second parameter
+
   %c - char
     no second parameter
 
@@ -181,6 +184,10 @@ static LONGHELP_BODY: &str = "
         still be interpreted and not throw a warning, you will have problems if you use this for a
         literal whose code begins with zero, as it will be viewed as in \\0NNN form.)
 
+   %q - shell quoted string - the string is quoted so that the shell reads it back as the
+         same word, in the way of ls --quoting-style=shell-escape.
+         e.g. it's will be printed as \"it's\", and a newline as $'\\n'.
+
    CHAR SUBSTITUTIONS
     The character field does not have a secondary parameter.
 
@@ -17,6 +17,7 @@ use super::num_format::num_format;
 use super::token;
 use super::unescaped_text::UnescapedText;
 use crate::cli;
+use uucore::quoting_style::{escape_name, QuotingStyle};
 
 fn err_conv(sofar: &str) {
     cli::err_msg(&format!("%{}: invalid conversion specification", sofar));
@@ -69,7 +70,7 @@ impl Sub {
         // for more dry printing, field characters are grouped
         // in initialization of token.
         let field_type = match field_char {
-            's' | 'b' => FieldType::Strf,
+            's' | 'b' | 'q' => FieldType::Strf,
             'd' | 'i' | 'u' | 'o' | 'x' | 'X' => FieldType::Intf,
             'f' | 'F' => FieldType::Floatf,
             'a' | 'A' => FieldType::CninetyNineHexFloatf,
@@ -160,7 +161,7 @@ impl SubParser {
 
         let mut legal_fields = vec![
             // 'a', 'A', //c99 hex float implementation not yet complete
-            'b', 'c', 'd', 'e', 'E', 'f', 'F', 'g', 'G', 'i', 'o', 's', 'u', 'x', 'X',
+            'b', 'c', 'd', 'e', 'E', 'f', 'F', 'g', 'G', 'i', 'o', 'q', 's', 'u', 'x', 'X',
         ];
         let mut specifiers = vec!['h', 'j', 'l', 'L', 't', 'z'];
         legal_fields.sort_unstable();
@@ -300,6 +301,7 @@ impl SubParser {
                 && (self.min_width_tmp.is_some()
                     || self.past_decimal
                     || self.second_field_tmp.is_some()))
+            || (field_char == 'q' && (self.past_decimal || self.second_field_tmp.is_some()))
         {
             err_conv(&self.text_so_far);
         }
@@ -355,6 +357,7 @@ impl token::Token for Sub {
         let pre_min_width_opt: Option<String> = match *field.field_type {
             // if %s just return arg
             // if %b use UnescapedText module's unescaping-fn
+            // if %q quote arg for the shell
             // if %c return first char of arg
             FieldType::Strf | FieldType::Charf => {
                 match pf_arg {
@@ -369,6 +372,14 @@ impl token::Token for Sub {
                                 UnescapedText::from_it_core(&mut a_it, true);
                                 None
                             }
+                            'q' => Some(escape_name(
+                                arg_string,
+                                &QuotingStyle::Shell {
+                                    escape: true,
+                                    always_quote: false,
+                                    show_control: false,
+                                },
+                            )),
                             // for 'c': get iter of string vals,
                             // get opt<char> of first val
                             // and map it to opt<String>
@@ -11,9 +11,11 @@
 extern crate uucore;
 
 use clap::{App, Arg};
+use std::env;
 use std::fs;
-use std::path::{Path, PathBuf};
-use uucore::fs::{canonicalize, CanonicalizeMode};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use uucore::fs::{normalize_path, CanonicalizeMode};
 
 static ABOUT: &str = "print the resolved path";
 static VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -21,6 +23,12 @@ static VERSION: &str = env!("CARGO_PKG_VERSION");
 static OPT_QUIET: &str = "quiet";
 static OPT_STRIP: &str = "strip";
 static OPT_ZERO: &str = "zero";
+static OPT_CANONICALIZE_EXISTING: &str = "canonicalize-existing";
+static OPT_CANONICALIZE_MISSING: &str = "canonicalize-missing";
+static OPT_LOGICAL: &str = "logical";
+static OPT_PHYSICAL: &str = "physical";
+static OPT_RELATIVE_TO: &str = "relative-to";
+static OPT_RELATIVE_BASE: &str = "relative-base";
 
 static ARG_FILES: &str = "files";
 
@@ -53,6 +61,48 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .long(OPT_ZERO)
                 .help("Separate output filenames with \\0 rather than newline"),
         )
+        .arg(
+            Arg::with_name(OPT_CANONICALIZE_EXISTING)
+                .short("e")
+                .long(OPT_CANONICALIZE_EXISTING)
+                .overrides_with(OPT_CANONICALIZE_MISSING)
+                .help("all components of the path must exist"),
+        )
+        .arg(
+            Arg::with_name(OPT_CANONICALIZE_MISSING)
+                .short("m")
+                .long(OPT_CANONICALIZE_MISSING)
+                .overrides_with(OPT_CANONICALIZE_EXISTING)
+                .help("no path components need exist or be a directory"),
+        )
+        .arg(
+            Arg::with_name(OPT_LOGICAL)
+                .short("L")
+                .long(OPT_LOGICAL)
+                .overrides_with(OPT_PHYSICAL)
+                .help("resolve '..' components before symlinks"),
+        )
+        .arg(
+            Arg::with_name(OPT_PHYSICAL)
+                .short("P")
+                .long(OPT_PHYSICAL)
+                .overrides_with(OPT_LOGICAL)
+                .help("resolve symlinks as encountered (the default)"),
+        )
+        .arg(
+            Arg::with_name(OPT_RELATIVE_TO)
+                .long(OPT_RELATIVE_TO)
+                .takes_value(true)
+                .value_name("DIR")
+                .help("print the resolved path relative to DIR"),
+        )
+        .arg(
+            Arg::with_name(OPT_RELATIVE_BASE)
+                .long(OPT_RELATIVE_BASE)
+                .takes_value(true)
+                .value_name("DIR")
+                .help("print absolute paths unless paths below DIR"),
+        )
         .arg(
             Arg::with_name(ARG_FILES)
                 .multiple(true)
@@ -70,61 +120,185 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .map(PathBuf::from)
         .collect();
 
-    let strip = matches.is_present(OPT_STRIP);
+    let resolve = if matches.is_present(OPT_STRIP) {
+        Resolve::None
+    } else if matches.is_present(OPT_LOGICAL) {
+        Resolve::Logical
+    } else {
+        Resolve::Physical
+    };
     let zero = matches.is_present(OPT_ZERO);
     let quiet = matches.is_present(OPT_QUIET);
+    // Without -e or -m, all but the last component must exist
+    let can_mode = if matches.is_present(OPT_CANONICALIZE_EXISTING) {
+        CanonicalizeMode::Existing
+    } else if matches.is_present(OPT_CANONICALIZE_MISSING) {
+        CanonicalizeMode::Missing
+    } else {
+        CanonicalizeMode::Normal
+    };
+
+    let relative_to = matches
+        .value_of(OPT_RELATIVE_TO)
+        .map(|dir| canonicalize_dir(dir, resolve, can_mode));
+    let relative_base = matches
+        .value_of(OPT_RELATIVE_BASE)
+        .map(|dir| canonicalize_dir(dir, resolve, can_mode));
+    // Like in GNU realpath, the base is also the directory to print paths relative to if
+    // there's no other, and a directory outside of the base disables both
+    let (relative_to, relative_base) = match (relative_to, relative_base) {
+        (None, Some(base)) => (Some(base.clone()), Some(base)),
+        (Some(ref to), Some(ref base)) if !to.starts_with(base) => (None, None),
+        dirs => dirs,
+    };
+
     let mut retcode = 0;
     for path in &paths {
-        if !resolve_path(path, strip, zero, quiet) {
+        if !resolve_path(
+            path,
+            resolve,
+            zero,
+            quiet,
+            can_mode,
+            relative_to.as_deref(),
+            relative_base.as_deref(),
+        ) {
             retcode = 1
         };
     }
     retcode
 }
 
-fn resolve_path(p: &Path, strip: bool, zero: bool, quiet: bool) -> bool {
-    let abs = canonicalize(p, CanonicalizeMode::Normal).unwrap();
+/// How the symbolic links in the paths are handled
+#[derive(Clone, Copy)]
+enum Resolve {
+    /// Resolve them as they're encountered (`-P`)
+    Physical,
+    /// Remove the `..` components before resolving them (`-L`)
+    Logical,
+    /// Don't resolve them at all (`-s`)
+    None,
+}
+
+fn canonicalize_dir(dir: &str, resolve: Resolve, can_mode: CanonicalizeMode) -> PathBuf {
+    match canonicalize_path(Path::new(dir), resolve, can_mode) {
+        Ok(path) => path,
+        Err(e) => crash!(1, "{}: {}", dir, e),
+    }
+}
 
-    if strip {
-        if zero {
-            print!("{}\0", p.display());
-        } else {
-            println!("{}", p.display())
+/// The absolute form of `path`, without `.` and `..` components, and with its symbolic links
+/// resolved unless `resolve` is `Resolve::None`.
+fn canonicalize_path(
+    path: &Path,
+    resolve: Resolve,
+    can_mode: CanonicalizeMode,
+) -> io::Result<PathBuf> {
+    let abs = env::current_dir()?.join(path);
+    match resolve {
+        Resolve::Physical => resolve_links(&abs, can_mode),
+        Resolve::Logical => resolve_links(&normalize_path(&abs), can_mode),
+        Resolve::None => {
+            let abs = normalize_path(&abs);
+            if can_mode == CanonicalizeMode::Existing {
+                fs::symlink_metadata(&abs)?;
+            }
+            Ok(abs)
         }
-        return true;
+    }
+}
+
+/// Resolve the symbolic links in the absolute `path` as they're encountered, so that a `..`
+/// after one of them goes to the parent of its target. Like the other components, the last
+/// one must exist with -e, and none of them needs to with -m.
+fn resolve_links(path: &Path, can_mode: CanonicalizeMode) -> io::Result<PathBuf> {
+    fn reversed_components(path: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+        path.components()
+            .rev()
+            .map(|c| PathBuf::from(c.as_os_str()))
     }
 
     let mut result = PathBuf::new();
+    // the components left to resolve, the next one last
+    let mut parts: Vec<PathBuf> = reversed_components(path).collect();
     let mut links_left = 256;
-
-    for part in abs.components() {
-        result.push(part.as_os_str());
-        loop {
-            if links_left == 0 {
-                if !quiet {
-                    show_error!("Too many symbolic links: {}", p.display())
-                };
-                return false;
+    while let Some(part) = parts.pop() {
+        match part.components().next() {
+            Some(Component::CurDir) => continue,
+            Some(Component::ParentDir) => {
+                result.pop();
+                continue;
             }
-            match fs::metadata(result.as_path()) {
-                Err(_) => break,
-                Ok(ref m) if !m.file_type().is_symlink() => break,
-                Ok(_) => {
-                    links_left -= 1;
-                    match fs::read_link(result.as_path()) {
-                        Ok(x) => {
-                            result.pop();
-                            result.push(x.as_path());
-                        }
-                        _ => {
-                            if !quiet {
-                                show_error!("Invalid path: {}", p.display())
-                            };
-                            return false;
-                        }
-                    }
+            _ => result.push(&part),
+        }
+        match fs::symlink_metadata(&result) {
+            Ok(ref metadata) if metadata.file_type().is_symlink() => {
+                if links_left == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "Too many levels of symbolic links",
+                    ));
                 }
+                links_left -= 1;
+                let target = fs::read_link(&result)?;
+                result.pop();
+                parts.extend(reversed_components(&target));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                let may_be_missing = match can_mode {
+                    CanonicalizeMode::Missing => true,
+                    CanonicalizeMode::Existing => false,
+                    _ => parts.is_empty(),
+                };
+                if !may_be_missing {
+                    return Err(e);
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// The path of `path` relative to the directory `dir`, both of them absolute and resolved.
+fn relative_path(path: &Path, dir: &Path) -> PathBuf {
+    let mut path_components = path.components().peekable();
+    let mut dir_components = dir.components().peekable();
+    while path_components.peek().is_some() && path_components.peek() == dir_components.peek() {
+        path_components.next();
+        dir_components.next();
+    }
+
+    let mut result: PathBuf = dir_components.map(|_| Component::ParentDir).collect();
+    result.extend(path_components);
+    if result.as_os_str().is_empty() {
+        result.push(Component::CurDir);
+    }
+    result
+}
+
+fn resolve_path(
+    p: &Path,
+    resolve: Resolve,
+    zero: bool,
+    quiet: bool,
+    can_mode: CanonicalizeMode,
+    relative_to: Option<&Path>,
+    relative_base: Option<&Path>,
+) -> bool {
+    let mut result = match canonicalize_path(p, resolve, can_mode) {
+        Ok(abs) => abs,
+        Err(e) => {
+            if !quiet {
+                show_error!("{}: {}", p.display(), e);
             }
+            return false;
+        }
+    };
+
+    if let Some(dir) = relative_to {
+        if relative_base.map_or(true, |base| result.starts_with(base)) {
+            result = relative_path(&result, dir);
         }
     }
 
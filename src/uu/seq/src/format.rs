@@ -0,0 +1,242 @@
+//  * This file is part of the uutils coreutils package.
+//  *
+//  * For the full copyright and license information, please view the LICENSE
+//  * file that was distributed with this source code.
+
+//! The `printf`-style formats of `seq -f`, which contain a single floating point conversion
+//! (`%e`, `%f` or `%g`, and their uppercase variants), with the usual flags, width and precision.
+
+#[derive(Default)]
+struct Spec {
+    left_align: bool,
+    plus_sign: bool,
+    space_sign: bool,
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+    conversion: char,
+}
+
+pub struct Format {
+    prefix: String,
+    spec: Spec,
+    suffix: String,
+}
+
+/// Appends the text of `format` up to the next directive to `text`, replacing `%%` with `%`,
+/// and returns the rest of `format`, starting after the `%` of the directive.
+fn take_text<'a>(format: &'a str, text: &mut String) -> Option<&'a str> {
+    let mut rest = format;
+    while let Some(i) = rest.find('%') {
+        text.push_str(&rest[..i]);
+        if rest[i + 1..].starts_with('%') {
+            text.push('%');
+            rest = &rest[i + 2..];
+        } else {
+            return Some(&rest[i + 1..]);
+        }
+    }
+    text.push_str(rest);
+    None
+}
+
+/// Returns the leading decimal digits of `s` and the rest of it.
+fn take_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    (&s[..end], &s[end..])
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Result<Format, String> {
+        let mut prefix = String::new();
+        let mut rest = match take_text(format, &mut prefix) {
+            Some(rest) => rest,
+            None => return Err(format!("format '{}' has no % directive", format)),
+        };
+
+        let mut spec = Spec::default();
+        loop {
+            match rest.chars().next() {
+                Some('-') => spec.left_align = true,
+                Some('+') => spec.plus_sign = true,
+                Some(' ') => spec.space_sign = true,
+                Some('#') => spec.alternate = true,
+                Some('0') => spec.zero_pad = true,
+                _ => break,
+            }
+            rest = &rest[1..];
+        }
+        let (width, after_width) = take_digits(rest);
+        spec.width = width.parse().unwrap_or(0);
+        rest = after_width;
+        if rest.starts_with('.') {
+            let (precision, after_precision) = take_digits(&rest[1..]);
+            spec.precision = Some(precision.parse().unwrap_or(0));
+            rest = after_precision;
+        }
+        if rest.starts_with('L') {
+            rest = &rest[1..];
+        }
+        spec.conversion = match rest.chars().next() {
+            Some(c) if "eEfFgG".contains(c) => c,
+            Some(c) => return Err(format!("format '{}' has unknown %{} directive", format, c)),
+            None => return Err(format!("format '{}' ends in %", format)),
+        };
+        rest = &rest[spec.conversion.len_utf8()..];
+
+        let mut suffix = String::new();
+        if take_text(rest, &mut suffix).is_some() {
+            return Err(format!("format '{}' has too many % directives", format));
+        }
+
+        Ok(Format {
+            prefix,
+            spec,
+            suffix,
+        })
+    }
+
+    pub fn format(&self, number: f64) -> String {
+        let spec = &self.spec;
+        let sign = if number.is_sign_negative() && !number.is_nan() {
+            "-"
+        } else if spec.plus_sign {
+            "+"
+        } else if spec.space_sign {
+            " "
+        } else {
+            ""
+        };
+
+        let mut digits = if number.is_finite() {
+            let precision = spec.precision.unwrap_or(6);
+            match spec.conversion.to_ascii_lowercase() {
+                'e' => format_exponent(number.abs(), precision, spec.alternate),
+                'f' => format_fixed(number.abs(), precision, spec.alternate),
+                _ => format_general(number.abs(), precision, spec.alternate),
+            }
+        } else if number.is_nan() {
+            "nan".to_string()
+        } else {
+            "inf".to_string()
+        };
+        if spec.conversion.is_ascii_uppercase() {
+            digits = digits.to_ascii_uppercase();
+        }
+
+        let len = sign.len() + digits.len();
+        let padding = spec.width.saturating_sub(len);
+        let number = if spec.left_align {
+            format!("{}{}{}", sign, digits, " ".repeat(padding))
+        } else if spec.zero_pad && number.is_finite() {
+            format!("{}{}{}", sign, "0".repeat(padding), digits)
+        } else {
+            format!("{}{}{}", " ".repeat(padding), sign, digits)
+        };
+        format!("{}{}{}", self.prefix, number, self.suffix)
+    }
+}
+
+/// `%f`
+fn format_fixed(number: f64, precision: usize, alternate: bool) -> String {
+    let mut s = format!("{:.*}", precision, number);
+    if alternate && precision == 0 {
+        s.push('.');
+    }
+    s
+}
+
+/// `%e`, with an exponent of at least two digits like in C
+fn format_exponent(number: f64, precision: usize, alternate: bool) -> String {
+    let s = format!("{:.*e}", precision, number);
+    let e = s.find('e').unwrap();
+    let (mantissa, exponent) = (&s[..e], &s[e + 1..]);
+    let (exponent_sign, exponent) = if exponent.starts_with('-') {
+        ('-', &exponent[1..])
+    } else {
+        ('+', exponent)
+    };
+    let point = if alternate && precision == 0 { "." } else { "" };
+    format!("{}{}e{}{:0>2}", mantissa, point, exponent_sign, exponent)
+}
+
+/// `%g`, which is `%e` for very small or large exponents and `%f` otherwise, without
+/// trailing zeros unless `alternate` is set
+fn format_general(number: f64, precision: usize, alternate: bool) -> String {
+    let precision = if precision == 0 { 1 } else { precision };
+    // The exponent after rounding to the precision
+    let exponent = {
+        let s = format!("{:.*e}", precision - 1, number);
+        s[s.find('e').unwrap() + 1..].parse::<i32>().unwrap()
+    };
+    let s = if exponent < -4 || exponent >= precision as i32 {
+        format_exponent(number, precision - 1, alternate)
+    } else {
+        format_fixed(
+            number,
+            (precision as i32 - 1 - exponent) as usize,
+            alternate,
+        )
+    };
+    if alternate || !s.contains('.') {
+        return s;
+    }
+    let (mantissa, exponent) = match s.find('e') {
+        Some(e) => s.split_at(e),
+        None => (&s[..], ""),
+    };
+    format!(
+        "{}{}",
+        mantissa.trim_end_matches('0').trim_end_matches('.'),
+        exponent
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(format: &str, number: f64) -> String {
+        Format::parse(format).unwrap().format(number)
+    }
+
+    #[test]
+    fn general() {
+        assert_eq!(format("%g", 1.0), "1");
+        assert_eq!(format("%g", 0.1), "0.1");
+        assert_eq!(format("%g", 100000.0), "100000");
+        assert_eq!(format("%g", 1000000.0), "1e+06");
+        assert_eq!(format("%g", 0.0001), "0.0001");
+        assert_eq!(format("%g", 0.00001), "1e-05");
+        assert_eq!(format("%G", 1e10), "1E+10");
+        assert_eq!(format("%#g", 1.0), "1.00000");
+        assert_eq!(format("%.3g", 999.9), "1e+03");
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(format("%e", 0.0), "0.000000e+00");
+        assert_eq!(format("%+.3e", 1000.0), "+1.000e+03");
+        assert_eq!(format("%.0e", 15.0), "2e+01");
+        assert_eq!(format("%.1e", 1e-100), "1.0e-100");
+    }
+
+    #[test]
+    fn fixed_and_padding() {
+        assert_eq!(format("%03.0f", 8.0), "008");
+        assert_eq!(format("%-5.2f|", 1.0), "1.00 |");
+        assert_eq!(format("%06.1f", -2.5), "-002.5");
+        assert_eq!(format("% f", 1.0), " 1.000000");
+        assert_eq!(format("%5g", std::f64::INFINITY), "  inf");
+        assert_eq!(format("%%%g%%", 1.0), "%1%");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Format::parse("abc").is_err());
+        assert!(Format::parse("%g %g").is_err());
+        assert!(Format::parse("%d").is_err());
+        assert!(Format::parse("%10").is_err());
+    }
+}
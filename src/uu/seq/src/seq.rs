@@ -1,20 +1,22 @@
-// TODO: Make -w flag work with decimals
-// TODO: Support -f flag
-
 // spell-checker:ignore (ToDO) istr chiter argptr ilen
 
 #[macro_use]
 extern crate uucore;
 
+mod format;
+
 use clap::{App, AppSettings, Arg};
 use std::cmp;
 use std::io::{stdout, Write};
 
+use crate::format::Format;
+
 static VERSION: &str = env!("CARGO_PKG_VERSION");
 static ABOUT: &str = "Display numbers from FIRST to LAST, in steps of INCREMENT.";
+static OPT_FORMAT: &str = "format";
 static OPT_SEPARATOR: &str = "separator";
 static OPT_TERMINATOR: &str = "terminator";
-static OPT_WIDTHS: &str = "widths";
+static OPT_WIDTHS: &str = "equal-width";
 
 static ARG_NUMBERS: &str = "numbers";
 
@@ -39,10 +41,7 @@ fn parse_float(mut s: &str) -> Result<f64, String> {
     }
     match s.parse() {
         Ok(n) => Ok(n),
-        Err(e) => Err(format!(
-            "seq: invalid floating point argument `{}`: {}",
-            s, e
-        )),
+        Err(_) => Err(format!("invalid floating point argument: '{}'", s)),
     }
 }
 
@@ -57,6 +56,15 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .version(VERSION)
         .about(ABOUT)
         .usage(&usage[..])
+        .arg(
+            Arg::with_name(OPT_FORMAT)
+                .short("f")
+                .long(OPT_FORMAT)
+                .help("use printf style floating-point FORMAT")
+                .takes_value(true)
+                .number_of_values(1)
+                .value_name("FORMAT"),
+        )
         .arg(
             Arg::with_name(OPT_SEPARATOR)
                 .short("s")
@@ -69,14 +77,15 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(OPT_TERMINATOR)
                 .short("t")
                 .long("terminator")
-                .help("Terminator character (defaults to separator)")
+                .help("Terminator character (defaults to \\n)")
                 .takes_value(true)
                 .number_of_values(1),
         )
         .arg(
             Arg::with_name(OPT_WIDTHS)
                 .short("w")
-                .long("widths")
+                .long(OPT_WIDTHS)
+                .alias("widths")
                 .help("Equalize widths of all numbers by padding with zeros"),
         )
         .arg(
@@ -99,14 +108,29 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     options.terminator = matches.value_of(OPT_TERMINATOR).map(String::from);
     options.widths = matches.is_present(OPT_WIDTHS);
 
+    let format = match matches.value_of(OPT_FORMAT) {
+        Some(_) if options.widths => {
+            show_usage_error!(
+                "format string may not be specified when printing equal width strings"
+            );
+            return 1;
+        }
+        Some(format) => match Format::parse(format) {
+            Ok(format) => Some(format),
+            Err(e) => {
+                show_error!("{}", e);
+                return 1;
+            }
+        },
+        None => None,
+    };
+
     let mut largest_dec = 0;
-    let mut padding = 0;
     let first = if numbers.len() > 1 {
         let slice = numbers[0];
         let len = slice.len();
         let dec = slice.find('.').unwrap_or(len);
         largest_dec = len - dec;
-        padding = dec;
         match parse_float(slice) {
             Ok(n) => n,
             Err(s) => {
@@ -122,7 +146,6 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         let len = slice.len();
         let dec = slice.find('.').unwrap_or(len);
         largest_dec = cmp::max(largest_dec, len - dec);
-        padding = cmp::max(padding, dec);
         match parse_float(slice) {
             Ok(n) => n,
             Err(s) => {
@@ -134,12 +157,11 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         1.0
     };
     if increment == 0.0 {
-        show_error!("increment value: '{}'", numbers[1]);
+        show_error!("invalid Zero increment value: '{}'", numbers[1]);
         return 1;
     }
     let last = {
         let slice = numbers[numbers.len() - 1];
-        padding = cmp::max(padding, slice.find('.').unwrap_or_else(|| slice.len()));
         match parse_float(slice) {
             Ok(n) => n,
             Err(s) => {
@@ -154,7 +176,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     let separator = escape_sequences(&options.separator[..]);
     let terminator = match options.terminator {
         Some(term) => escape_sequences(&term[..]),
-        None => separator.clone(),
+        None => "\n".to_string(),
     };
     print_seq(
         first,
@@ -164,17 +186,43 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         separator,
         terminator,
         options.widths,
-        padding,
+        format.as_ref(),
     );
 
     0
 }
 
-fn done_printing(next: f64, increment: f64, last: f64) -> bool {
-    if increment >= 0f64 {
-        next > last
+/// The number of increments from `first` that stay within `last`, or `None` if the sequence is
+/// empty.
+///
+/// This is computed once rather than by accumulating `increment`, so that rounding errors don't
+/// add up; a quotient that is a whole number up to rounding errors (like `0.3 / 0.1`) counts as
+/// one, so that `last` itself is printed.
+fn count_increments(first: f64, increment: f64, last: f64) -> Option<u64> {
+    let steps = (last - first) / increment;
+    let rounded = steps.round();
+    let steps = if (steps - rounded).abs() < 1e-9 * rounded.abs().max(1.0) {
+        rounded
+    } else {
+        steps.floor()
+    };
+    if steps >= 0.0 {
+        Some(steps as u64)
+    } else {
+        None
+    }
+}
+
+/// Left-pad `number` with zeros to `width`, after its sign if it is negative.
+fn pad_with_zeros(number: &str, width: usize) -> String {
+    if number.len() >= width {
+        return number.to_string();
+    }
+    let zeros = "0".repeat(width - number.len());
+    if number.starts_with('-') {
+        format!("-{}{}", zeros, &number[1..])
     } else {
-        next < last
+        format!("{}{}", zeros, number)
     }
 }
 
@@ -187,28 +235,31 @@ fn print_seq(
     separator: String,
     terminator: String,
     pad: bool,
-    padding: usize,
+    format: Option<&Format>,
 ) {
-    let mut i = 0isize;
-    let mut value = first + i as f64 * increment;
-    while !done_printing(value, increment, last) {
-        let istr = format!("{:.*}", largest_dec, value);
-        let ilen = istr.len();
-        let before_dec = istr.find('.').unwrap_or(ilen);
-        if pad && before_dec < padding {
-            for _ in 0..(padding - before_dec) {
-                print!("0");
-            }
+    let count = match count_increments(first, increment, last) {
+        Some(count) => count,
+        None => return,
+    };
+    let format = |i: u64| {
+        let value = first + i as f64 * increment;
+        match format {
+            Some(format) => format.format(value),
+            None => format!("{:.*}", largest_dec, value),
         }
-        print!("{}", istr);
-        i += 1;
-        value = first + i as f64 * increment;
-        if !done_printing(value, increment, last) {
+    };
+    // The widest number is at either end of the sequence
+    let width = if pad {
+        cmp::max(format(0).len(), format(count).len())
+    } else {
+        0
+    };
+    for i in 0..=count {
+        print!("{}", pad_with_zeros(&format(i), width));
+        if i < count {
             print!("{}", separator);
         }
     }
-    if (first >= last && increment < 0f64) || (first <= last && increment > 0f64) {
-        print!("{}", terminator);
-    }
+    print!("{}", terminator);
     crash_if_err!(1, stdout().flush());
 }
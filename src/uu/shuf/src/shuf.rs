@@ -12,8 +12,9 @@ extern crate uucore;
 
 use clap::{App, Arg};
 use rand::Rng;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{stdin, stdout, BufReader, BufWriter, Read, Write};
+use std::io::{stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
 use uucore::InvalidEncodingHandling;
 
 enum Mode {
@@ -159,33 +160,43 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         Mode::Echo(args) => {
             let mut evec = args.iter().map(String::as_bytes).collect::<Vec<_>>();
             find_seps(&mut evec, options.sep);
-            shuf_bytes(&mut evec, options);
+            let mut rng = open_rng(&options);
+            shuf_bytes(&mut evec, options, &mut rng);
         }
         Mode::InputRange((b, e)) => {
-            let rvec = (b..e).map(|x| format!("{}", x)).collect::<Vec<String>>();
-            let mut rvec = rvec.iter().map(String::as_bytes).collect::<Vec<&[u8]>>();
-            shuf_bytes(&mut rvec, options);
+            shuf_range(b, e, options);
+        }
+        Mode::Default(filename) if options.head_count != std::usize::MAX && !options.repeat => {
+            let mut rng = open_rng(&options);
+            let sample = sample_input_file(&filename, &options, &mut rng);
+            let mut sample = sample.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            shuf_bytes(&mut sample, options, &mut rng);
         }
         Mode::Default(filename) => {
             let fdata = read_input_file(&filename);
             let mut fdata = vec![&fdata[..]];
             find_seps(&mut fdata, options.sep);
-            shuf_bytes(&mut fdata, options);
+            let mut rng = open_rng(&options);
+            shuf_bytes(&mut fdata, options, &mut rng);
         }
     }
 
     0
 }
 
-fn read_input_file(filename: &str) -> Vec<u8> {
-    let mut file = BufReader::new(if filename == "-" {
+fn open_input_file(filename: &str) -> BufReader<Box<dyn Read>> {
+    BufReader::new(if filename == "-" {
         Box::new(stdin()) as Box<dyn Read>
     } else {
         match File::open(filename) {
             Ok(f) => Box::new(f) as Box<dyn Read>,
             Err(e) => crash!(1, "failed to open '{}': {}", filename, e),
         }
-    });
+    })
+}
+
+fn read_input_file(filename: &str) -> Vec<u8> {
+    let mut file = open_input_file(filename);
 
     let mut data = Vec::new();
     if let Err(e) = file.read_to_end(&mut data) {
@@ -195,6 +206,42 @@ fn read_input_file(filename: &str) -> Vec<u8> {
     data
 }
 
+/// Choose `opts.head_count` lines of the input uniformly at random, keeping only that many lines
+/// in memory (reservoir sampling). The chosen lines are not in a random order yet.
+fn sample_input_file(filename: &str, opts: &Options, rng: &mut WrappedRng) -> Vec<Vec<u8>> {
+    let mut file = open_input_file(filename);
+    let mut reservoir: Vec<Vec<u8>> = Vec::new();
+    if opts.head_count == 0 {
+        return reservoir;
+    }
+
+    let mut seen = 0;
+    loop {
+        let mut line = Vec::new();
+        match file.read_until(opts.sep, &mut line) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(e) => crash!(1, "failed reading '{}': {}", filename, e),
+        }
+        if line.last() == Some(&opts.sep) {
+            line.pop();
+        }
+
+        // the line replaces a randomly chosen one with probability head_count / (seen + 1)
+        if reservoir.len() < opts.head_count {
+            reservoir.push(line);
+        } else {
+            let r = rng.gen_range(0, seen + 1);
+            if r < opts.head_count {
+                reservoir[r] = line;
+            }
+        }
+        seen += 1;
+    }
+
+    reservoir
+}
+
 fn find_seps(data: &mut Vec<&[u8]>, sep: u8) {
     // need to use for loop so we don't borrow the vector as we modify it in place
     // basic idea:
@@ -228,22 +275,28 @@ fn find_seps(data: &mut Vec<&[u8]>, sep: u8) {
     }
 }
 
-fn shuf_bytes(input: &mut Vec<&[u8]>, opts: Options) {
-    let mut output = BufWriter::new(match opts.output {
+fn open_output(opts: &Options) -> BufWriter<Box<dyn Write>> {
+    BufWriter::new(match opts.output {
         None => Box::new(stdout()) as Box<dyn Write>,
-        Some(s) => match File::create(&s[..]) {
+        Some(ref s) => match File::create(&s[..]) {
             Ok(f) => Box::new(f) as Box<dyn Write>,
             Err(e) => crash!(1, "failed to open '{}' for writing: {}", &s[..], e),
         },
-    });
+    })
+}
 
-    let mut rng = match opts.random_source {
-        Some(r) => WrappedRng::RngFile(rand::read::ReadRng::new(match File::open(&r[..]) {
+fn open_rng(opts: &Options) -> WrappedRng {
+    match opts.random_source {
+        Some(ref r) => WrappedRng::RngFile(rand::read::ReadRng::new(match File::open(&r[..]) {
             Ok(f) => f,
             Err(e) => crash!(1, "failed to open random source '{}': {}", &r[..], e),
         })),
         None => WrappedRng::RngDefault(rand::thread_rng()),
-    };
+    }
+}
+
+fn shuf_bytes(input: &mut Vec<&[u8]>, opts: Options, rng: &mut WrappedRng) {
+    let mut output = open_output(&opts);
 
     // we're generating a random usize. To keep things fair, we take this number mod ceil(log2(length+1))
     let mut len_mod = 1;
@@ -281,6 +334,39 @@ fn shuf_bytes(input: &mut Vec<&[u8]>, opts: Options) {
     }
 }
 
+/// Shuffle the numbers in `begin..end` without storing all of them, so that picking a few numbers
+/// out of a huge range is cheap: this is a Fisher-Yates shuffle, where only the positions that
+/// were swapped are kept in a map.
+fn shuf_range(begin: usize, end: usize, opts: Options) {
+    let mut output = open_output(&opts);
+    let mut rng = open_rng(&opts);
+
+    let mut swapped: HashMap<usize, usize> = HashMap::new();
+    let mut remaining = end - begin;
+    let mut count = opts.head_count;
+    while count > 0 && remaining > 0 {
+        let r = rng.gen_range(0, remaining);
+        let value = if opts.repeat {
+            r
+        } else {
+            // the value at position r is replaced with the last remaining one
+            remaining -= 1;
+            let value = *swapped.get(&r).unwrap_or(&r);
+            let last = *swapped.get(&remaining).unwrap_or(&remaining);
+            swapped.insert(r, last);
+            swapped.remove(&remaining);
+            value
+        };
+
+        write!(output, "{}", begin + value).unwrap_or_else(|e| crash!(1, "write failed: {}", e));
+        output
+            .write_all(&[opts.sep])
+            .unwrap_or_else(|e| crash!(1, "write failed: {}", e));
+
+        count -= 1;
+    }
+}
+
 fn parse_range(input_range: &str) -> Result<(usize, usize), String> {
     let split: Vec<&str> = input_range.split('-').collect();
     if split.len() != 2 {
@@ -294,6 +380,9 @@ fn parse_range(input_range: &str) -> Result<(usize, usize), String> {
             Ok(m) => m,
             Err(_) => return Err(format!("invalid input range: '{}'", split[1])),
         };
+        if end + 1 < begin {
+            return Err(format!("invalid input range: '{}'", input_range));
+        }
         Ok((begin, end + 1))
     }
 }
@@ -310,4 +399,11 @@ impl WrappedRng {
             WrappedRng::RngDefault(ref mut r) => r.gen(),
         }
     }
+
+    fn gen_range(&mut self, low: usize, high: usize) -> usize {
+        match *self {
+            WrappedRng::RngFile(ref mut r) => r.gen_range(low, high),
+            WrappedRng::RngDefault(ref mut r) => r.gen_range(low, high),
+        }
+    }
 }
@@ -30,7 +30,6 @@ pub struct NumInfo {
 }
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct NumInfoParseSettings {
-    pub accept_si_units: bool,
     pub thousands_separator: Option<char>,
     pub decimal_pt: Option<char>,
 }
@@ -38,7 +37,6 @@ pub struct NumInfoParseSettings {
 impl Default for NumInfoParseSettings {
     fn default() -> Self {
         Self {
-            accept_si_units: false,
             thousands_separator: None,
             decimal_pt: Some('.'),
         }
@@ -77,29 +75,8 @@ impl NumInfo {
             }
 
             if Self::is_invalid_char(char, &mut had_decimal_pt, &parse_settings) {
-                let si_unit = if parse_settings.accept_si_units {
-                    match char {
-                        'K' | 'k' => 3,
-                        'M' => 6,
-                        'G' => 9,
-                        'T' => 12,
-                        'P' => 15,
-                        'E' => 18,
-                        'Z' => 21,
-                        'Y' => 24,
-                        _ => 0,
-                    }
-                } else {
-                    0
-                };
                 return if let Some(start) = start {
-                    (
-                        NumInfo {
-                            exponent: exponent + si_unit,
-                            sign,
-                        },
-                        start..idx,
-                    )
+                    (NumInfo { exponent, sign }, start..idx)
                 } else {
                     (
                         NumInfo {
@@ -221,6 +198,127 @@ pub fn numeric_str_cmp((a, a_info): (&str, &NumInfo), (b, b_info): (&str, &NumIn
     }
 }
 
+/// The unit of a human-readable number, like the `K` in `4.0K`.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+pub struct HumanUnit {
+    /// The power of the base this unit stands for, e.g. 2 for `M`.
+    power: u8,
+    /// Whether the base is 1024 or 1000.
+    binary: bool,
+}
+
+impl HumanUnit {
+    /// The unit of numbers without a suffix.
+    pub const NONE: HumanUnit = HumanUnit {
+        power: 0,
+        binary: false,
+    };
+
+    /// Parse the unit at the start of `suffix`, which should directly follow the number.
+    /// Also returns the length of the unit.
+    ///
+    /// `K`, `M`, `G`, ... are powers of 1024 like in the output of `du -h`, as are `Ki` and `KiB`.
+    /// `k` and a unit directly followed by `B`, like `KB`, are powers of 1000.
+    pub fn parse(suffix: &str) -> (Self, usize) {
+        let bytes = suffix.as_bytes();
+        let power = match bytes.first() {
+            Some(b'K') | Some(b'k') => 1,
+            Some(b'M') => 2,
+            Some(b'G') => 3,
+            Some(b'T') => 4,
+            Some(b'P') => 5,
+            Some(b'E') => 6,
+            Some(b'Z') => 7,
+            Some(b'Y') => 8,
+            _ => return (Self::NONE, 0),
+        };
+        match (bytes.get(1), bytes.get(2)) {
+            (Some(b'i'), Some(b'B')) => (
+                Self {
+                    power,
+                    binary: true,
+                },
+                3,
+            ),
+            (Some(b'i'), _) => (
+                Self {
+                    power,
+                    binary: true,
+                },
+                2,
+            ),
+            (Some(b'B'), _) => (
+                Self {
+                    power,
+                    binary: false,
+                },
+                2,
+            ),
+            _ => (
+                Self {
+                    power,
+                    binary: bytes[0] != b'k',
+                },
+                1,
+            ),
+        }
+    }
+
+    /// Multiply the number given by `digits` * 10^`exponent` by this unit.
+    fn apply(self, digits: &mut Vec<u8>, exponent: &mut i64) {
+        if !self.binary {
+            *exponent += 3 * self.power as i64;
+            return;
+        }
+        for _ in 0..self.power {
+            let mut carry = 0;
+            for digit in digits.iter_mut().rev() {
+                let product = *digit as u32 * 1024 + carry;
+                *digit = (product % 10) as u8;
+                carry = product / 10;
+            }
+            while carry > 0 {
+                digits.insert(0, (carry % 10) as u8);
+                carry /= 10;
+                *exponent += 1;
+            }
+        }
+    }
+}
+
+/// Compare two human-readable numbers (see [HumanUnit]) by their value, falling back to
+/// [numeric_str_cmp] if they have the same unit.
+pub fn human_numeric_str_cmp(
+    (a, a_info, a_unit): (&str, &NumInfo, HumanUnit),
+    (b, b_info, b_unit): (&str, &NumInfo, HumanUnit),
+) -> Ordering {
+    // Units don't matter if one of the numbers is zero (or no number at all).
+    if a_unit == b_unit || a_info.sign != b_info.sign || a.is_empty() || b.is_empty() {
+        return numeric_str_cmp((a, a_info), (b, b_info));
+    }
+
+    let scale = |num: &str, info: &NumInfo, unit: HumanUnit| {
+        let mut digits: Vec<u8> = num
+            .bytes()
+            .filter(|c| c.is_ascii_digit())
+            .map(|c| c - b'0')
+            .collect();
+        let mut exponent = info.exponent;
+        unit.apply(&mut digits, &mut exponent);
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        (exponent, digits)
+    };
+    let ordering = scale(a, a_info, a_unit).cmp(&scale(b, b_info, b_unit));
+
+    if a_info.sign == Sign::Negative {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -443,13 +541,7 @@ mod tests {
     }
     #[test]
     fn invalid_with_unit() {
-        let info = NumInfo::parse(
-            "-K",
-            NumInfoParseSettings {
-                accept_si_units: true,
-                ..Default::default()
-            },
-        );
+        let info = NumInfo::parse("-K", Default::default());
         assert_eq!(
             info,
             (
@@ -461,4 +553,67 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn parses_units() {
+        let binary = |power| HumanUnit {
+            power,
+            binary: true,
+        };
+        let decimal = |power| HumanUnit {
+            power,
+            binary: false,
+        };
+        assert_eq!(HumanUnit::parse(""), (HumanUnit::NONE, 0));
+        assert_eq!(HumanUnit::parse("B"), (HumanUnit::NONE, 0));
+        assert_eq!(HumanUnit::parse("x"), (HumanUnit::NONE, 0));
+        assert_eq!(HumanUnit::parse("K"), (binary(1), 1));
+        assert_eq!(HumanUnit::parse("k"), (decimal(1), 1));
+        assert_eq!(HumanUnit::parse("MB"), (decimal(2), 2));
+        assert_eq!(HumanUnit::parse("Gi"), (binary(3), 2));
+        assert_eq!(HumanUnit::parse("TiB foo"), (binary(4), 3));
+        assert_eq!(HumanUnit::parse("Y"), (binary(8), 1));
+    }
+
+    fn human_helper(a: &str, b: &str, expected: Ordering) {
+        let parse = |n: &str| {
+            let (info, range) = NumInfo::parse(n, Default::default());
+            let unit = HumanUnit::parse(&n[range.end..]).0;
+            (n[range].to_owned(), info, unit)
+        };
+        let (a, a_info, a_unit) = parse(a);
+        let (b, b_info, b_unit) = parse(b);
+        let ordering = human_numeric_str_cmp((&a, &a_info, a_unit), (&b, &b_info, b_unit));
+        assert_eq!(ordering, expected);
+        let ordering = human_numeric_str_cmp((&b, &b_info, b_unit), (&a, &a_info, a_unit));
+        assert_eq!(ordering, expected.reverse());
+    }
+    #[test]
+    fn test_human_units() {
+        human_helper("1K", "2M", Ordering::Less);
+        human_helper("2M", "1G", Ordering::Less);
+        human_helper("1025", "1K", Ordering::Greater);
+        human_helper("1000K", "1M", Ordering::Less);
+        human_helper("1024K", "1M", Ordering::Equal);
+        human_helper("1000KB", "1MB", Ordering::Equal);
+        human_helper("1.5Gi", "1536M", Ordering::Equal);
+        human_helper("0.8M", "8981K", Ordering::Less);
+        human_helper("1k", "1K", Ordering::Less);
+        human_helper("999.9", "1k", Ordering::Less);
+    }
+    #[test]
+    fn test_human_trailing_b() {
+        human_helper("512B", "512", Ordering::Equal);
+        human_helper("1KB", "999B", Ordering::Greater);
+        human_helper("1.0KiB", "1K", Ordering::Equal);
+    }
+    #[test]
+    fn test_human_zero_and_sign() {
+        human_helper("0", "0K", Ordering::Equal);
+        human_helper("0G", "1", Ordering::Less);
+        human_helper("-1K", "-1000", Ordering::Less);
+        human_helper("-1G", "1K", Ordering::Less);
+        human_helper("-0", "0K", Ordering::Less);
+        human_helper("foo", "0M", Ordering::Equal);
+    }
 }
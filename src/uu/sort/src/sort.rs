@@ -17,16 +17,17 @@ extern crate uucore;
 
 mod external_sort;
 mod numeric_str_cmp;
+mod version_cmp;
 
 use clap::{App, Arg};
 use external_sort::{ExternalSorter, ExternallySortable};
 use fnv::FnvHasher;
 use itertools::Itertools;
-use numeric_str_cmp::{numeric_str_cmp, NumInfo, NumInfoParseSettings};
-use rand::distributions::Alphanumeric;
+use numeric_str_cmp::{
+    human_numeric_str_cmp, numeric_str_cmp, HumanUnit, NumInfo, NumInfoParseSettings,
+};
 use rand::{thread_rng, Rng};
 use rayon::prelude::*;
-use semver::Version;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use std::cmp::Ordering;
@@ -34,7 +35,7 @@ use std::collections::BinaryHeap;
 use std::env;
 use std::fs::File;
 use std::hash::{Hash, Hasher};
-use std::io::{stdin, stdout, BufRead, BufReader, BufWriter, Lines, Read, Write};
+use std::io::{stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
 use std::mem::replace;
 use std::ops::Range;
 use std::path::Path;
@@ -42,6 +43,7 @@ use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
 use uucore::fs::is_stdin_interactive; // for Iterator::dedup()
 use uucore::InvalidEncodingHandling;
+use version_cmp::compare_version;
 
 static NAME: &str = "sort";
 static ABOUT: &str = "Display sorted concatenation of all FILE(s).";
@@ -67,6 +69,9 @@ static OPT_DICTIONARY_ORDER: &str = "dictionary-order";
 static OPT_MERGE: &str = "merge";
 static OPT_CHECK: &str = "check";
 static OPT_CHECK_SILENT: &str = "check-silent";
+static CHECK_QUIET: &str = "quiet";
+static CHECK_SILENT: &str = "silent";
+static CHECK_DIAGNOSE_FIRST: &str = "diagnose-first";
 static OPT_DEBUG: &str = "debug";
 static OPT_IGNORE_CASE: &str = "ignore-case";
 static OPT_IGNORE_BLANKS: &str = "ignore-blanks";
@@ -78,6 +83,7 @@ static OPT_UNIQUE: &str = "unique";
 static OPT_KEY: &str = "key";
 static OPT_SEPARATOR: &str = "field-separator";
 static OPT_RANDOM: &str = "random-sort";
+static OPT_RANDOM_SOURCE: &str = "random-source";
 static OPT_ZERO_TERMINATED: &str = "zero-terminated";
 static OPT_PARALLEL: &str = "parallel";
 static OPT_FILES0_FROM: &str = "files0-from";
@@ -101,6 +107,7 @@ enum SortMode {
     GeneralNumeric,
     Month,
     Version,
+    Random,
     Default,
 }
 #[derive(Clone)]
@@ -118,8 +125,7 @@ struct GlobalSettings {
     unique: bool,
     check: bool,
     check_silent: bool,
-    random: bool,
-    salt: String,
+    salt: [u8; 16],
     selectors: Vec<FieldSelector>,
     separator: Option<char>,
     threads: String,
@@ -167,8 +173,7 @@ impl Default for GlobalSettings {
             unique: false,
             check: false,
             check_silent: false,
-            random: false,
-            salt: String::new(),
+            salt: [0; 16],
             selectors: vec![],
             separator: None,
             threads: String::new(),
@@ -186,7 +191,6 @@ struct KeySettings {
     ignore_case: bool,
     dictionary_order: bool,
     ignore_non_printing: bool,
-    random: bool,
     reverse: bool,
 }
 
@@ -197,7 +201,6 @@ impl From<&GlobalSettings> for KeySettings {
             ignore_blanks: settings.ignore_blanks,
             ignore_case: settings.ignore_case,
             ignore_non_printing: settings.ignore_non_printing,
-            random: settings.random,
             reverse: settings.reverse,
             dictionary_order: settings.dictionary_order,
         }
@@ -240,6 +243,7 @@ impl SelectionRange {
 enum NumCache {
     AsF64(GeneralF64ParseResult),
     WithInfo(NumInfo),
+    WithUnit(NumInfo, HumanUnit),
     None,
 }
 
@@ -256,6 +260,12 @@ impl NumCache {
             _ => unreachable!(),
         }
     }
+    fn as_num_info_with_unit(&self) -> (&NumInfo, HumanUnit) {
+        match self {
+            NumCache::WithUnit(n, unit) => (n, *unit),
+            _ => unreachable!(),
+        }
+    }
 }
 #[derive(Serialize, Deserialize, Clone)]
 struct Selection {
@@ -314,16 +324,21 @@ impl Line {
                 let num_cache = if selector.settings.mode == SortMode::Numeric
                     || selector.settings.mode == SortMode::HumanNumeric
                 {
+                    let str = range.get_str(&line);
                     let (info, num_range) = NumInfo::parse(
-                        range.get_str(&line),
+                        str,
                         NumInfoParseSettings {
-                            accept_si_units: selector.settings.mode == SortMode::HumanNumeric,
                             thousands_separator: Some(THOUSANDS_SEP),
                             decimal_pt: Some(DECIMAL_PT),
                         },
                     );
+                    let num_cache = if selector.settings.mode == SortMode::HumanNumeric {
+                        NumCache::WithUnit(info, HumanUnit::parse(&str[num_range.end..]).0)
+                    } else {
+                        NumCache::WithInfo(info)
+                    };
                     range.shorten(num_range);
-                    NumCache::WithInfo(info)
+                    num_cache
                 } else if selector.settings.mode == SortMode::GeneralNumeric {
                     let str = range.get_str(&line);
                     NumCache::AsF64(general_f64_parse(&str[get_leading_gen(str)]))
@@ -359,7 +374,6 @@ impl Line {
                     let (_, num_range) = NumInfo::parse(
                         &self.line[selection.clone()],
                         NumInfoParseSettings {
-                            accept_si_units: selector.settings.mode == SortMode::HumanNumeric,
                             thousands_separator: Some(THOUSANDS_SEP),
                             decimal_pt: Some(DECIMAL_PT),
                         },
@@ -370,12 +384,10 @@ impl Line {
                     selection.start += num_range.start;
                     selection.end = selection.start + num_range.len();
 
-                    // include a trailing si unit
-                    if selector.settings.mode == SortMode::HumanNumeric
-                        && self.line[selection.end..initial_selection.end]
-                            .starts_with(&['k', 'K', 'M', 'G', 'T', 'P', 'E', 'Z', 'Y'][..])
-                    {
-                        selection.end += 1;
+                    // include a trailing unit
+                    if selector.settings.mode == SortMode::HumanNumeric {
+                        selection.end +=
+                            HumanUnit::parse(&self.line[selection.end..initial_selection.end]).1;
                     }
 
                     // include leading zeroes, a leading minus or a leading decimal point
@@ -436,7 +448,7 @@ impl Line {
                 }
             }
         }
-        if !(settings.random
+        if !(settings.mode == SortMode::Random
             || settings.stable
             || settings.unique
             || !(settings.dictionary_order
@@ -568,7 +580,7 @@ impl KeyPosition {
                     'h' => settings.mode = SortMode::HumanNumeric,
                     'i' => settings.ignore_non_printing = true,
                     'n' => settings.mode = SortMode::Numeric,
-                    'R' => settings.random = true,
+                    'R' => settings.mode = SortMode::Random,
                     'r' => settings.reverse = true,
                     'V' => settings.mode = SortMode::Version,
                     c => {
@@ -592,7 +604,9 @@ impl KeyPosition {
                             | SortMode::GeneralNumeric
                             | SortMode::Month => SortMode::Default,
                             // Only SortMode::Default and SortMode::Version work with dictionary_order and ignore_non_printing
-                            m @ SortMode::Default | m @ SortMode::Version => m,
+                            m @ SortMode::Default
+                            | m @ SortMode::Version
+                            | m @ SortMode::Random => m,
                         }
                     }
                     _ => {}
@@ -725,17 +739,21 @@ impl FieldSelector {
 }
 
 struct MergeableFile<'a> {
-    lines: Lines<BufReader<Box<dyn Read>>>,
+    lines: Box<dyn Iterator<Item = String> + 'a>,
     current_line: Line,
+    file_index: usize,
     settings: &'a GlobalSettings,
 }
 
 // BinaryHeap depends on `Ord`. Note that we want to pop smallest items
 // from the heap first, and BinaryHeap.pop() returns the largest, so we
 // trick it into the right order by calling reverse() here.
+// Equal lines are taken from the file that was given first.
 impl<'a> Ord for MergeableFile<'a> {
     fn cmp(&self, other: &MergeableFile) -> Ordering {
-        compare_by(&self.current_line, &other.current_line, self.settings).reverse()
+        compare_by(&self.current_line, &other.current_line, self.settings)
+            .reverse()
+            .then_with(|| other.file_index.cmp(&self.file_index))
     }
 }
 
@@ -753,6 +771,7 @@ impl<'a> PartialEq for MergeableFile<'a> {
 
 impl<'a> Eq for MergeableFile<'a> {}
 
+/// Merges already sorted files, keeping only the current line of each file in memory.
 struct FileMerger<'a> {
     heap: BinaryHeap<MergeableFile<'a>>,
     settings: &'a GlobalSettings,
@@ -765,11 +784,20 @@ impl<'a> FileMerger<'a> {
             settings,
         }
     }
-    fn push_file(&mut self, mut lines: Lines<BufReader<Box<dyn Read>>>) {
-        if let Some(Ok(next_line)) = lines.next() {
+    /// Add a file, where `file_index` is its position on the command line.
+    fn push_file(&mut self, reader: BufReader<Box<dyn Read>>, file_index: usize) {
+        let mut lines: Box<dyn Iterator<Item = String>> = if self.settings.zero_terminated {
+            Box::new(reader.split(b'\0').flatten().map(|line| {
+                String::from_utf8(line).expect("Could not parse string from zero terminated input.")
+            }))
+        } else {
+            Box::new(reader.lines().take_while(Result::is_ok).flatten())
+        };
+        if let Some(next_line) = lines.next() {
             let mergeable_file = MergeableFile {
                 lines,
                 current_line: Line::new(next_line, &self.settings),
+                file_index,
                 settings: &self.settings,
             };
             self.heap.push(mergeable_file);
@@ -783,7 +811,7 @@ impl<'a> Iterator for FileMerger<'a> {
         match self.heap.pop() {
             Some(mut current) => {
                 match current.lines.next() {
-                    Some(Ok(next_line)) => {
+                    Some(next_line) => {
                         let ret = replace(
                             &mut current.current_line,
                             Line::new(next_line, &self.settings),
@@ -791,7 +819,7 @@ impl<'a> Iterator for FileMerger<'a> {
                         self.heap.push(current);
                         Some(ret)
                     }
-                    _ => {
+                    None => {
                         // Don't put it back in the heap (it's empty/erroring)
                         // but its first line is still valid.
                         Some(current.current_line)
@@ -873,6 +901,10 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(OPT_CHECK)
                 .short("c")
                 .long(OPT_CHECK)
+                .takes_value(true)
+                .require_equals(true)
+                .min_values(0)
+                .possible_values(&[CHECK_QUIET, CHECK_SILENT, CHECK_DIAGNOSE_FIRST])
                 .help("check for sorted input; do not sort"),
         )
         .arg(
@@ -912,7 +944,14 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(OPT_RANDOM)
                 .short("R")
                 .long(OPT_RANDOM)
-                .help("shuffle in random order"),
+                .help("shuffle in random order, keeping equal keys next to each other"),
+        )
+        .arg(
+            Arg::with_name(OPT_RANDOM_SOURCE)
+                .long(OPT_RANDOM_SOURCE)
+                .help("get random bytes from FILE, to make --random-sort reproducible")
+                .takes_value(true)
+                .value_name("FILE"),
         )
         .arg(
             Arg::with_name(OPT_REVERSE)
@@ -939,6 +978,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .help("sort by a key")
                 .long_help(LONG_HELP_KEYS)
                 .multiple(true)
+                .number_of_values(1)
                 .takes_value(true),
         )
         .arg(
@@ -1020,7 +1060,9 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             .unwrap_or_default()
     };
 
-    settings.mode = if matches.is_present(OPT_HUMAN_NUMERIC_SORT) {
+    settings.mode = if matches.is_present(OPT_RANDOM) {
+        SortMode::Random
+    } else if matches.is_present(OPT_HUMAN_NUMERIC_SORT) {
         SortMode::HumanNumeric
     } else if matches.is_present(OPT_MONTH_SORT) {
         SortMode::Month
@@ -1072,8 +1114,12 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     settings.merge = matches.is_present(OPT_MERGE);
 
     settings.check = matches.is_present(OPT_CHECK);
-    if matches.is_present(OPT_CHECK_SILENT) {
-        settings.check_silent = matches.is_present(OPT_CHECK_SILENT);
+    let check_mode = matches.value_of(OPT_CHECK);
+    if matches.is_present(OPT_CHECK_SILENT)
+        || check_mode == Some(CHECK_QUIET)
+        || check_mode == Some(CHECK_SILENT)
+    {
+        settings.check_silent = true;
         settings.check = true;
     };
 
@@ -1086,16 +1132,21 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     settings.stable = matches.is_present(OPT_STABLE);
     settings.unique = matches.is_present(OPT_UNIQUE);
 
-    if matches.is_present(OPT_RANDOM) {
-        settings.random = matches.is_present(OPT_RANDOM);
-        settings.salt = get_rand_string();
-    }
+    settings.salt = match matches.value_of(OPT_RANDOM_SOURCE) {
+        Some(path) => read_salt(path),
+        None => get_rand_salt(),
+    };
 
     if files.is_empty() {
         /* if no file, default to stdin */
         files.push("-".to_owned());
     } else if settings.check && files.len() != 1 {
-        crash!(1, "extra operand `{}' not allowed with -c", files[1])
+        show_usage_error!(
+            "extra operand '{}' not allowed with -{}",
+            files[1],
+            if settings.check_silent { 'C' } else { 'c' }
+        );
+        return 2;
     }
 
     if let Some(arg) = matches.args.get(OPT_SEPARATOR) {
@@ -1138,7 +1189,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         }
     }
 
-    if !settings.stable || !matches.is_present(OPT_KEY) {
+    if !(settings.stable || settings.unique) || !matches.is_present(OPT_KEY) {
         // add a default selector matching the whole line
         let key_settings = KeySettings::from(&settings);
         settings.selectors.push(FieldSelector {
@@ -1156,8 +1207,15 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
 }
 
 fn exec(files: Vec<String>, settings: GlobalSettings) -> i32 {
+    if settings.check {
+        return exec_check_file(&files[0], &settings);
+    }
+
+    if settings.merge {
+        return exec_merge(&files, &settings);
+    }
+
     let mut lines = Vec::new();
-    let mut file_merger = FileMerger::new(&settings);
 
     for path in &files {
         let (reader, _) = match open(path) {
@@ -1167,9 +1225,7 @@ fn exec(files: Vec<String>, settings: GlobalSettings) -> i32 {
 
         let buf_reader = BufReader::new(reader);
 
-        if settings.merge {
-            file_merger.push_file(buf_reader.lines());
-        } else if settings.zero_terminated {
+        if settings.zero_terminated {
             for line in buf_reader.split(b'\0').flatten() {
                 lines.push(Line::new(
                     std::str::from_utf8(&line)
@@ -1189,10 +1245,6 @@ fn exec(files: Vec<String>, settings: GlobalSettings) -> i32 {
         }
     }
 
-    if settings.check {
-        return exec_check_file(&lines, &settings);
-    }
-
     // Only use ext_sorter when we need to.
     // Probably faster that we don't create
     // an owned value each run
@@ -1202,16 +1254,7 @@ fn exec(files: Vec<String>, settings: GlobalSettings) -> i32 {
         sort_by(&mut lines, &settings);
     }
 
-    if settings.merge {
-        if settings.unique {
-            print_sorted(
-                file_merger.dedup_by(|a, b| compare_by(a, b, &settings) == Ordering::Equal),
-                &settings,
-            )
-        } else {
-            print_sorted(file_merger, &settings)
-        }
-    } else if settings.unique {
+    if settings.unique {
         print_sorted(
             lines
                 .into_iter()
@@ -1225,36 +1268,60 @@ fn exec(files: Vec<String>, settings: GlobalSettings) -> i32 {
     0
 }
 
-fn exec_check_file(unwrapped_lines: &[Line], settings: &GlobalSettings) -> i32 {
-    // errors yields the line before each disorder,
-    // plus the last line (quirk of .coalesce())
-    let mut errors =
-        unwrapped_lines
-            .iter()
-            .enumerate()
-            .coalesce(|(last_i, last_line), (i, line)| {
-                if compare_by(&last_line, &line, &settings) == Ordering::Greater {
-                    Err(((last_i, last_line), (i, line)))
-                } else {
-                    Ok((i, line))
-                }
-            });
-    if let Some((first_error_index, _line)) = errors.next() {
-        // Check for a second "error", as .coalesce() always returns the last
-        // line, no matter what our merging function does.
-        if let Some(_last_line_or_next_error) = errors.next() {
-            if !settings.check_silent {
-                println!("sort: disorder in line {}", first_error_index);
-            };
-            1
-        } else {
-            // first "error" was actually the last line.
-            0
+fn exec_merge(files: &[String], settings: &GlobalSettings) -> i32 {
+    let mut file_merger = FileMerger::new(settings);
+    for (file_index, path) in files.iter().enumerate() {
+        if let Some((reader, _)) = open(path) {
+            file_merger.push_file(BufReader::new(reader), file_index);
         }
+    }
+
+    if settings.unique {
+        print_sorted(
+            file_merger.dedup_by(|a, b| compare_by(a, b, settings) == Ordering::Equal),
+            settings,
+        )
     } else {
-        // unwrapped_lines was empty. Empty files are defined to be sorted.
-        0
+        print_sorted(file_merger, settings)
     }
+
+    0
+}
+
+/// Check that the file at `path` is sorted, without keeping more than two lines in memory.
+/// With `--unique`, equal lines are also out of order.
+fn exec_check_file(path: &str, settings: &GlobalSettings) -> i32 {
+    let reader = match open(path) {
+        Some((reader, _)) => BufReader::new(reader),
+        None => return 2,
+    };
+    let separator = if settings.zero_terminated {
+        b'\0'
+    } else {
+        b'\n'
+    };
+
+    let mut prev: Option<Line> = None;
+    for (index, line) in reader.split(separator).enumerate() {
+        let line = match line {
+            Ok(line) => Line::new(String::from_utf8_lossy(&line).into_owned(), settings),
+            Err(e) => {
+                show_error!("{0}: {1}", path, e.to_string());
+                return 2;
+            }
+        };
+        if let Some(prev) = &prev {
+            let ordering = compare_by(prev, &line, settings);
+            if ordering == Ordering::Greater || (settings.unique && ordering == Ordering::Equal) {
+                if !settings.check_silent {
+                    show_info!("{}:{}: disorder: {}", path, index + 1, line.line);
+                }
+                return 1;
+            }
+        }
+        prev = Some(line);
+    }
+    0
 }
 
 fn ext_sort_by(unsorted: Vec<Line>, settings: GlobalSettings) -> Vec<Line> {
@@ -1287,22 +1354,24 @@ fn compare_by(a: &Line, b: &Line, global_settings: &GlobalSettings) -> Ordering
         let b_str = b_selection.get_str(b);
         let settings = &selector.settings;
 
-        let cmp: Ordering = if settings.random {
-            random_shuffle(a_str, b_str, global_settings.salt.clone())
-        } else {
-            match settings.mode {
-                SortMode::Numeric | SortMode::HumanNumeric => numeric_str_cmp(
-                    (a_str, a_selection.num_cache.as_num_info()),
-                    (b_str, b_selection.num_cache.as_num_info()),
-                ),
-                SortMode::GeneralNumeric => general_numeric_compare(
-                    general_f64_parse(&a_str[get_leading_gen(a_str)]),
-                    general_f64_parse(&b_str[get_leading_gen(b_str)]),
-                ),
-                SortMode::Month => month_compare(a_str, b_str),
-                SortMode::Version => version_compare(a_str, b_str),
-                SortMode::Default => default_compare(a_str, b_str),
+        let cmp: Ordering = match settings.mode {
+            SortMode::Numeric => numeric_str_cmp(
+                (a_str, a_selection.num_cache.as_num_info()),
+                (b_str, b_selection.num_cache.as_num_info()),
+            ),
+            SortMode::HumanNumeric => {
+                let (a_info, a_unit) = a_selection.num_cache.as_num_info_with_unit();
+                let (b_info, b_unit) = b_selection.num_cache.as_num_info_with_unit();
+                human_numeric_str_cmp((a_str, a_info, a_unit), (b_str, b_info, b_unit))
             }
+            SortMode::GeneralNumeric => general_numeric_compare(
+                general_f64_parse(&a_str[get_leading_gen(a_str)]),
+                general_f64_parse(&b_str[get_leading_gen(b_str)]),
+            ),
+            SortMode::Month => month_compare(a_str, b_str),
+            SortMode::Version => compare_version(a_str, b_str),
+            SortMode::Random => random_shuffle(a_str, b_str, &global_settings.salt),
+            SortMode::Default => default_compare(a_str, b_str),
         };
         if cmp != Ordering::Equal {
             return if settings.reverse { cmp.reverse() } else { cmp };
@@ -1310,7 +1379,10 @@ fn compare_by(a: &Line, b: &Line, global_settings: &GlobalSettings) -> Ordering
     }
 
     // Call "last resort compare" if all selectors returned Equal
-    let cmp = if global_settings.random || global_settings.stable || global_settings.unique {
+    let cmp = if global_settings.mode == SortMode::Random
+        || global_settings.stable
+        || global_settings.unique
+    {
         Ordering::Equal
     } else {
         default_compare(&a.line, &b.line)
@@ -1406,12 +1478,17 @@ fn general_numeric_compare(a: GeneralF64ParseResult, b: GeneralF64ParseResult) -
     a.partial_cmp(&b).unwrap()
 }
 
-fn get_rand_string() -> String {
-    thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(16)
-        .map(char::from)
-        .collect::<String>()
+fn get_rand_salt() -> [u8; 16] {
+    thread_rng().gen()
+}
+
+/// Read the salt for --random-sort from the start of `path`.
+fn read_salt(path: &str) -> [u8; 16] {
+    let mut salt = [0; 16];
+    if let Err(e) = File::open(path).and_then(|mut file| file.read_exact(&mut salt)) {
+        crash!(1, "{}: {}", path, e);
+    }
+    salt
 }
 
 fn get_hash<T: Hash>(t: &T) -> u64 {
@@ -1420,14 +1497,10 @@ fn get_hash<T: Hash>(t: &T) -> u64 {
     s.finish()
 }
 
-fn random_shuffle(a: &str, b: &str, x: String) -> Ordering {
-    #![allow(clippy::comparison_chain)]
-    let salt_slice = x.as_str();
-
-    let da = get_hash(&[a, salt_slice].concat());
-    let db = get_hash(&[b, salt_slice].concat());
-
-    da.cmp(&db)
+/// Compare two keys by their hash with the given salt. Equal keys always compare equal,
+/// so lines with the same key end up next to each other after shuffling.
+fn random_shuffle(a: &str, b: &str, salt: &[u8; 16]) -> Ordering {
+    get_hash(&(salt, a)).cmp(&get_hash(&(salt, b)))
 }
 
 #[derive(Eq, Ord, PartialEq, PartialOrd, Clone, Copy)]
@@ -1491,31 +1564,6 @@ fn month_compare(a: &str, b: &str) -> Ordering {
     }
 }
 
-fn version_parse(a: &str) -> Version {
-    let result = Version::parse(a);
-
-    match result {
-        Ok(vers_a) => vers_a,
-        // Non-version lines parse to 0.0.0
-        Err(_e) => Version::parse("0.0.0").unwrap(),
-    }
-}
-
-fn version_compare(a: &str, b: &str) -> Ordering {
-    #![allow(clippy::comparison_chain)]
-    let ver_a = version_parse(a);
-    let ver_b = version_parse(b);
-
-    // Version::cmp is not implemented; implement comparison directly
-    if ver_a > ver_b {
-        Ordering::Greater
-    } else if ver_a < ver_b {
-        Ordering::Less
-    } else {
-        Ordering::Equal
-    }
-}
-
 fn remove_nondictionary_chars(s: &str) -> String {
     // According to GNU, dictionary chars are those of ASCII
     // and a blank is a space or a tab
@@ -1593,9 +1641,9 @@ mod tests {
     fn test_random_shuffle() {
         let a = "Ted";
         let b = "Ted";
-        let c = get_rand_string();
+        let c = get_rand_salt();
 
-        assert_eq!(Ordering::Equal, random_shuffle(a, b, c));
+        assert_eq!(Ordering::Equal, random_shuffle(a, b, &c));
     }
 
     #[test]
@@ -1614,20 +1662,20 @@ mod tests {
         assert_eq!(Ordering::Less, month_compare(a, b));
     }
     #[test]
-    fn test_version_compare() {
+    fn test_compare_version() {
         let a = "1.2.3-alpha2";
         let b = "1.4.0";
 
-        assert_eq!(Ordering::Less, version_compare(a, b));
+        assert_eq!(Ordering::Less, compare_version(a, b));
     }
 
     #[test]
     fn test_random_compare() {
         let a = "9";
         let b = "9";
-        let c = get_rand_string();
+        let c = get_rand_salt();
 
-        assert_eq!(Ordering::Equal, random_shuffle(a, b, c));
+        assert_eq!(Ordering::Equal, random_shuffle(a, b, &c));
     }
 
     #[test]
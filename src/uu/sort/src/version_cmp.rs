@@ -0,0 +1,206 @@
+//  * This file is part of the uutils coreutils package.
+//  *
+//  * For the full copyright and license information, please view the LICENSE
+//  * file that was distributed with this source code.
+
+// spell-checker:ignore (ToDO) filevercmp verrevcmp
+
+//! Comparison of strings containing version numbers, like GNU's `filevercmp`.
+//!
+//! Strings are split into alternating runs of non-digits and digits. Runs of digits are
+//! compared numerically (ignoring leading zeros); runs of non-digits are compared character
+//! by character, with letters sorting before other characters, and `~` before anything,
+//! even the end of the string (so that `1.0~rc1` sorts before `1.0`).
+//!
+//! As in GNU sort, file name suffixes (like `.tar.gz`) are only compared if the rest of the
+//! strings are equivalent, and `.`, `..` and other strings starting with `.` sort first.
+
+use std::cmp::Ordering;
+
+/// The sort order of a character in a run of non-digits; digits only appear here at the end
+/// of a run, and sort like the end of the string.
+fn order(c: Option<&u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => *c as i32,
+        Some(b'~') => -1,
+        Some(c) => *c as i32 + 256,
+    }
+}
+
+/// Compare two version strings, without any special treatment for file name suffixes.
+fn verrevcmp(a: &[u8], b: &[u8]) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    let is_non_digit = |s: &[u8]| s.first().map_or(false, |c| !c.is_ascii_digit());
+
+    while !a.is_empty() || !b.is_empty() {
+        while is_non_digit(a) || is_non_digit(b) {
+            let (a_order, b_order) = (order(a.first()), order(b.first()));
+            if a_order != b_order {
+                return a_order.cmp(&b_order);
+            }
+            // Both strings have the same non-digit character here.
+            a = &a[1..];
+            b = &b[1..];
+        }
+
+        while a.first() == Some(&b'0') {
+            a = &a[1..];
+        }
+        while b.first() == Some(&b'0') {
+            b = &b[1..];
+        }
+
+        // Without leading zeros, the longest run of digits is the largest number; if they have
+        //  the same length, the first differing digit decides.
+        let mut first_diff = Ordering::Equal;
+        while a.first().map_or(false, u8::is_ascii_digit)
+            && b.first().map_or(false, u8::is_ascii_digit)
+        {
+            if first_diff == Ordering::Equal {
+                first_diff = a[0].cmp(&b[0]);
+            }
+            a = &a[1..];
+            b = &b[1..];
+        }
+        if a.first().map_or(false, u8::is_ascii_digit) {
+            return Ordering::Greater;
+        }
+        if b.first().map_or(false, u8::is_ascii_digit) {
+            return Ordering::Less;
+        }
+        if first_diff != Ordering::Equal {
+            return first_diff;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// The length of `s` without its file name suffix, which is the longest sequence at the end of
+/// `s` matching `(\.[A-Za-z~][A-Za-z0-9~]*)*`.
+fn prefix_len(s: &[u8]) -> usize {
+    let is_suffix_char = |c: u8| c.is_ascii_alphanumeric() || c == b'~';
+    let mut prefix_len = 0;
+    let mut i = 0;
+    loop {
+        while i + 1 < s.len()
+            && s[i] == b'.'
+            && (s[i + 1].is_ascii_alphabetic() || s[i + 1] == b'~')
+        {
+            i += 2;
+            while i < s.len() && is_suffix_char(s[i]) {
+                i += 1;
+            }
+        }
+        if i == s.len() {
+            return prefix_len;
+        }
+        i += 1;
+        prefix_len = i;
+    }
+}
+
+/// Compare two strings as version numbers.
+pub fn compare_version(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+
+    // The empty string sorts first.
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Less,
+        (false, true) => return Ordering::Greater,
+        (false, false) => {}
+    }
+
+    // Then ".", "..", and other strings starting with "."
+    if a[0] == b'.' || b[0] == b'.' {
+        if a[0] != b'.' {
+            return Ordering::Greater;
+        } else if b[0] != b'.' {
+            return Ordering::Less;
+        }
+        for special in &[&b"."[..], &b".."[..]] {
+            match (a == *special, b == *special) {
+                (true, true) => return Ordering::Equal,
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                (false, false) => {}
+            }
+        }
+    }
+
+    // Only compare the suffixes if the rest of the strings are equivalent.
+    let (a_prefix, b_prefix) = (&a[..prefix_len(a)], &b[..prefix_len(b)]);
+    match verrevcmp(a_prefix, b_prefix) {
+        Ordering::Equal if a_prefix.len() != a.len() || b_prefix.len() != b.len() => {
+            verrevcmp(a, b)
+        }
+        ordering => ordering,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_sorted(versions: &[&str]) {
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(
+                    compare_version(a, b),
+                    i.cmp(&j),
+                    "comparing {:?} with {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn numeric_segments() {
+        assert_sorted(&["1.2.9", "1.2.10", "1.10", "2", "10"]);
+        assert_sorted(&["pkg-1.2.9", "pkg-1.2.10", "pkg-1.10.0"]);
+    }
+
+    #[test]
+    fn leading_zeros() {
+        assert_eq!(compare_version("01.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_version("1.00", "1.0"), Ordering::Equal);
+        assert_eq!(compare_version("1.001", "1.2"), Ordering::Less);
+    }
+
+    #[test]
+    fn segment_count() {
+        assert_sorted(&["1", "1.0", "1.0.1", "1.1"]);
+    }
+
+    #[test]
+    fn tilde() {
+        assert_sorted(&["~", "1.0~rc1", "1.0", "1.0a", "1.0+", "1.0.1"]);
+        assert_sorted(&["1.0~~", "1.0~", "1.0~a", "1.0"]);
+    }
+
+    #[test]
+    fn letters_before_other_characters() {
+        assert_sorted(&["1a", "1z", "1+", "1-", "1.", "a"]);
+    }
+
+    #[test]
+    fn dot_files_first() {
+        assert_sorted(&["", ".", "..", ".a", ".b1", "a"]);
+    }
+
+    #[test]
+    fn suffixes() {
+        assert_eq!(prefix_len(b"foo.tar.gz"), 3);
+        assert_eq!(prefix_len(b"foo-1.2.tar.gz"), 7);
+        assert_eq!(prefix_len(b"1.0a"), 4);
+        assert_eq!(prefix_len(b".bashrc"), 0);
+        assert_eq!(prefix_len(b"1.0.a"), 3);
+        assert_sorted(&["foo.txt", "foo-1.2.tar.gz", "foo-1.10.tar.gz"]);
+        assert_sorted(&["foo-1.2.tar", "foo-1.2.tar.gz"]);
+    }
+}
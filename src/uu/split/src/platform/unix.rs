@@ -1,6 +1,7 @@
 use std::env;
 use std::io::Write;
-use std::io::{BufWriter, Result};
+use std::io::{BufWriter, ErrorKind, Result};
+use std::os::unix::process::ExitStatusExt;
 use std::process::{Child, Command, Stdio};
 /// A writer that writes to a shell_process' stdin
 ///
@@ -9,15 +10,24 @@ use std::process::{Child, Command, Stdio};
 struct FilterWriter {
     /// Running shell process
     shell_process: Child,
+    /// The command and the name of the output file, for error messages
+    command: String,
+    filepath: String,
 }
 
 impl Write for FilterWriter {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        self.shell_process
+        let result = self
+            .shell_process
             .stdin
             .as_mut()
             .expect("failed to get shell stdin")
-            .write(buf)
+            .write(buf);
+        match result {
+            // like GNU split, ignore commands that don't read all of their input
+            Err(ref e) if e.kind() == ErrorKind::BrokenPipe => Ok(buf.len()),
+            result => result,
+        }
     }
     fn flush(&mut self) -> Result<()> {
         self.shell_process
@@ -28,35 +38,6 @@ impl Write for FilterWriter {
     }
 }
 
-/// Have an environment variable set at a value during this lifetime
-struct WithEnvVarSet {
-    /// Env var key
-    _previous_var_key: String,
-    /// Previous value set to this key
-    _previous_var_value: std::result::Result<String, env::VarError>,
-}
-impl WithEnvVarSet {
-    /// Save previous value assigned to key, set key=value
-    fn new(key: &str, value: &str) -> WithEnvVarSet {
-        let previous_env_value = env::var(key);
-        env::set_var(key, value);
-        WithEnvVarSet {
-            _previous_var_key: String::from(key),
-            _previous_var_value: previous_env_value,
-        }
-    }
-}
-
-impl Drop for WithEnvVarSet {
-    /// Restore previous value now that this is being dropped by context
-    fn drop(&mut self) {
-        if let Ok(ref prev_value) = self._previous_var_value {
-            env::set_var(&self._previous_var_key, &prev_value);
-        } else {
-            env::remove_var(&self._previous_var_key)
-        }
-    }
-}
 impl FilterWriter {
     /// Create a new filter running a command with $FILE pointing at the output name
     ///
@@ -65,38 +46,50 @@ impl FilterWriter {
     /// * `command` - The shell command to execute
     /// * `filepath` - Path of the output file (forwarded to command as $FILE)
     fn new(command: &str, filepath: &str) -> FilterWriter {
-        // set $FILE, save previous value (if there was one)
-        let _with_env_var_set = WithEnvVarSet::new("FILE", &filepath);
-
         let shell_process =
             Command::new(env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_owned()))
                 .arg("-c")
                 .arg(command)
+                .env("FILE", filepath)
                 .stdin(Stdio::piped())
                 .spawn()
                 .expect("Couldn't spawn filter command");
 
-        FilterWriter { shell_process }
+        FilterWriter {
+            shell_process,
+            command: command.to_owned(),
+            filepath: filepath.to_owned(),
+        }
     }
 }
 
 impl Drop for FilterWriter {
-    /// flush stdin, close it and wait on `shell_process` before dropping self
+    /// close stdin and wait on `shell_process` before dropping self
     fn drop(&mut self) {
-        {
-            // close stdin by dropping it
-            let _stdin = self.shell_process.stdin.as_mut();
-        }
+        // close stdin by dropping it, so that the command gets to the end of its input
+        drop(self.shell_process.stdin.take());
         let exit_status = self
             .shell_process
             .wait()
             .expect("Couldn't wait for child process");
         if let Some(return_code) = exit_status.code() {
             if return_code != 0 {
-                crash!(1, "Shell process returned {}", return_code);
+                crash!(
+                    return_code,
+                    "with FILE={}, exit {} from command: {}",
+                    self.filepath,
+                    return_code,
+                    self.command
+                );
             }
-        } else {
-            crash!(1, "Shell process terminated by signal")
+        } else if let Some(signal) = exit_status.signal() {
+            crash!(
+                128 + signal,
+                "with FILE={}, signal {} from command: {}",
+                self.filepath,
+                signal,
+                self.command
+            );
         }
     }
 }
@@ -16,7 +16,7 @@ use clap::{App, Arg};
 use std::char;
 use std::env;
 use std::fs::File;
-use std::io::{stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{self, stdin, stdout, BufRead, BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
 static NAME: &str = "split";
@@ -25,6 +25,7 @@ static VERSION: &str = env!("CARGO_PKG_VERSION");
 static OPT_BYTES: &str = "bytes";
 static OPT_LINE_BYTES: &str = "line-bytes";
 static OPT_LINES: &str = "lines";
+static OPT_NUMBER: &str = "number";
 static OPT_ADDITIONAL_SUFFIX: &str = "additional-suffix";
 static OPT_FILTER: &str = "filter";
 static OPT_NUMERIC_SUFFIXES: &str = "numeric-suffixes";
@@ -85,6 +86,14 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .default_value("1000")
                 .help("write to shell COMMAND file name is $FILE (Currently not implemented for Windows)"),
         )
+        .arg(
+            Arg::with_name(OPT_NUMBER)
+                .short("n")
+                .long(OPT_NUMBER)
+                .takes_value(true)
+                .value_name("CHUNKS")
+                .help("generate CHUNKS output files; CHUNKS is N (by bytes), l/N (without splitting lines) or r/N (round robin distribution of the lines)"),
+        )
         // rest of the arguments
         .arg(
             Arg::with_name(OPT_ADDITIONAL_SUFFIX)
@@ -138,6 +147,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         prefix: "".to_owned(),
         numeric_suffix: false,
         suffix_length: 0,
+        explicit_suffix_length: false,
         additional_suffix: "".to_owned(),
         input: "".to_owned(),
         filter: None,
@@ -152,6 +162,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .parse()
         .unwrap_or_else(|_| panic!("Invalid number for {}", OPT_SUFFIX_LENGTH));
 
+    settings.explicit_suffix_length = matches.occurrences_of(OPT_SUFFIX_LENGTH) > 0;
     settings.numeric_suffix = matches.occurrences_of(OPT_NUMERIC_SUFFIXES) > 0;
     settings.additional_suffix = matches.value_of(OPT_ADDITIONAL_SUFFIX).unwrap().to_owned();
 
@@ -159,16 +170,15 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     // check that the user is not specifying more than one strategy
     // note: right now, this exact behaviour cannot be handled by ArgGroup since ArgGroup
     // considers a default value Arg as "defined"
-    let explicit_strategies =
-        vec![OPT_LINE_BYTES, OPT_LINES, OPT_BYTES]
-            .into_iter()
-            .fold(0, |count, strat| {
-                if matches.occurrences_of(strat) > 0 {
-                    count + 1
-                } else {
-                    count
-                }
-            });
+    let explicit_strategies = vec![OPT_LINE_BYTES, OPT_LINES, OPT_BYTES, OPT_NUMBER]
+        .into_iter()
+        .fold(0, |count, strat| {
+            if matches.occurrences_of(strat) > 0 {
+                count + 1
+            } else {
+                count
+            }
+        });
     if explicit_strategies > 1 {
         crash!(1, "cannot split in more than one way");
     }
@@ -177,7 +187,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     settings.strategy = String::from(OPT_LINES);
     settings.strategy_param = matches.value_of(OPT_LINES).unwrap().to_owned();
     // take any (other) defined strategy
-    for strat in vec![OPT_LINE_BYTES, OPT_BYTES].into_iter() {
+    for strat in vec![OPT_LINE_BYTES, OPT_BYTES, OPT_NUMBER].into_iter() {
         if matches.occurrences_of(strat) > 0 {
             settings.strategy = String::from(strat);
             settings.strategy_param = matches.value_of(strat).unwrap().to_owned();
@@ -204,6 +214,8 @@ struct Settings {
     prefix: String,
     numeric_suffix: bool,
     suffix_length: usize,
+    /// Whether `suffix_length` was given, rather than widened as needed with `--number`
+    explicit_suffix_length: bool,
     additional_suffix: String,
     input: String,
     /// When supplied, a shell command to output to instead of xaa, xab …
@@ -259,36 +271,55 @@ struct ByteSplitter {
     require_whole_line: bool,
 }
 
+/// Parse a size like `100`, `10K` (1024 bytes), `10KB` (1000 bytes) or `10KiB`, with the
+/// suffixes of GNU split: `b` (512 bytes), then K, M, G, T, P and E, in any of the three forms,
+/// and a lowercase `k` or `m` for K and M.
+fn parse_size(size: &str) -> Option<usize> {
+    let digits_end = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (number, suffix) = size.split_at(digits_end);
+    let number: u64 = number.parse().ok()?;
+
+    let multiplier: u64 = if suffix.is_empty() {
+        1
+    } else if suffix == "b" {
+        512
+    } else {
+        let mut chars = suffix.chars();
+        let power = match chars.next()? {
+            'K' | 'k' => 1,
+            'M' | 'm' => 2,
+            'G' => 3,
+            'T' => 4,
+            'P' => 5,
+            'E' => 6,
+            _ => return None,
+        };
+        let base: u64 = match chars.as_str() {
+            "" | "iB" => 1024,
+            "B" => 1000,
+            _ => return None,
+        };
+        base.pow(power)
+    };
+
+    let size = number.checked_mul(multiplier)?;
+    if size == 0 || size > usize::max_value() as u64 {
+        return None;
+    }
+    Some(size as usize)
+}
+
 impl ByteSplitter {
     fn new(settings: &Settings) -> ByteSplitter {
-        let mut strategy_param: Vec<char> = settings.strategy_param.chars().collect();
-        let suffix = strategy_param.pop().unwrap();
-        let multiplier = match suffix {
-            '0'..='9' => 1usize,
-            'b' => 512usize,
-            'k' => 1024usize,
-            'm' => 1024usize * 1024usize,
-            _ => crash!(1, "invalid number of bytes"),
-        };
-        let n = if suffix.is_alphabetic() {
-            match strategy_param
-                .iter()
-                .cloned()
-                .collect::<String>()
-                .parse::<usize>()
-            {
-                Ok(a) => a,
-                Err(e) => crash!(1, "invalid number of bytes: {}", e),
-            }
-        } else {
-            match settings.strategy_param.parse::<usize>() {
-                Ok(a) => a,
-                Err(e) => crash!(1, "invalid number of bytes: {}", e),
-            }
+        let n = match parse_size(&settings.strategy_param) {
+            Some(n) => n,
+            None => crash!(1, "invalid number of bytes: '{}'", settings.strategy_param),
         };
         ByteSplitter {
-            saved_bytes_to_write: n * multiplier,
-            bytes_to_write: n * multiplier,
+            saved_bytes_to_write: n,
+            bytes_to_write: n,
             break_on_line_end: settings.strategy == "b",
             require_whole_line: false,
         }
@@ -349,8 +380,144 @@ fn num_prefix(i: usize, width: usize) -> String {
     c
 }
 
-fn split(settings: &Settings) -> i32 {
-    let mut reader = BufReader::new(if settings.input == "-" {
+/// How `--number` divides the input into chunks
+#[derive(Clone, Copy, PartialEq)]
+enum ChunkMode {
+    Bytes,
+    Lines,
+    RoundRobin,
+}
+
+fn parse_chunks(param: &str) -> (ChunkMode, usize) {
+    let parts: Vec<&str> = param.split('/').collect();
+    let (mode, number) = match parts.as_slice() {
+        [number] => (ChunkMode::Bytes, number),
+        ["l", number] => (ChunkMode::Lines, number),
+        ["r", number] => (ChunkMode::RoundRobin, number),
+        _ => crash!(1, "invalid number of chunks: '{}'", param),
+    };
+    match number.parse::<usize>() {
+        Ok(n) if n > 0 => (mode, n),
+        _ => crash!(1, "invalid number of chunks: '{}'", number),
+    }
+}
+
+fn output_filename(settings: &Settings, suffix_length: usize, fileno: usize) -> String {
+    let mut filename = settings.prefix.clone();
+    filename.push_str(
+        if settings.numeric_suffix {
+            num_prefix(fileno, suffix_length)
+        } else {
+            str_prefix(fileno, suffix_length)
+        }
+        .as_ref(),
+    );
+    filename.push_str(settings.additional_suffix.as_ref());
+    filename
+}
+
+/// The length of the suffixes needed to name `n` files.
+fn suffix_length_for(settings: &Settings, n: usize) -> usize {
+    let radix = if settings.numeric_suffix { 10 } else { 26 };
+    let mut length = 1;
+    let mut count = radix;
+    while count < n {
+        length += 1;
+        count = count.saturating_mul(radix);
+    }
+    length
+}
+
+/// Split the input into the given number of chunks, which are all created, even if some of
+/// them are empty.
+fn split_into_chunks(settings: &Settings) -> i32 {
+    let (mode, n) = parse_chunks(&settings.strategy_param);
+
+    let needed_suffix_length = suffix_length_for(settings, n);
+    let suffix_length = if settings.suffix_length >= needed_suffix_length {
+        settings.suffix_length
+    } else if settings.explicit_suffix_length {
+        crash!(
+            1,
+            "the suffix length needs to be at least {}",
+            needed_suffix_length
+        )
+    } else {
+        needed_suffix_length
+    };
+
+    let mut input = open_input(settings);
+    // The chunk boundaries depend on the size of the whole input, which is only read in
+    // advance when it isn't a regular file
+    let (input_size, buffered) = match std::fs::metadata(&settings.input) {
+        Ok(metadata) if settings.input != "-" && metadata.is_file() => (metadata.len(), None),
+        _ if mode == ChunkMode::RoundRobin => (0, None),
+        _ => {
+            let mut data = Vec::new();
+            crash_if_err!(1, input.read_to_end(&mut data));
+            (data.len() as u64, Some(data))
+        }
+    };
+    let mut buffered_input = buffered.as_deref().unwrap_or_default();
+    let reader: &mut dyn BufRead = if buffered.is_some() {
+        &mut buffered_input
+    } else {
+        &mut input
+    };
+
+    let mut writers: Vec<_> = (0..n)
+        .map(|fileno| {
+            let filename = output_filename(settings, suffix_length, fileno);
+            if settings.verbose {
+                println!("creating file '{}'", filename);
+            }
+            platform::instantiate_current_writer(&settings.filter, filename.as_str())
+        })
+        .collect();
+
+    let chunk_size = std::cmp::max(input_size / n as u64, 1);
+    match mode {
+        ChunkMode::RoundRobin => {
+            let mut line = Vec::new();
+            let mut lineno = 0;
+            while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+                crash_if_err!(1, writers[lineno % n].write_all(&line));
+                line.clear();
+                lineno += 1;
+            }
+        }
+        ChunkMode::Bytes => {
+            for (k, writer) in writers.iter_mut().enumerate() {
+                let start = std::cmp::min(k as u64 * chunk_size, input_size);
+                let end = if k == n - 1 {
+                    input_size
+                } else {
+                    std::cmp::min(start + chunk_size, input_size)
+                };
+                crash_if_err!(1, io::copy(&mut reader.take(end - start), writer));
+            }
+        }
+        ChunkMode::Lines => {
+            // Each line goes to the chunk its first byte is in
+            let mut line = Vec::new();
+            let mut start = 0;
+            while reader.read_until(b'\n', &mut line).unwrap_or(0) > 0 {
+                let k = std::cmp::min(start / chunk_size, n as u64 - 1) as usize;
+                crash_if_err!(1, writers[k].write_all(&line));
+                start += line.len() as u64;
+                line.clear();
+            }
+        }
+    }
+
+    for writer in writers.iter_mut() {
+        crash_if_err!(1, writer.flush());
+    }
+    0
+}
+
+fn open_input(settings: &Settings) -> BufReader<Box<dyn Read>> {
+    BufReader::new(if settings.input == "-" {
         Box::new(stdin()) as Box<dyn Read>
     } else {
         let r = match File::open(Path::new(&settings.input)) {
@@ -362,7 +529,15 @@ fn split(settings: &Settings) -> i32 {
             ),
         };
         Box::new(r) as Box<dyn Read>
-    });
+    })
+}
+
+fn split(settings: &Settings) -> i32 {
+    if settings.strategy == OPT_NUMBER {
+        return split_into_chunks(settings);
+    }
+
+    let mut reader = open_input(settings);
 
     let mut splitter: Box<dyn Splitter> = match settings.strategy.as_str() {
         s if s == OPT_LINES => Box::new(LineSplitter::new(settings)),
@@ -385,16 +560,7 @@ fn split(settings: &Settings) -> i32 {
             }
         }
         if control.request_new_file {
-            let mut filename = settings.prefix.clone();
-            filename.push_str(
-                if settings.numeric_suffix {
-                    num_prefix(fileno, settings.suffix_length)
-                } else {
-                    str_prefix(fileno, settings.suffix_length)
-                }
-                .as_ref(),
-            );
-            filename.push_str(settings.additional_suffix.as_ref());
+            let filename = output_filename(settings, settings.suffix_length, fileno);
 
             crash_if_err!(1, writer.flush());
             fileno += 1;
@@ -16,6 +16,7 @@ pub use uucore::libc::{
     S_IFSOCK, S_IRGRP, S_IROTH, S_IRUSR, S_ISGID, S_ISUID, S_ISVTX, S_IWGRP, S_IWOTH, S_IWUSR,
     S_IXGRP, S_IXOTH, S_IXUSR,
 };
+use uucore::quoting_style::{escape_name, QuotingStyle};
 
 pub trait BirthTime {
     fn pretty_birth(&self) -> String;
@@ -81,6 +82,37 @@ pub fn pretty_filetype<'a>(mode: mode_t, size: u64) -> &'a str {
     }
 }
 
+/// The major number of a device, in the encoding of the platform
+pub fn major(dev: u64) -> u64 {
+    if cfg!(target_os = "linux") {
+        ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff)
+    } else {
+        dev >> 8
+    }
+}
+
+/// The minor number of a device, in the encoding of the platform
+pub fn minor(dev: u64) -> u64 {
+    if cfg!(target_os = "linux") {
+        (dev & 0xff) | ((dev >> 12) & !0xff)
+    } else {
+        dev & 0xff
+    }
+}
+
+/// Quote a file name for `%N`, like GNU's default `shell-escape-always`
+/// quoting style.
+pub fn pretty_quote(name: &str) -> String {
+    escape_name(
+        name,
+        &QuotingStyle::Shell {
+            escape: true,
+            always_quote: true,
+            show_control: false,
+        },
+    )
+}
+
 pub fn pretty_access(mode: mode_t) -> String {
     let mut result = String::with_capacity(10);
     result.push(match mode & S_IFMT {
@@ -193,6 +225,12 @@ pub trait FsMeta {
 }
 
 impl FsMeta for Sstatfs {
+    // the block counts are in fragments on Linux
+    #[cfg(target_os = "linux")]
+    fn blksize(&self) -> i64 {
+        self.f_frsize as i64
+    }
+    #[cfg(not(target_os = "linux"))]
     fn blksize(&self) -> i64 {
         self.f_bsize as i64
     }
@@ -223,7 +261,7 @@ impl FsMeta for Sstatfs {
 
     #[cfg(target_os = "linux")]
     fn iosize(&self) -> u64 {
-        self.f_frsize as u64
+        self.f_bsize as u64
     }
     #[cfg(any(target_vendor = "apple", target_os = "freebsd"))]
     fn iosize(&self) -> u64 {
@@ -441,6 +441,7 @@ impl Stater {
                             'f' => tokens.push(Token::Char('\x0C')),
                             'n' => tokens.push(Token::Char('\n')),
                             'r' => tokens.push(Token::Char('\r')),
+                            't' => tokens.push(Token::Char('\t')),
                             'v' => tokens.push(Token::Char('\x0B')),
                             c => {
                                 show_warning!("unrecognized escape '\\{}'", c);
@@ -658,12 +659,12 @@ impl Stater {
                                                 }
                                             };
                                             arg = format!(
-                                                "`{}' -> `{}'",
-                                                file,
-                                                dst.to_string_lossy()
+                                                "{} -> {}",
+                                                pretty_quote(file),
+                                                pretty_quote(&dst.to_string_lossy())
                                             );
                                         } else {
-                                            arg = format!("`{}'", file);
+                                            arg = pretty_quote(file);
                                         }
                                         otype = OutputType::Str;
                                     }
@@ -680,13 +681,13 @@ impl Stater {
                                     // major device type in hex, for character/block device special
                                     // files
                                     't' => {
-                                        arg = format!("{:x}", meta.rdev() >> 8);
+                                        arg = format!("{:x}", major(meta.rdev()));
                                         otype = OutputType::UnsignedHex;
                                     }
                                     // minor device type in hex, for character/block device special
                                     // files
                                     'T' => {
-                                        arg = format!("{:x}", meta.rdev() & 0xff);
+                                        arg = format!("{:x}", minor(meta.rdev()));
                                         otype = OutputType::UnsignedHex;
                                     }
                                     // user ID of owner
@@ -731,7 +732,7 @@ impl Stater {
                                     // time of last data modification, seconds since Epoch
                                     'Y' => {
                                         arg = format!("{}", meta.mtime());
-                                        otype = OutputType::Str;
+                                        otype = OutputType::Integer;
                                     }
                                     // time of last status change, human-readable
                                     'z' => {
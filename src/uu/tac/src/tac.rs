@@ -11,7 +11,9 @@
 extern crate uucore;
 
 use clap::{App, Arg};
-use std::io::{stdin, stdout, BufReader, Read, Stdout, Write};
+use memchr::memrchr;
+use regex::bytes::Regex;
+use std::io::{stdin, stdout, BufReader, BufWriter, Read, StdoutLock, Write};
 use std::{fs::File, path::Path};
 use uucore::InvalidEncodingHandling;
 
@@ -48,7 +50,10 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(options::REGEX)
                 .short("r")
                 .long(options::REGEX)
-                .help("interpret the sequence as a regular expression (NOT IMPLEMENTED)")
+                .help(
+                    "interpret the sequence as a regular expression, in Rust's syntax rather than \
+                     GNU's: '(...)' groups, '|' alternates and '\\n' matches a newline",
+                )
                 .takes_value(false),
         )
         .arg(
@@ -82,11 +87,22 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     tac(files, before, regex, &separator[..])
 }
 
-fn tac(filenames: Vec<String>, before: bool, _: bool, separator: &str) -> i32 {
+fn tac(filenames: Vec<String>, before: bool, regex: bool, separator: &str) -> i32 {
     let mut exit_code = 0;
-    let mut out = stdout();
-    let sbytes = separator.as_bytes();
-    let slen = sbytes.len();
+    let out = stdout();
+    let mut out = BufWriter::new(out.lock());
+    let patterns = if regex {
+        // the anchored one checks the separators found by the other one from each position
+        match (
+            Regex::new(separator),
+            Regex::new(&format!("^(?:{})", separator)),
+        ) {
+            (Ok(pattern), Ok(anchored)) => Some((pattern, anchored)),
+            (Err(e), _) | (_, Err(e)) => crash!(1, "invalid regular expression: {}", e),
+        }
+    } else {
+        None
+    };
 
     for filename in &filenames {
         let mut file = BufReader::new(if filename == "-" {
@@ -122,56 +138,70 @@ fn tac(filenames: Vec<String>, before: bool, _: bool, separator: &str) -> i32 {
             continue;
         };
 
-        // find offsets in string of all separators
-        let mut offsets = Vec::new();
-        let mut i = 0;
-        loop {
-            if i + slen > data.len() {
-                break;
-            }
+        let separators = match patterns {
+            Some((ref pattern, ref anchored)) => find_regex_separators(&data, pattern, anchored),
+            None => find_literal_separators(&data, separator.as_bytes()),
+        };
 
-            if &data[i..i + slen] == sbytes {
-                offsets.push(i);
-                i += slen;
-            } else {
-                i += 1;
-            }
+        // each record ends at the end of a separator, or starts at its start with --before
+        let mut end = data.len();
+        for &(sep_start, sep_end) in &separators {
+            let record_start = if before { sep_start } else { sep_end };
+            write_record(&mut out, &data[record_start..end]);
+            end = record_start;
         }
+        write_record(&mut out, &data[..end]);
+    }
+    out.flush()
+        .unwrap_or_else(|e| crash!(1, "failed to write to stdout: {}", e));
 
-        // if there isn't a separator at the end of the file, fake it
-        if offsets.is_empty() || *offsets.last().unwrap() < data.len() - slen {
-            offsets.push(data.len());
-        }
+    exit_code
+}
 
-        let mut prev = *offsets.last().unwrap();
-        let mut start = true;
-        for off in offsets.iter().rev().skip(1) {
-            // correctly handle case of no final separator in file
-            if start && prev == data.len() {
-                show_line(&mut out, &[], &data[*off + slen..prev], before);
-                start = false;
-            } else {
-                show_line(&mut out, sbytes, &data[*off + slen..prev], before);
-            }
-            prev = *off;
+/// The start and end of the occurrences of `separator` in `data`, from the last one to the
+/// first. Like in GNU tac, they're searched for backwards from the end of `data`.
+fn find_literal_separators(data: &[u8], separator: &[u8]) -> Vec<(usize, usize)> {
+    let mut separators = Vec::new();
+    // the end of the record we're looking for a separator in
+    let mut end = data.len();
+    let mut candidates = data;
+    while let Some(start) = memrchr(separator[0], candidates) {
+        if data[start..end].starts_with(separator) {
+            separators.push((start, start + separator.len()));
+            end = start;
         }
-        show_line(&mut out, sbytes, &data[0..prev], before);
+        candidates = &data[..start];
     }
-
-    exit_code
+    separators
 }
 
-fn show_line(out: &mut Stdout, sep: &[u8], dat: &[u8], before: bool) {
-    if before {
-        out.write_all(sep)
-            .unwrap_or_else(|e| crash!(1, "failed to write to stdout: {}", e));
+/// The start and end of the matches of `pattern` in `data`, from the last one to the first.
+/// Like in GNU tac, they're searched for backwards from the end of `data`, so that a separator
+/// is the match of `anchored` at the rightmost position of the rest of the record. Only the
+/// positions inside the matches of a single forward pass of `pattern` can start one.
+fn find_regex_separators(data: &[u8], pattern: &Regex, anchored: &Regex) -> Vec<(usize, usize)> {
+    let matches: Vec<_> = pattern
+        .find_iter(data)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    let mut separators = Vec::new();
+    // the end of the record we're looking for a separator in
+    let mut end = data.len();
+    for &(match_start, match_end) in matches.iter().rev() {
+        for start in (match_start..match_end.max(match_start + 1)).rev() {
+            if start >= end {
+                continue;
+            }
+            if let Some(m) = anchored.find(&data[start..end]) {
+                separators.push((start, start + m.end()));
+                end = start;
+            }
+        }
     }
+    separators
+}
 
-    out.write_all(dat)
+fn write_record(out: &mut BufWriter<StdoutLock>, record: &[u8]) {
+    out.write_all(record)
         .unwrap_or_else(|e| crash!(1, "failed to write to stdout: {}", e));
-
-    if !before {
-        out.write_all(sep)
-            .unwrap_or_else(|e| crash!(1, "failed to write to stdout: {}", e));
-    }
 }
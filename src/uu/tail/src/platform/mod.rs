@@ -8,13 +8,13 @@
  */
 
 #[cfg(unix)]
-pub use self::unix::{supports_pid_checks, Pid, ProcessChecker};
+pub use self::unix::{is_same_file, supports_pid_checks, Pid, ProcessChecker};
 
 #[cfg(windows)]
-pub use self::windows::{supports_pid_checks, Pid, ProcessChecker};
+pub use self::windows::{is_same_file, supports_pid_checks, Pid, ProcessChecker};
 
 #[cfg(target_os = "redox")]
-pub use self::redox::{supports_pid_checks, Pid, ProcessChecker};
+pub use self::redox::{is_same_file, supports_pid_checks, Pid, ProcessChecker};
 
 #[cfg(unix)]
 mod unix;
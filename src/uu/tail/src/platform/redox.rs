@@ -1,6 +1,8 @@
 // spell-checker:ignore (ToDO) ENOSYS EPERM
 
 use self::syscall::{Error, ENOSYS, EPERM};
+use std::fs::File;
+use std::os::unix::fs::MetadataExt;
 
 pub type Pid = usize;
 
@@ -23,3 +25,11 @@ impl ProcessChecker {
 pub fn supports_pid_checks(pid: self::Pid) -> bool {
     true
 }
+
+/// Whether both are the same file, i.e. have the same device and inode.
+pub fn is_same_file(a: &File, b: &File) -> bool {
+    match (a.metadata(), b.metadata()) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
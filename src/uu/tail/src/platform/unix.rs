@@ -9,7 +9,9 @@
 
 // spell-checker:ignore (ToDO) errno EPERM ENOSYS
 
+use std::fs::File;
 use std::io::Error;
+use std::os::unix::fs::MetadataExt;
 
 pub type Pid = libc::pid_t;
 
@@ -36,6 +38,14 @@ pub fn supports_pid_checks(pid: self::Pid) -> bool {
     unsafe { !(libc::kill(pid, 0) != 0 && get_errno() == libc::ENOSYS) }
 }
 
+/// Whether both are the same file, i.e. have the same device and inode.
+pub fn is_same_file(a: &File, b: &File) -> bool {
+    match (a.metadata(), b.metadata()) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
 #[inline]
 fn get_errno() -> i32 {
     Error::last_os_error().raw_os_error().unwrap()
@@ -9,7 +9,12 @@
 
 extern crate winapi;
 
+use std::fs::File;
+use std::mem;
+use std::os::windows::io::AsRawHandle;
+
 use self::winapi::shared::minwindef::DWORD;
+use self::winapi::um::fileapi::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION};
 use self::winapi::um::handleapi::CloseHandle;
 use self::winapi::um::processthreadsapi::OpenProcess;
 use self::winapi::um::synchapi::WaitForSingleObject;
@@ -57,3 +62,29 @@ impl Drop for ProcessChecker {
 pub fn supports_pid_checks(_pid: self::Pid) -> bool {
     true
 }
+
+/// Whether both are the same file, i.e. have the same volume serial number and file index.
+/// Their creation time would not do, since file system tunneling keeps it for a file which is
+/// recreated under the same name.
+pub fn is_same_file(a: &File, b: &File) -> bool {
+    match (file_id(a), file_id(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The volume serial number and file index of `file`
+fn file_id(file: &File) -> Option<(DWORD, DWORD, DWORD)> {
+    unsafe {
+        let mut info: BY_HANDLE_FILE_INFORMATION = mem::zeroed();
+        if GetFileInformationByHandle(file.as_raw_handle() as HANDLE, &mut info) == 0 {
+            None
+        } else {
+            Some((
+                info.dwVolumeSerialNumber,
+                info.nFileIndexHigh,
+                info.nFileIndexLow,
+            ))
+        }
+    }
+}
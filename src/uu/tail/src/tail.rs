@@ -22,7 +22,7 @@ use std::collections::VecDeque;
 use std::error::Error;
 use std::fmt;
 use std::fs::File;
-use std::io::{stdin, stdout, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::io::{stdin, stdout, BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 use std::thread::sleep;
 use std::time::Duration;
@@ -35,14 +35,22 @@ pub mod options {
     }
     pub static BYTES: &str = "bytes";
     pub static FOLLOW: &str = "follow";
+    pub static FOLLOW_RETRY: &str = "F";
     pub static LINES: &str = "lines";
     pub static PID: &str = "pid";
+    pub static RETRY: &str = "retry";
     pub static SLEEP_INT: &str = "sleep-interval";
     pub static ZERO_TERM: &str = "zero-terminated";
 }
 
 static ARG_FILES: &str = "files";
 
+#[derive(PartialEq)]
+enum FollowMode {
+    Descriptor,
+    Name,
+}
+
 enum FilterMode {
     Bytes(u64),
     Lines(u64, u8), // (number of lines, delimiter)
@@ -50,9 +58,10 @@ enum FilterMode {
 
 struct Settings {
     mode: FilterMode,
-    sleep_msec: u32,
+    sleep_interval: Duration,
     beginning: bool,
-    follow: bool,
+    follow: Option<FollowMode>,
+    retry: bool,
     pid: platform::Pid,
 }
 
@@ -60,9 +69,10 @@ impl Default for Settings {
     fn default() -> Settings {
         Settings {
             mode: FilterMode::Lines(10, b'\n'),
-            sleep_msec: 1000,
+            sleep_interval: Duration::from_secs(1),
             beginning: false,
-            follow: false,
+            follow: None,
+            retry: false,
             pid: 0,
         }
     }
@@ -87,7 +97,18 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(options::FOLLOW)
                 .short("f")
                 .long(options::FOLLOW)
-                .help("Print the file as it grows"),
+                .takes_value(true)
+                .require_equals(true)
+                .min_values(0)
+                .possible_values(&["descriptor", "name"])
+                .help(
+                    "Print the file as it grows, following its descriptor unless 'name' is given",
+                ),
+        )
+        .arg(
+            Arg::with_name(options::FOLLOW_RETRY)
+                .short(options::FOLLOW_RETRY)
+                .help("Same as --follow=name --retry"),
         )
         .arg(
             Arg::with_name(options::LINES)
@@ -103,6 +124,11 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .takes_value(true)
                 .help("with -f, terminate after process ID, PID dies"),
         )
+        .arg(
+            Arg::with_name(options::RETRY)
+                .long(options::RETRY)
+                .help("Keep trying to open a file if it is inaccessible"),
+        )
         .arg(
             Arg::with_name(options::verbosity::QUIET)
                 .short("q")
@@ -119,7 +145,8 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .short("s")
                 .takes_value(true)
                 .long(options::SLEEP_INT)
-                .help("Number or seconds to sleep between polling the file when running with -f"),
+                .value_name("N")
+                .help("Number of seconds to sleep between polling the file when running with -f"),
         )
         .arg(
             Arg::with_name(options::verbosity::VERBOSE)
@@ -142,12 +169,25 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
 
     let matches = app.get_matches_from(args);
 
-    settings.follow = matches.is_present(options::FOLLOW);
-    if settings.follow {
-        if let Some(n) = matches.value_of(options::SLEEP_INT) {
-            let parsed: Option<u32> = n.parse().ok();
-            if let Some(m) = parsed {
-                settings.sleep_msec = m * 1000
+    settings.follow = if matches.is_present(options::FOLLOW_RETRY)
+        || matches.value_of(options::FOLLOW) == Some("name")
+    {
+        Some(FollowMode::Name)
+    } else if matches.is_present(options::FOLLOW) {
+        Some(FollowMode::Descriptor)
+    } else {
+        None
+    };
+    settings.retry =
+        matches.is_present(options::RETRY) || matches.is_present(options::FOLLOW_RETRY);
+    if let Some(n) = matches.value_of(options::SLEEP_INT) {
+        match n.parse::<f64>() {
+            Ok(secs) if secs >= 0.0 && secs < std::u32::MAX as f64 => {
+                settings.sleep_interval = Duration::from_secs_f64(secs)
+            }
+            _ => {
+                show_error!("invalid number of seconds: '{}'", n);
+                return 1;
             }
         }
     }
@@ -156,7 +196,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         if let Ok(pid) = pid_str.parse() {
             settings.pid = pid;
             if pid != 0 {
-                if !settings.follow {
+                if settings.follow.is_none() {
                     show_warning!("PID ignored; --pid=PID is useful only when following");
                 }
 
@@ -216,13 +256,14 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .map(|v| v.map(ToString::to_string).collect())
         .unwrap_or_default();
 
+    let mut has_error = false;
     if files.is_empty() {
         let mut buffer = BufReader::new(stdin());
         unbounded_tail(&mut buffer, &settings);
     } else {
         let multiple = files.len() > 1;
         let mut first_header = true;
-        let mut readers = Vec::new();
+        let mut followed = Vec::new();
 
         for filename in &files {
             if (multiple || verbose) && !quiet {
@@ -237,28 +278,46 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             if path.is_dir() {
                 continue;
             }
-            let mut file = File::open(&path).unwrap();
-            if is_seekable(&mut file) {
-                bounded_tail(&file, &settings);
-                if settings.follow {
-                    let reader = BufReader::new(file);
-                    readers.push(reader);
+            let mut file = match File::open(path) {
+                Ok(file) => file,
+                Err(err) => {
+                    show_error!("cannot open '{}' for reading: {}", filename, err);
+                    has_error = true;
+                    if settings.follow == Some(FollowMode::Name) && settings.retry {
+                        followed.push(FollowedFile {
+                            name: filename.clone(),
+                            reader: None,
+                        });
+                    }
+                    continue;
                 }
+            };
+            let reader = if is_seekable(&mut file) {
+                bounded_tail(&file, &settings);
+                BufReader::new(file)
             } else {
                 let mut reader = BufReader::new(file);
                 unbounded_tail(&mut reader, &settings);
-                if settings.follow {
-                    readers.push(reader);
-                }
+                reader
+            };
+            if settings.follow.is_some() {
+                followed.push(FollowedFile {
+                    name: filename.clone(),
+                    reader: Some(reader),
+                });
             }
         }
 
-        if settings.follow {
-            follow(&mut readers[..], &files[..], &settings);
+        if settings.follow.is_some() && !followed.is_empty() {
+            follow(&mut followed[..], &settings);
         }
     }
 
-    0
+    if has_error {
+        1
+    } else {
+        0
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -357,36 +416,59 @@ pub fn parse_size(mut size_slice: &str) -> Result<u64, ParseSizeErr> {
 /// block read at a time.
 const BLOCK_SIZE: u64 = 1 << 16;
 
-fn follow<T: Read>(readers: &mut [BufReader<T>], filenames: &[String], settings: &Settings) {
-    assert!(settings.follow);
-    let mut last = readers.len() - 1;
+/// A file followed with `--follow`. Its reader is `None` when it is followed by name and
+/// is currently inaccessible.
+struct FollowedFile {
+    name: String,
+    reader: Option<BufReader<File>>,
+}
+
+fn follow(files: &mut [FollowedFile], settings: &Settings) {
+    assert!(settings.follow.is_some());
+    let mut last = files.len() - 1;
     let mut read_some = false;
     let mut process = platform::ProcessChecker::new(settings.pid);
+    let stdout = stdout();
 
     loop {
-        sleep(Duration::new(0, settings.sleep_msec * 1000));
+        sleep(settings.sleep_interval);
 
         let pid_is_dead = !read_some && settings.pid != 0 && process.is_dead();
         read_some = false;
 
-        for (i, reader) in readers.iter_mut().enumerate() {
+        let mut stdout = stdout.lock();
+        for (i, file) in files.iter_mut().enumerate() {
+            if settings.follow == Some(FollowMode::Name) {
+                reopen_if_replaced(file, settings.retry);
+            }
+            let reader = match file.reader.as_mut() {
+                Some(reader) => reader,
+                None => continue,
+            };
+
+            if was_truncated(reader) {
+                show_info!("{}: file truncated", file.name);
+                reader.seek(SeekFrom::Start(0)).unwrap();
+            }
+
             // Print all new content since the last pass
             loop {
-                let mut datum = String::new();
-                match reader.read_line(&mut datum) {
+                let mut datum = Vec::new();
+                match reader.read_until(b'\n', &mut datum) {
                     Ok(0) => break,
                     Ok(_) => {
                         read_some = true;
                         if i != last {
-                            println!("\n==> {} <==", filenames[i]);
+                            writeln!(stdout, "\n==> {} <==", file.name).unwrap();
                             last = i;
                         }
-                        print!("{}", datum);
+                        stdout.write_all(&datum).unwrap();
                     }
                     Err(err) => panic!("{}", err),
                 }
             }
         }
+        stdout.flush().unwrap();
 
         if pid_is_dead {
             break;
@@ -394,6 +476,50 @@ fn follow<T: Read>(readers: &mut [BufReader<T>], filenames: &[String], settings:
     }
 }
 
+/// When following by name, switch to the file now found at the path of `file`, if it is not
+/// the one being read. Files which become inaccessible are only looked for again with `retry`.
+fn reopen_if_replaced(file: &mut FollowedFile, retry: bool) {
+    if file.reader.is_none() && !retry {
+        return;
+    }
+    if let Err(err) = std::fs::metadata(&file.name) {
+        if file.reader.take().is_some() {
+            if err.kind() == ErrorKind::NotFound {
+                show_info!(
+                    "'{}' has become inaccessible: No such file or directory",
+                    file.name
+                );
+            } else {
+                show_info!("'{}' has become inaccessible: {}", file.name, err);
+            }
+        }
+        return;
+    }
+    let new_file = match File::open(&file.name) {
+        Ok(new_file) => new_file,
+        Err(_) => return,
+    };
+    if let Some(reader) = &file.reader {
+        if platform::is_same_file(reader.get_ref(), &new_file) {
+            return;
+        }
+        show_info!("'{}' has been replaced;  following new file", file.name);
+    } else {
+        show_info!("'{}' has appeared;  following new file", file.name);
+    }
+    file.reader = Some(BufReader::new(new_file));
+}
+
+/// Whether the regular file read by `reader` became shorter than what was already read from it.
+fn was_truncated(reader: &mut BufReader<File>) -> bool {
+    match reader.get_ref().metadata() {
+        Ok(metadata) if metadata.is_file() => reader
+            .seek(SeekFrom::Current(0))
+            .map_or(false, |pos| metadata.len() < pos),
+        _ => false,
+    }
+}
+
 /// Iterate over bytes in the file, in reverse, until `should_stop` returns
 /// true. The `file` is left seek'd to the position just after the byte that
 /// `should_stop` returned true for.
@@ -16,7 +16,7 @@ use clap::{App, AppSettings, Arg};
 use std::io::ErrorKind;
 use std::process::{Command, Stdio};
 use std::time::Duration;
-use uucore::process::ChildExt;
+use uucore::process::{ChildExt, ExitStatus};
 use uucore::signals::signal_by_name_or_value;
 use uucore::InvalidEncodingHandling;
 
@@ -38,8 +38,8 @@ pub mod options {
 
     // Positional args.
     pub static DURATION: &str = "duration";
+    // the command and its arguments, which may start with a hyphen
     pub static COMMAND: &str = "command";
-    pub static ARGS: &str = "args";
 }
 
 struct Config {
@@ -56,35 +56,29 @@ struct Config {
 impl Config {
     fn from(options: clap::ArgMatches) -> Config {
         let signal = match options.value_of(options::SIGNAL) {
-            Some(signal_) => {
-                let signal_result = signal_by_name_or_value(&signal_);
-                match signal_result {
-                    None => {
-                        unreachable!("invalid signal '{}'", signal_);
-                    }
-                    Some(signal_value) => signal_value,
+            Some(signal_) => match signal_by_name_or_value(&signal_.to_uppercase()) {
+                None => {
+                    show_usage_error!("'{}': invalid signal", signal_);
+                    exit!(ERR_EXIT_STATUS);
                 }
-            }
+                Some(signal_value) => signal_value,
+            },
             _ => uucore::signals::signal_by_name_or_value("TERM").unwrap(),
         };
 
         let kill_after: Duration = match options.value_of(options::KILL_AFTER) {
-            Some(time) => uucore::parse_time::from_str(&time).unwrap(),
+            Some(time) => parse_duration(time),
             None => Duration::new(0, 0),
         };
 
-        let duration: Duration =
-            uucore::parse_time::from_str(options.value_of(options::DURATION).unwrap()).unwrap();
+        let duration: Duration = parse_duration(options.value_of(options::DURATION).unwrap());
 
         let preserve_status: bool = options.is_present(options::PRESERVE_STATUS);
         let foreground = options.is_present(options::FOREGROUND);
 
-        let command: String = options.value_of(options::COMMAND).unwrap().to_string();
-
-        let command_args: Vec<String> = match options.values_of(options::ARGS) {
-            Some(values) => values.map(|x| x.to_owned()).collect(),
-            None => vec![],
-        };
+        let mut command_line = options.values_of(options::COMMAND).unwrap();
+        let command: String = command_line.next().unwrap().to_string();
+        let command_args: Vec<String> = command_line.map(|x| x.to_owned()).collect();
 
         Config {
             foreground,
@@ -98,6 +92,13 @@ impl Config {
     }
 }
 
+fn parse_duration(time: &str) -> Duration {
+    match uucore::parse_time::from_str(time) {
+        Ok(duration) => duration,
+        Err(e) => crash!(ERR_EXIT_STATUS, "{}", e),
+    }
+}
+
 pub fn uumain(args: impl uucore::Args) -> i32 {
     let args = args
         .collect_str(InvalidEncodingHandling::ConvertLossy)
@@ -117,6 +118,8 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .arg(
             Arg::with_name(options::KILL_AFTER)
                 .short("k")
+                .long(options::KILL_AFTER)
+                .help("also send a KILL signal if COMMAND is still running this long after the initial signal was sent")
                 .takes_value(true))
         .arg(
             Arg::with_name(options::PRESERVE_STATUS)
@@ -139,9 +142,8 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             Arg::with_name(options::COMMAND)
                 .index(2)
                 .required(true)
-        )
-        .arg(
-            Arg::with_name(options::ARGS).multiple(true)
+                .multiple(true)
+                .allow_hyphen_values(true)
         )
         .setting(AppSettings::TrailingVarArg);
 
@@ -159,6 +161,14 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
     )
 }
 
+/// The exit code of a shell for a command that finished with `status`, which is 128 plus the
+/// signal for a command killed by one.
+fn shell_exit_code(status: ExitStatus) -> i32 {
+    status
+        .code()
+        .unwrap_or_else(|| 128 + status.signal().unwrap())
+}
+
 /// TODO: Improve exit codes, and make them consistent with the GNU Coreutil
 /// exit codes.
 
@@ -194,22 +204,30 @@ fn timeout(
         }
     };
     match process.wait_or_timeout(duration) {
-        Ok(Some(status)) => status.code().unwrap_or_else(|| status.signal().unwrap()),
+        Ok(Some(status)) => shell_exit_code(status),
         Ok(None) => {
             return_if_err!(ERR_EXIT_STATUS, process.send_signal(signal));
-            match process.wait_or_timeout(kill_after) {
-                Ok(Some(status)) => {
+            let status = if kill_after == Duration::new(0, 0) {
+                // Without --kill-after, the command may run as long as it likes after the signal
+                Some(ExitStatus::from_std_status(return_if_err!(
+                    ERR_EXIT_STATUS,
+                    process.wait()
+                )))
+            } else {
+                return_if_err!(ERR_EXIT_STATUS, process.wait_or_timeout(kill_after))
+            };
+            match status {
+                Some(status) => {
                     if preserve_status {
-                        status.code().unwrap_or_else(|| status.signal().unwrap())
+                        shell_exit_code(status)
+                    } else if signal == uucore::signals::signal_by_name_or_value("KILL").unwrap() {
+                        // like GNU, which is killed with the command
+                        137
                     } else {
                         124
                     }
                 }
-                Ok(None) => {
-                    if kill_after == Duration::new(0, 0) {
-                        // XXX: this may not be right
-                        return 124;
-                    }
+                None => {
                     return_if_err!(
                         ERR_EXIT_STATUS,
                         process
@@ -218,7 +236,6 @@ fn timeout(
                     return_if_err!(ERR_EXIT_STATUS, process.wait());
                     137
                 }
-                Err(_) => 124,
             }
         }
         Err(_) => {
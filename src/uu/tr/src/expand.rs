@@ -11,7 +11,6 @@
 
 use std::char::from_u32;
 use std::cmp::min;
-use std::iter::Peekable;
 use std::ops::RangeInclusive;
 
 /// Parse a backslash escape sequence to the corresponding character. Assumes
@@ -57,10 +56,46 @@ fn parse_sequence(s: &str) -> (char, usize) {
     }
 }
 
+/// The characters of a POSIX character class, like `upper` for `[:upper:]`, in
+/// ascending order.
+fn class_chars(name: &str) -> Option<Vec<char>> {
+    let is_member: fn(&u8) -> bool = match name {
+        "alnum" => u8::is_ascii_alphanumeric,
+        "alpha" => u8::is_ascii_alphabetic,
+        "blank" => |c| *c == b' ' || *c == b'\t',
+        "cntrl" => u8::is_ascii_control,
+        "digit" => u8::is_ascii_digit,
+        "graph" => u8::is_ascii_graphic,
+        "lower" => u8::is_ascii_lowercase,
+        "print" => |c| c.is_ascii_graphic() || *c == b' ',
+        "punct" => u8::is_ascii_punctuation,
+        "space" => |c| (b'\t'..=b'\r').contains(c) || *c == b' ',
+        "upper" => u8::is_ascii_uppercase,
+        "xdigit" => u8::is_ascii_hexdigit,
+        _ => return None,
+    };
+    Some((0..128u8).filter(is_member).map(char::from).collect())
+}
+
+#[derive(Clone)]
 struct Unescape<'a> {
     string: &'a str,
 }
 
+impl<'a> Unescape<'a> {
+    /// If the string continues with a character class like `[:upper:]`, consume it and
+    /// return its characters.
+    fn take_class(&mut self) -> Option<Vec<char>> {
+        if !self.string.starts_with("[:") {
+            return None;
+        }
+        let end = self.string[2..].find(":]")? + 2;
+        let chars = class_chars(&self.string[2..end])?;
+        self.string = &self.string[end + 2..];
+        Some(chars)
+    }
+}
+
 impl<'a> Iterator for Unescape<'a> {
     type Item = char;
 
@@ -95,7 +130,8 @@ impl<'a> Iterator for Unescape<'a> {
 
 pub struct ExpandSet<'a> {
     range: RangeInclusive<u32>,
-    unesc: Peekable<Unescape<'a>>,
+    class: std::vec::IntoIter<char>,
+    unesc: Unescape<'a>,
 }
 
 impl<'a> Iterator for ExpandSet<'a> {
@@ -116,13 +152,22 @@ impl<'a> Iterator for ExpandSet<'a> {
             }
         }
 
-        if let Some(first) = self.unesc.next() {
-            // peek ahead
-            if self.unesc.peek() == Some(&'-') && self.unesc.size_hint().0 > 1 {
-                self.unesc.next(); // this is the '-'
-                let last = self.unesc.next().unwrap(); // this is the end of the range
+        // then the rest of a character class
+        if let Some(c) = self.class.next() {
+            return Some(c);
+        }
+        if let Some(class) = self.unesc.take_class() {
+            self.class = class.into_iter();
+            return self.class.next();
+        }
 
-                {
+        if let Some(first) = self.unesc.next() {
+            // look ahead for a range, which needs an unescaped '-' followed by its end
+            let mut ahead = self.unesc.clone();
+            if ahead.string.starts_with('-') {
+                ahead.next();
+                if let Some(last) = ahead.next() {
+                    self.unesc = ahead;
                     self.range = first as u32 + 1..=last as u32;
                 }
             }
@@ -138,8 +183,9 @@ impl<'a> ExpandSet<'a> {
     #[inline]
     pub fn new(s: &'a str) -> ExpandSet<'a> {
         ExpandSet {
-            range: 0..=0,
-            unesc: Unescape { string: s }.peekable(),
+            range: RangeInclusive::new(1, 0),
+            class: Vec::new().into_iter(),
+            unesc: Unescape { string: s },
         }
     }
 }
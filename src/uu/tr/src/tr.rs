@@ -125,13 +125,28 @@ impl SymbolTranslator for DeleteAndSqueezeOperation {
 
 struct TranslateOperation {
     translate_map: FnvHashMap<usize, char>,
+    complement: Option<(BitSet, char)>,
 }
 
 impl TranslateOperation {
-    fn new(set1: ExpandSet, set2: &mut ExpandSet, truncate: bool) -> TranslateOperation {
+    fn new(
+        set1: ExpandSet,
+        set2: &mut ExpandSet,
+        truncate: bool,
+        complement: bool,
+    ) -> TranslateOperation {
         let mut map = FnvHashMap::default();
         let mut s2_prev = '_';
-        for i in set1 {
+        let mut set1_chars: Vec<char> = set1.collect();
+        let set1_bset: BitSet = set1_chars.iter().map(|c| *c as usize).collect();
+        if complement {
+            // The complement of SET1 is all the bytes that are not in it, in ascending order
+            set1_chars = (0..=255u8)
+                .filter(|c| !set1_bset.contains(*c as usize))
+                .map(char::from)
+                .collect();
+        }
+        for i in set1_chars {
             let s2_next = set2.next();
 
             if s2_next.is_none() && truncate {
@@ -141,13 +156,63 @@ impl TranslateOperation {
                 map.insert(i as usize, s2_prev);
             }
         }
-        TranslateOperation { translate_map: map }
+        TranslateOperation {
+            translate_map: map,
+            // Characters beyond the byte range that are not in SET1 go to the last of SET2
+            complement: if complement && !truncate {
+                Some((set1_bset, s2_prev))
+            } else {
+                None
+            },
+        }
     }
 }
 
 impl SymbolTranslator for TranslateOperation {
     fn translate(&self, c: char, _prev_c: char) -> Option<char> {
-        Some(*self.translate_map.get(&(c as usize)).unwrap_or(&c))
+        if let Some(t) = self.translate_map.get(&(c as usize)) {
+            return Some(*t);
+        }
+        match self.complement {
+            Some((ref set1, last)) if !set1.contains(c as usize) => Some(last),
+            _ => Some(c),
+        }
+    }
+}
+
+struct TranslateAndSqueezeOperation {
+    translate: TranslateOperation,
+    squeeze_set: BitSet,
+}
+
+impl TranslateAndSqueezeOperation {
+    fn new(
+        set1: ExpandSet,
+        set2: &str,
+        truncate: bool,
+        complement: bool,
+    ) -> TranslateAndSqueezeOperation {
+        TranslateAndSqueezeOperation {
+            translate: TranslateOperation::new(
+                set1,
+                &mut ExpandSet::new(set2),
+                truncate,
+                complement,
+            ),
+            squeeze_set: ExpandSet::new(set2).map(|c| c as usize).collect(),
+        }
+    }
+}
+
+impl SymbolTranslator for TranslateAndSqueezeOperation {
+    fn translate(&self, c: char, prev_c: char) -> Option<char> {
+        // Runs are squeezed after translation, so `prev_c` is the last translated character.
+        let c = self.translate.translate(c, prev_c)?;
+        if prev_c == c && self.squeeze_set.contains(c as usize) {
+            None
+        } else {
+            Some(c)
+        }
     }
 }
 
@@ -158,9 +223,10 @@ fn translate_input<T: SymbolTranslator>(
 ) {
     let mut buf = String::with_capacity(BUFFER_LEN + 4);
     let mut output_buf = String::with_capacity(BUFFER_LEN + 4);
+    // Squeezed runs may span lines, e.g. with `tr -s '\n'`.
+    let mut prev_c = 0 as char;
 
     while let Ok(length) = input.read_line(&mut buf) {
-        let mut prev_c = 0 as char;
         if length == 0 {
             break;
         }
@@ -168,8 +234,8 @@ fn translate_input<T: SymbolTranslator>(
             // isolation to make borrow checker happy
             let filtered = buf.chars().filter_map(|c| {
                 let res = translator.translate(c, prev_c);
-                if res.is_some() {
-                    prev_c = c;
+                if let Some(out_c) = res {
+                    prev_c = out_c;
                 }
                 res
             });
@@ -256,11 +322,6 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         return 1;
     }
 
-    if complement_flag && !delete_flag && !squeeze_flag {
-        show_error!("-c is only supported with -d or -s");
-        return 1;
-    }
-
     let stdin = stdin();
     let mut locked_stdin = stdin.lock();
     let stdout = stdout();
@@ -277,12 +338,20 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             let op = DeleteOperation::new(set1, complement_flag);
             translate_input(&mut locked_stdin, &mut buffered_stdout, op);
         }
+    } else if squeeze_flag && sets.len() > 1 {
+        let op = TranslateAndSqueezeOperation::new(
+            set1,
+            sets[1].as_ref(),
+            truncate_flag,
+            complement_flag,
+        );
+        translate_input(&mut locked_stdin, &mut buffered_stdout, op);
     } else if squeeze_flag {
         let op = SqueezeOperation::new(set1, complement_flag);
         translate_input(&mut locked_stdin, &mut buffered_stdout, op);
     } else {
         let mut set2 = ExpandSet::new(sets[1].as_ref());
-        let op = TranslateOperation::new(set1, &mut set2, truncate_flag);
+        let op = TranslateOperation::new(set1, &mut set2, truncate_flag, complement_flag);
         translate_input(&mut locked_stdin, &mut buffered_stdout, op)
     }
 
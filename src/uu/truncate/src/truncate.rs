@@ -11,7 +11,8 @@
 extern crate uucore;
 
 use clap::{App, Arg};
-use std::fs::{metadata, File, OpenOptions};
+use std::fs::{metadata, OpenOptions};
+use std::io::ErrorKind;
 use std::path::Path;
 
 #[derive(Eq, PartialEq)]
@@ -98,6 +99,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
             .long("size")
             .help("set or adjust the size of each file according to SIZE, which is in bytes unless --io-blocks is specified")
             .value_name("SIZE")
+            .allow_hyphen_values(true)
         )
         .arg(Arg::with_name(ARG_FILES).multiple(true).takes_value(true).min_values(1))
         .get_matches_from(args);
@@ -136,23 +138,18 @@ fn truncate(
         Some(size_string) => parse_size(&size_string),
         None => (0, TruncateMode::Reference),
     };
+    if modsize == 0 && (mode == TruncateMode::RoundDown || mode == TruncateMode::RoundUp) {
+        crash!(1, "division by zero");
+    }
 
     let refsize = match reference {
         Some(ref rfilename) => {
-            match mode {
-                // Only Some modes work with a reference
-                TruncateMode::Reference => (), //No --size was given
-                TruncateMode::Extend => (),
-                TruncateMode::Reduce => (),
-                _ => crash!(1, "you must specify a relative ‘--size’ with ‘--reference’"),
-            };
-            let _ = match File::open(Path::new(rfilename)) {
-                Ok(m) => m,
-                Err(f) => crash!(1, "{}", f.to_string()),
-            };
+            if mode == TruncateMode::Absolute {
+                crash!(1, "you must specify a relative ‘--size’ with ‘--reference’");
+            }
             match metadata(rfilename) {
                 Ok(meta) => meta.len(),
-                Err(f) => crash!(1, "{}", f.to_string()),
+                Err(f) => crash!(1, "cannot stat ‘{}’: {}", rfilename, f.to_string()),
             }
         }
         None => 0,
@@ -179,8 +176,11 @@ fn truncate(
                 let tsize: u64 = match mode {
                     TruncateMode::Absolute => modsize,
                     TruncateMode::Reference => fsize,
-                    TruncateMode::Extend => fsize + modsize,
-                    TruncateMode::Reduce => fsize - modsize,
+                    TruncateMode::Extend => match fsize.checked_add(modsize) {
+                        Some(tsize) => tsize,
+                        None => crash!(1, "overflow extending size of file ‘{}’", filename),
+                    },
+                    TruncateMode::Reduce => fsize.saturating_sub(modsize),
                     TruncateMode::AtMost => {
                         if fsize > modsize {
                             modsize
@@ -196,13 +196,21 @@ fn truncate(
                         }
                     }
                     TruncateMode::RoundDown => fsize - fsize % modsize,
-                    TruncateMode::RoundUp => fsize + fsize % modsize,
+                    TruncateMode::RoundUp => match fsize % modsize {
+                        0 => fsize,
+                        rem => match fsize.checked_add(modsize - rem) {
+                            Some(tsize) => tsize,
+                            None => crash!(1, "overflow rounding up size of file ‘{}’", filename),
+                        },
+                    },
                 };
                 match file.set_len(tsize) {
                     Ok(_) => {}
                     Err(f) => crash!(1, "{}", f.to_string()),
                 };
             }
+            // -c: files that don't exist are left alone
+            Err(ref f) if no_create && f.kind() == ErrorKind::NotFound => {}
             Err(f) => crash!(1, "{}", f.to_string()),
         }
     }
@@ -210,62 +218,50 @@ fn truncate(
 
 fn parse_size(size: &str) -> (u64, TruncateMode) {
     let clean_size = size.replace(" ", "");
-    let mode = match clean_size.chars().next().unwrap() {
-        '+' => TruncateMode::Extend,
-        '-' => TruncateMode::Reduce,
-        '<' => TruncateMode::AtMost,
-        '>' => TruncateMode::AtLeast,
-        '/' => TruncateMode::RoundDown,
-        '*' => TruncateMode::RoundUp,
+    let mode = match clean_size.chars().next() {
+        Some('+') => TruncateMode::Extend,
+        Some('-') => TruncateMode::Reduce,
+        Some('<') => TruncateMode::AtMost,
+        Some('>') => TruncateMode::AtLeast,
+        Some('/') => TruncateMode::RoundDown,
+        Some('%') => TruncateMode::RoundUp,
         _ => TruncateMode::Absolute, /* assume that the size is just a number */
     };
-    let bytes = {
-        let mut slice = if mode == TruncateMode::Absolute {
-            &clean_size
-        } else {
-            &clean_size[1..]
-        };
-        if slice.chars().last().unwrap().is_alphabetic() {
-            slice = &slice[..slice.len() - 1];
-            if !slice.is_empty() && slice.chars().last().unwrap().is_alphabetic() {
-                slice = &slice[..slice.len() - 1];
-            }
-        }
-        slice
-    }
-    .to_owned();
-    let mut number: u64 = match bytes.parse() {
-        Ok(num) => num,
-        Err(e) => crash!(1, "'{}' is not a valid number: {}", size, e),
+    let number = if mode == TruncateMode::Absolute {
+        &clean_size[..]
+    } else {
+        &clean_size[1..]
+    };
+    let digits_end = number
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(number.len());
+    let (digits, suffix) = number.split_at(digits_end);
+
+    // K, M, G, ... are powers of 1024, or of 1000 when followed by B
+    let mut suffix_chars = suffix.chars();
+    let exponent = match suffix_chars.next() {
+        None => Some(0),
+        Some(c) => "KMGTPEZY"
+            .find(c.to_ascii_uppercase())
+            .map(|i| i as u32 + 1),
+    };
+    let base: Option<u64> = match suffix_chars.as_str() {
+        "" | "iB" => Some(1024),
+        "B" => Some(1000),
+        _ => None,
+    };
+    let number = match (digits.parse::<u64>(), exponent, base) {
+        (Ok(n), Some(exponent), Some(base)) => base
+            .checked_pow(exponent)
+            .and_then(|multiplier| n.checked_mul(multiplier)),
+        _ => crash!(1, "Invalid number: ‘{}’", size),
     };
-    if clean_size.chars().last().unwrap().is_alphabetic() {
-        number *= match clean_size.chars().last().unwrap().to_ascii_uppercase() {
-            'B' => match clean_size
-                .chars()
-                .nth(clean_size.len() - 2)
-                .unwrap()
-                .to_ascii_uppercase()
-            {
-                'K' => 1000u64,
-                'M' => 1000u64.pow(2),
-                'G' => 1000u64.pow(3),
-                'T' => 1000u64.pow(4),
-                'P' => 1000u64.pow(5),
-                'E' => 1000u64.pow(6),
-                'Z' => 1000u64.pow(7),
-                'Y' => 1000u64.pow(8),
-                letter => crash!(1, "'{}B' is not a valid suffix.", letter),
-            },
-            'K' => 1024u64,
-            'M' => 1024u64.pow(2),
-            'G' => 1024u64.pow(3),
-            'T' => 1024u64.pow(4),
-            'P' => 1024u64.pow(5),
-            'E' => 1024u64.pow(6),
-            'Z' => 1024u64.pow(7),
-            'Y' => 1024u64.pow(8),
-            letter => crash!(1, "'{}' is not a valid suffix.", letter),
-        };
+    match number {
+        Some(number) => (number, mode),
+        None => crash!(
+            1,
+            "Invalid number: ‘{}’: Value too large for defined data type",
+            size
+        ),
     }
-    (number, mode)
 }
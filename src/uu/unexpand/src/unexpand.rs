@@ -76,8 +76,8 @@ impl Options {
             && !matches.is_present(options::FIRST_ONLY);
         let uflag = !matches.is_present(options::NO_UTF8);
 
-        let files = match matches.value_of(options::FILE) {
-            Some(v) => vec![v.to_string()],
+        let files = match matches.values_of(options::FILE) {
+            Some(v) => v.map(|v| v.to_string()).collect(),
             None => vec!["-".to_owned()],
         };
 
@@ -271,6 +271,7 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                     options::REPEATED,
                     options::ALL_REPEATED,
                     options::UNIQUE,
+                    options::COUNT,
                 ]),
         )
         .arg(
@@ -347,6 +348,11 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         }
     };
 
+    if matches.is_present(options::COUNT) && matches.is_present(options::ALL_REPEATED) {
+        show_usage_error!("printing all duplicated lines and repeat counts is meaningless");
+        return 1;
+    }
+
     let uniq = Uniq {
         repeats_only: matches.is_present(options::REPEATED)
             || matches.is_present(options::ALL_REPEATED),
@@ -15,6 +15,7 @@ use count_bytes::count_bytes_fast;
 
 use clap::{App, Arg, ArgMatches};
 use thiserror::Error;
+use unicode_width::UnicodeWidthChar;
 
 use std::cmp::max;
 use std::fs::File;
@@ -160,6 +161,7 @@ static VERSION: &str = env!("CARGO_PKG_VERSION");
 pub mod options {
     pub static BYTES: &str = "bytes";
     pub static CHAR: &str = "chars";
+    pub static FILES0_FROM: &str = "files0-from";
     pub static LINES: &str = "lines";
     pub static MAX_LINE_LENGTH: &str = "max-line-length";
     pub static WORDS: &str = "words";
@@ -194,6 +196,16 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
                 .long(options::CHAR)
                 .help("print the character counts"),
         )
+        .arg(
+            Arg::with_name(options::FILES0_FROM)
+                .long(options::FILES0_FROM)
+                .takes_value(true)
+                .value_name("F")
+                .help(
+                    "read input from the files specified by NUL-terminated names in file F; \
+                     If F is - then read names from standard input",
+                ),
+        )
         .arg(
             Arg::with_name(options::LINES)
                 .short("l")
@@ -220,19 +232,67 @@ pub fn uumain(args: impl uucore::Args) -> i32 {
         .map(|v| v.map(ToString::to_string).collect())
         .unwrap_or_default();
 
-    if files.is_empty() {
+    let mut invalid_names = false;
+    if let Some(files0_from) = matches.value_of(options::FILES0_FROM) {
+        if let Some(operand) = files.first() {
+            show_error!(
+                "extra operand '{}'\nfile operands cannot be combined with --files0-from",
+                operand
+            );
+            return 1;
+        }
+        let names = match read_files0_from(files0_from) {
+            Ok(names) => names,
+            Err(err) => {
+                show_error!("cannot open '{}' for reading: {}", files0_from, err);
+                return 1;
+            }
+        };
+        for (i, name) in names.into_iter().enumerate() {
+            if name.is_empty() {
+                show_error!("{}:{}: invalid zero-length file name", files0_from, i + 1);
+                invalid_names = true;
+            } else if name == "-" && files0_from == "-" {
+                show_error!("when reading file names from stdin, no file name of '-' allowed");
+                invalid_names = true;
+            } else {
+                files.push(name);
+            }
+        }
+    } else if files.is_empty() {
         files.push("-".to_owned());
     }
 
     let settings = Settings::new(&matches);
 
-    if wc(files, &settings).is_ok() {
+    if wc(files, &settings).is_ok() && !invalid_names {
         0
     } else {
         1
     }
 }
 
+/// Read the NUL-terminated file names in `path`, or in standard input for `-`.
+fn read_files0_from(path: &str) -> io::Result<Vec<String>> {
+    let mut buf = Vec::new();
+    if path == "-" {
+        io::stdin().read_to_end(&mut buf)?;
+    } else {
+        File::open(path)?.read_to_end(&mut buf)?;
+    }
+    // The last name does not need to be terminated.
+    if buf.last() == Some(&0) {
+        buf.pop();
+    }
+    if buf.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(buf
+        .split(|&b| b == 0)
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect())
+}
+
 const CR: u8 = b'\r';
 const LF: u8 = b'\n';
 const SPACE: u8 = b' ';
@@ -245,6 +305,25 @@ fn is_word_separator(byte: u8) -> bool {
     byte == SPACE || byte == TAB || byte == CR || byte == SYN || byte == FF
 }
 
+/// The width of the widest part of a line between carriage returns and form feeds, as
+/// displayed on a terminal: tabs advance to the next multiple of 8 columns, and
+/// control characters take no space.
+fn line_display_width<I: Iterator<Item = char>>(chars: I) -> usize {
+    let mut max_width = 0;
+    let mut width = 0;
+    for c in chars {
+        match c {
+            '\r' | '\x0c' => {
+                max_width = max(max_width, width);
+                width = 0;
+            }
+            '\t' => width += 8 - width % 8,
+            _ => width += c.width().unwrap_or(0),
+        }
+    }
+    max(max_width, width)
+}
+
 fn word_count_from_reader<T: WordCountable>(
     mut reader: T,
     settings: &Settings,
@@ -301,21 +380,23 @@ fn word_count_from_reader<T: WordCountable>(
         if decode_chars {
             // try and convert the bytes to UTF-8 first
             let current_char_count;
+            let current_line_length;
             match from_utf8(&raw_line[..]) {
                 Ok(line) => {
                     word_count += line.split_whitespace().count();
                     current_char_count = line.chars().count();
+                    current_line_length = line_display_width(line.chars());
                 }
                 Err(..) => {
                     word_count += raw_line.split(|&x| is_word_separator(x)).count();
-                    current_char_count = raw_line.iter().filter(|c| c.is_ascii()).count()
+                    current_char_count = raw_line.iter().filter(|c| c.is_ascii()).count();
+                    current_line_length = line_display_width(
+                        raw_line.iter().filter(|c| c.is_ascii()).map(|&c| c as char),
+                    );
                 }
             }
             char_count += current_char_count;
-            if current_char_count > longest_line_length {
-                // -L is a GNU 'wc' extension so same behavior on LF
-                longest_line_length = current_char_count - (ends_lf as usize);
-            }
+            longest_line_length = max(longest_line_length, current_line_length);
         }
 
         raw_line.truncate(0);
@@ -355,12 +436,26 @@ fn wc(files: Vec<String>, settings: &Settings) -> Result<(), u32> {
     let num_files = files.len();
 
     for path in &files {
-        let word_count = word_count_from_path(&path, settings).unwrap_or_else(|err| {
-            show_error!("{}", err);
-            error_count += 1;
-            WordCount::default()
-        });
-        max_width = max(max_width, word_count.bytes.to_string().len() + 1);
+        let word_count = match word_count_from_path(path, settings) {
+            Ok(word_count) => word_count,
+            Err(err) => {
+                error_count += 1;
+                match err {
+                    // Directories are still listed, with empty counts
+                    WcError::IsDirectory(_) => {
+                        show_error!("{}", err);
+                        WordCount::default()
+                    }
+                    WcError::Io(_) => {
+                        show_error!("{}: {}", path, err);
+                        continue;
+                    }
+                }
+            }
+        };
+        // Tabs can make the longest line wider than the number of bytes
+        let widest = max(word_count.bytes, word_count.max_line_length);
+        max_width = max(max_width, widest.to_string().len() + 1);
         total_word_count += word_count;
         results.push(word_count.with_title(path));
     }
@@ -387,6 +482,17 @@ fn wc(files: Vec<String>, settings: &Settings) -> Result<(), u32> {
     }
 }
 
+/// Write `count` right aligned in `min_width` columns, which include the space that
+/// separates it from the previous column, so that a count wider than the others (like a
+/// total) is still separated.
+fn write_count<W: Write>(out: &mut W, count: usize, min_width: usize) -> io::Result<()> {
+    if min_width == 0 {
+        write!(out, "{}", count)
+    } else {
+        write!(out, " {:1$}", count, min_width - 1)
+    }
+}
+
 fn print_stats(
     settings: &Settings,
     result: &TitledWordCount,
@@ -402,23 +508,19 @@ fn print_stats(
     }
 
     if settings.show_lines {
-        write!(stdout_lock, "{:1$}", result.count.lines, min_width)?;
+        write_count(&mut stdout_lock, result.count.lines, min_width)?;
     }
     if settings.show_words {
-        write!(stdout_lock, "{:1$}", result.count.words, min_width)?;
+        write_count(&mut stdout_lock, result.count.words, min_width)?;
     }
     if settings.show_bytes {
-        write!(stdout_lock, "{:1$}", result.count.bytes, min_width)?;
+        write_count(&mut stdout_lock, result.count.bytes, min_width)?;
     }
     if settings.show_chars {
-        write!(stdout_lock, "{:1$}", result.count.chars, min_width)?;
+        write_count(&mut stdout_lock, result.count.chars, min_width)?;
     }
     if settings.show_max_line_length {
-        write!(
-            stdout_lock,
-            "{:1$}",
-            result.count.max_line_length, min_width
-        )?;
+        write_count(&mut stdout_lock, result.count.max_line_length, min_width)?;
     }
 
     if result.title == "-" {
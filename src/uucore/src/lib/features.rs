@@ -6,6 +6,8 @@ pub mod encoding;
 pub mod fs;
 #[cfg(feature = "parse_time")]
 pub mod parse_time;
+#[cfg(feature = "quoting-style")]
+pub mod quoting_style;
 #[cfg(feature = "zero-copy")]
 pub mod zero_copy;
 
@@ -9,7 +9,7 @@
 
 extern crate data_encoding;
 
-use self::data_encoding::{DecodeError, BASE32, BASE64};
+use self::data_encoding::{DecodeError, BASE32, BASE64, BASE64URL, BASE64URL_NOPAD};
 
 use std::io::{self, Read, Write};
 
@@ -30,6 +30,8 @@ pub type DecodeResult = Result<Vec<u8>, EncodingError>;
 pub enum Format {
     Base32,
     Base64,
+    /// base64 with the URL and file name safe alphabet, which has `-` and `_` for `+` and `/`
+    Base64Url,
 }
 use self::Format::*;
 
@@ -37,6 +39,7 @@ pub fn encode(f: Format, input: &[u8]) -> String {
     match f {
         Base32 => BASE32.encode(input),
         Base64 => BASE64.encode(input),
+        Base64Url => BASE64URL.encode(input),
     }
 }
 
@@ -44,6 +47,11 @@ pub fn decode(f: Format, input: &[u8]) -> DecodeResult {
     Ok(match f {
         Base32 => BASE32.decode(input)?,
         Base64 => BASE64.decode(input)?,
+        // The padding is often left out of URLs, so it's optional
+        Base64Url => {
+            let end = input.iter().rposition(|&c| c != b'=').map_or(0, |i| i + 1);
+            BASE64URL_NOPAD.decode(&input[..end])?
+        }
     })
 }
 
@@ -65,6 +73,7 @@ impl<R: Read> Data<R> {
             alphabet: match format {
                 Base32 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567=",
                 Base64 => b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789=+/",
+                Base64Url => b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789=-_",
             },
         }
     }
@@ -85,7 +94,8 @@ impl<R: Read> Data<R> {
         if self.ignore_garbage {
             buf.retain(|c| self.alphabet.contains(c));
         } else {
-            buf.retain(|&c| c != b'\r' && c != b'\n');
+            // Like GNU, only newlines are allowed between the encoded characters
+            buf.retain(|&c| c != b'\n');
         };
         decode(self.format, &buf)
     }
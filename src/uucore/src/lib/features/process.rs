@@ -41,7 +41,7 @@ pub enum ExitStatus {
 }
 
 impl ExitStatus {
-    fn from_std_status(status: StdExitStatus) -> Self {
+    pub fn from_std_status(status: StdExitStatus) -> Self {
         #[cfg(unix)]
         {
             use std::os::unix::process::ExitStatusExt;
@@ -1,8 +1,20 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Quote names in the quoting styles of GNU, as in `ls --quoting-style`, `printf %q` or
+//! `stat %N`
+
 use std::char::from_digit;
 
-const SPECIAL_SHELL_CHARS: &str = "~`#$&*()|[]{};\\'\"<>?! ";
+/// Characters that make a name need quoting anywhere in it
+const SPECIAL_SHELL_CHARS: &str = " !\"$&'()*;<=>?[\\^`|";
+
+/// Characters that are still special inside double quotes
+const SPECIAL_IN_DOUBLE_QUOTES: &str = "\"$`\\!";
 
-pub(super) enum QuotingStyle {
+pub enum QuotingStyle {
     Shell {
         escape: bool,
         always_quote: bool,
@@ -17,7 +29,7 @@ pub(super) enum QuotingStyle {
 }
 
 #[derive(Clone, Copy)]
-pub(super) enum Quotes {
+pub enum Quotes {
     None,
     Single,
     Double,
@@ -35,7 +47,6 @@ enum EscapeState {
     Done,
     Char(char),
     Backslash(char),
-    ForceQuote(char),
     Octal(EscapeOctal),
 }
 
@@ -120,10 +131,10 @@ impl EscapedChar {
         Self { state: init_state }
     }
 
-    fn new_shell(c: char, escape: bool, quotes: Quotes) -> Self {
+    /// The escape of a control character in a `$'...'` expression
+    fn new_shell(c: char) -> Self {
         use EscapeState::*;
         let init_state = match c {
-            _ if !escape && c.is_control() => Char(c),
             '\x07' => Backslash('a'),
             '\x08' => Backslash('b'),
             '\t' => Backslash('t'),
@@ -131,13 +142,7 @@ impl EscapedChar {
             '\x0B' => Backslash('v'),
             '\x0C' => Backslash('f'),
             '\r' => Backslash('r'),
-            '\x00'..='\x1F' | '\x7F' => Octal(EscapeOctal::from(c)),
-            '\'' => match quotes {
-                Quotes::Single => Backslash('\''),
-                _ => Char('\''),
-            },
-            _ if SPECIAL_SHELL_CHARS.contains(c) => ForceQuote(c),
-            _ => Char(c),
+            _ => Octal(EscapeOctal::from(c)),
         };
         Self { state: init_state }
     }
@@ -161,7 +166,7 @@ impl Iterator for EscapedChar {
                 self.state = EscapeState::Char(c);
                 Some('\\')
             }
-            EscapeState::Char(c) | EscapeState::ForceQuote(c) => {
+            EscapeState::Char(c) => {
                 self.state = EscapeState::Done;
                 Some(c)
             }
@@ -171,85 +176,74 @@ impl Iterator for EscapedChar {
     }
 }
 
-fn shell_without_escape(name: &str, quotes: Quotes, show_control_chars: bool) -> (String, bool) {
-    let mut must_quote = false;
-    let mut escaped_str = String::with_capacity(name.len());
-
-    for c in name.chars() {
-        let escaped = {
-            let ec = EscapedChar::new_shell(c, false, quotes);
-            if show_control_chars {
-                ec
-            } else {
-                ec.hide_control()
-            }
-        };
+/// Whether `name` must be quoted to be read back as the same word by the shell. Control
+/// characters only need it when they're escaped.
+fn shell_needs_quoting(name: &str, escape: bool) -> bool {
+    name.is_empty()
+        || name.starts_with('#')
+        || name.starts_with('~')
+        || name == "{"
+        || name == "}"
+        || name
+            .chars()
+            .any(|c| SPECIAL_SHELL_CHARS.contains(c) || (escape && c.is_ascii_control()))
+}
 
-        match escaped.state {
-            EscapeState::Backslash('\'') => escaped_str.push_str("'\\''"),
-            EscapeState::ForceQuote(x) => {
-                must_quote = true;
-                escaped_str.push(x);
-            }
-            _ => {
-                for char in escaped {
-                    escaped_str.push(char);
-                }
-            }
+fn shell_quote(name: &str, escape: bool, always_quote: bool, show_control: bool) -> String {
+    let shown = |c: char| {
+        if !show_control && c.is_control() {
+            '?'
+        } else {
+            c
         }
+    };
+
+    if !always_quote && !shell_needs_quoting(name, escape) {
+        return name.chars().map(shown).collect();
     }
-    (escaped_str, must_quote)
-}
 
-fn shell_with_escape(name: &str, quotes: Quotes) -> (String, bool) {
-    // We need to keep track of whether we are in a dollar expression
-    // because e.g. \b\n is escaped as $'\b\n' and not like $'b'$'n'
-    let mut in_dollar = false;
-    let mut must_quote = false;
-    let mut escaped_str = String::with_capacity(name.len());
+    // Single quotes are simplest in double quotes, if nothing else in the name is special
+    // there
+    if name.contains('\'')
+        && !name
+            .chars()
+            .any(|c| SPECIAL_IN_DOUBLE_QUOTES.contains(c) || (escape && c.is_ascii_control()))
+    {
+        return format!("\"{}\"", name.chars().map(shown).collect::<String>());
+    }
 
+    // Otherwise everything is in single quotes, except single quotes themselves, which are
+    // escaped, and the escaped control characters, which are put in $'...'. We need to keep
+    // track of whether we are in one, because e.g. \b\n is escaped as $'\b\n' and not like
+    // $'\b'$'\n'
+    let mut quoted = String::with_capacity(name.len() + 2);
+    quoted.push('\'');
+    let mut in_dollar = false;
     for c in name.chars() {
-        let escaped = EscapedChar::new_shell(c, true, quotes);
-        match escaped.state {
-            EscapeState::Char(x) => {
-                if in_dollar {
-                    escaped_str.push_str("''");
-                    in_dollar = false;
-                }
-                escaped_str.push(x);
-            }
-            EscapeState::ForceQuote(x) => {
-                if in_dollar {
-                    escaped_str.push_str("''");
-                    in_dollar = false;
-                }
-                must_quote = true;
-                escaped_str.push(x);
-            }
-            // Single quotes are not put in dollar expressions, but are escaped
-            // if the string also contains double quotes. In that case, they must
-            // be handled separately.
-            EscapeState::Backslash('\'') => {
-                must_quote = true;
-                in_dollar = false;
-                escaped_str.push_str("'\\''");
-            }
-            _ => {
-                if !in_dollar {
-                    escaped_str.push_str("'$'");
-                    in_dollar = true;
-                }
-                must_quote = true;
-                for char in escaped {
-                    escaped_str.push(char);
-                }
+        if escape && c.is_ascii_control() {
+            if !in_dollar {
+                quoted.push_str("'$'");
+                in_dollar = true;
             }
+            quoted.extend(EscapedChar::new_shell(c));
+            continue;
+        }
+        if in_dollar {
+            quoted.push_str("''");
+            in_dollar = false;
+        }
+        if c == '\'' {
+            quoted.push_str("'\\''");
+        } else {
+            quoted.push(shown(c));
         }
     }
-    (escaped_str, must_quote)
+    quoted.push('\'');
+    quoted
 }
 
-pub(super) fn escape_name(name: &str, style: &QuotingStyle) -> String {
+/// Quote `name` in the given style
+pub fn escape_name(name: &str, style: &QuotingStyle) -> String {
     match style {
         QuotingStyle::Literal { show_control } => {
             if !show_control {
@@ -276,35 +270,13 @@ pub(super) fn escape_name(name: &str, style: &QuotingStyle) -> String {
             escape,
             always_quote,
             show_control,
-        } => {
-            let (quotes, must_quote) = if name.contains('"') {
-                (Quotes::Single, true)
-            } else if name.contains('\'') {
-                (Quotes::Double, true)
-            } else if *always_quote {
-                (Quotes::Single, true)
-            } else {
-                (Quotes::Single, false)
-            };
-
-            let (escaped_str, contains_quote_chars) = if *escape {
-                shell_with_escape(name, quotes)
-            } else {
-                shell_without_escape(name, quotes, *show_control)
-            };
-
-            match (must_quote | contains_quote_chars, quotes) {
-                (true, Quotes::Single) => format!("'{}'", escaped_str),
-                (true, Quotes::Double) => format!("\"{}\"", escaped_str),
-                _ => escaped_str,
-            }
-        }
+        } => shell_quote(name, *escape, *always_quote, *show_control),
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::quoting_style::{escape_name, Quotes, QuotingStyle};
+    use super::{escape_name, Quotes, QuotingStyle};
     fn get_style(s: &str) -> QuotingStyle {
         match s {
             "literal" => QuotingStyle::Literal {
@@ -640,6 +612,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_special_positions() {
+        // Tildes and hashes are only special at the start, and braces on their own
+        for name in &["~one", "#one", "{", "}"] {
+            check_names(
+                name,
+                vec![
+                    (&format!("'{}'", name), "shell"),
+                    (&format!("'{}'", name), "shell-escape"),
+                ],
+            );
+        }
+        for name in &["one~two", "one#two", "one{two", "one}"] {
+            check_names(name, vec![(name, "shell"), (name, "shell-escape")]);
+        }
+        for name in &["one=two", "one^two", "one[two"] {
+            check_names(name, vec![(&format!("'{}'", name), "shell-escape")]);
+        }
+    }
+
     #[test]
     fn test_backslash() {
         // Escaped in C-style, but not in Shell-style escaping
@@ -37,6 +37,8 @@ pub use crate::features::encoding;
 pub use crate::features::fs;
 #[cfg(feature = "parse_time")]
 pub use crate::features::parse_time;
+#[cfg(feature = "quoting-style")]
+pub use crate::features::quoting_style;
 #[cfg(feature = "zero-copy")]
 pub use crate::features::zero_copy;
 
@@ -44,6 +44,45 @@ fn test_ignore_garbage() {
     }
 }
 
+#[test]
+fn test_interspersed_garbage() {
+    let input = "aGVs*bG8s IHdv\tcmxk!IQ==\n";
+    new_ucmd!()
+        .arg("-d")
+        .pipe_in(input)
+        .fails()
+        .stderr_only("base64: error: invalid input\n");
+    new_ucmd!()
+        .args(&["-d", "-i"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_only("hello, world!");
+}
+
+#[test]
+fn test_decode_carriage_return() {
+    new_ucmd!()
+        .arg("-d")
+        .pipe_in("aGVsbG8sIHdvcmxkIQ==\r\n")
+        .fails()
+        .stderr_only("base64: error: invalid input\n");
+    new_ucmd!()
+        .args(&["-d", "--ignore-garbage"])
+        .pipe_in("aGVsbG8s\r\nIHdvcmxkIQ==\r\n")
+        .succeeds()
+        .stdout_only("hello, world!");
+}
+
+#[test]
+fn test_ignore_garbage_url_safe() {
+    // '+' and '/' aren't part of the URL safe alphabet
+    new_ucmd!()
+        .args(&["-d", "-i", "--url-safe"])
+        .pipe_in("Pz8-+Pz8_/Pg==")
+        .succeeds()
+        .stdout_only("??>???>");
+}
+
 #[test]
 fn test_wrap() {
     for wrap_param in vec!["-w", "--wrap"] {
@@ -57,6 +96,47 @@ fn test_wrap() {
     }
 }
 
+#[test]
+fn test_wrap_line_lengths() {
+    let input = "x".repeat(100);
+    let result = new_ucmd!().args(&["-w", "10"]).pipe_in(input).succeeds();
+    let lines: Vec<&str> = result.stdout_str().lines().collect();
+    assert_eq!(lines.len(), 14);
+    assert!(lines[..13].iter().all(|line| line.len() == 10));
+    assert_eq!(lines[13], "h4eA==");
+
+    // 76 columns by default
+    let input = "x".repeat(100);
+    let result = new_ucmd!().pipe_in(input).succeeds();
+    let lengths: Vec<usize> = result.stdout_str().lines().map(str::len).collect();
+    assert_eq!(lengths, [76, 60]);
+}
+
+#[test]
+fn test_wrap_zero() {
+    let input = "x".repeat(100);
+    let result = new_ucmd!().args(&["-w", "0"]).pipe_in(input).succeeds();
+    assert_eq!(result.stdout_str().len(), 136);
+    assert!(!result.stdout_str().contains('\n'));
+}
+
+#[test]
+fn test_decode_wrapped() {
+    let input = "x".repeat(100);
+    for wrap in &["0", "10", "76"] {
+        let encoded = new_ucmd!()
+            .args(&["-w", wrap])
+            .pipe_in(input.clone())
+            .succeeds()
+            .stdout_move_str();
+        new_ucmd!()
+            .arg("-d")
+            .pipe_in(encoded)
+            .succeeds()
+            .stdout_only(input.clone());
+    }
+}
+
 #[test]
 fn test_wrap_no_arg() {
     for wrap_param in vec!["-w", "--wrap"] {
@@ -77,3 +157,43 @@ fn test_wrap_bad_arg() {
             .stderr_only("base64: error: invalid wrap size: ‘b’: invalid digit found in string\n");
     }
 }
+
+#[test]
+fn test_url_safe_encode() {
+    let input: &[u8] = b"\xfb\xff\xbf\xfe";
+    new_ucmd!()
+        .pipe_in(input)
+        .succeeds()
+        .stdout_only("+/+//g==\n");
+    new_ucmd!()
+        .arg("--url-safe")
+        .pipe_in(input)
+        .succeeds()
+        .stdout_only("-_-__g==\n");
+}
+
+#[test]
+fn test_url_safe_decode() {
+    for input in &["Pz8-Pz8_Pg==", "Pz8-Pz8_Pg", "Pz8-Pz8_\nPg"] {
+        new_ucmd!()
+            .args(&["--url-safe", "-d"])
+            .pipe_in(*input)
+            .succeeds()
+            .stdout_only("??>???>");
+    }
+    new_ucmd!()
+        .args(&["--url-safe", "-d"])
+        .pipe_in("aGVsbG8sIHdvcmxkIQ")
+        .succeeds()
+        .stdout_only("hello, world!");
+}
+
+#[test]
+fn test_url_safe_decode_standard_alphabet() {
+    new_ucmd!()
+        .args(&["--url-safe", "-d"])
+        .pipe_in("+/+//g==")
+        .fails()
+        .stderr_is("base64: error: invalid input");
+    new_ucmd!().arg("-d").pipe_in("-_-__g==").fails();
+}
@@ -279,6 +279,26 @@ fn test_stdin_show_all() {
     }
 }
 
+#[test]
+fn test_show_all_raw_bytes() {
+    for same_param in vec!["-A", "--show-all", "-vET"] {
+        new_ucmd!()
+            .arg(same_param)
+            .pipe_in(&b"a\r\nb\x7f\x80\x9f\xa0\xff\x1b\tc\n"[..])
+            .succeeds()
+            .stdout_only("a^M$\nb^?M-^@M-^_M- M-^?^[^Ic$\n");
+    }
+}
+
+#[test]
+fn test_show_nonprinting_carriage_return() {
+    new_ucmd!()
+        .arg("-v")
+        .pipe_in(&b"a\r\n\x01\x1f\x80\tb\n"[..])
+        .succeeds()
+        .stdout_only("a^M\n^A^_M-^@\tb\n");
+}
+
 #[test]
 fn test_stdin_nonprinting_and_endofline() {
     new_ucmd!()
@@ -331,6 +351,28 @@ fn test_non_blank_overrides_number() {
     }
 }
 
+#[test]
+fn test_numbering_across_files() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("first", "a\n\nb\n");
+    at.write("second", "\nc\n\n");
+
+    scene
+        .ucmd()
+        .args(&["-n", "first", "second"])
+        .succeeds()
+        .stdout_only("     1\ta\n     2\t\n     3\tb\n     4\t\n     5\tc\n     6\t\n");
+    for args in &[&["-b"][..], &["-n", "-b"], &["-b", "-n"]] {
+        scene
+            .ucmd()
+            .args(*args)
+            .args(&["first", "second"])
+            .succeeds()
+            .stdout_only("     1\ta\n\n     2\tb\n\n     3\tc\n\n");
+    }
+}
+
 #[test]
 fn test_squeeze_blank_before_numbering() {
     for same_param in vec!["-s", "--squeeze-blank"] {
@@ -342,6 +384,40 @@ fn test_squeeze_blank_before_numbering() {
     }
 }
 
+#[test]
+fn test_squeeze_blank_leading_and_trailing() {
+    new_ucmd!()
+        .arg("-s")
+        .pipe_in("\n\n\na\n\n\n\nb\n\n\n\n")
+        .succeeds()
+        .stdout_only("\na\n\nb\n\n");
+}
+
+#[test]
+fn test_squeeze_blank_numbering() {
+    // Squeezed lines are not numbered
+    new_ucmd!()
+        .args(&["-s", "-n"])
+        .pipe_in("\n\n\na\n\n\n\nb\n\n\n\n")
+        .succeeds()
+        .stdout_only("     1\t\n     2\ta\n     3\t\n     4\tb\n     5\t\n");
+    new_ucmd!()
+        .args(&["-s", "-b"])
+        .pipe_in("\n\n\na\n\n\n\nb\n\n\n\n")
+        .succeeds()
+        .stdout_only("\n     1\ta\n\n     2\tb\n\n");
+}
+
+#[test]
+fn test_squeeze_blank_across_files() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("first", "a\n\n\n");
+    at.write("second", "\n\n\nb\n");
+    ucmd.args(&["-s", "-n", "first", "second"])
+        .succeeds()
+        .stdout_only("     1\ta\n     2\t\n     3\tb\n");
+}
+
 /// This tests reading from Unix character devices
 #[test]
 #[cfg(unix)]
@@ -48,7 +48,6 @@ fn emptyempty() {
         .stdout_only_fixture("emptyempty.expected");
 }
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn output_delimiter() {
     new_ucmd!()
@@ -57,13 +56,43 @@ fn output_delimiter() {
         .stdout_only_fixture("ab_delimiter_word.expected");
 }
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
-fn output_delimiter_require_arg() {
+fn output_delimiter_empty() {
+    // like GNU comm, an empty delimiter is a NUL
     new_ucmd!()
         .args(&["--output-delimiter=", "a", "b"])
-        .fails()
-        .stderr_only("error to be defined");
+        .succeeds()
+        .stdout_only("a\n\0b\n\0\0z\n");
+}
+
+#[test]
+fn suppress_unique_columns() {
+    new_ucmd!()
+        .args(&["-12", "sorted_1", "sorted_2"])
+        .succeeds()
+        .stdout_only("banana\ndate\nfig\n");
+}
+
+#[test]
+fn suppress_common_column() {
+    new_ucmd!()
+        .args(&["-3", "sorted_1", "sorted_2"])
+        .succeeds()
+        .stdout_only("apple\n\tcarrot\ncherry\n\tegg\n\tgrape\n");
+}
+
+#[test]
+fn output_delimiter_columns() {
+    new_ucmd!()
+        .args(&["--output-delimiter= | ", "sorted_1", "sorted_2"])
+        .succeeds()
+        .stdout_only(
+            "apple\n |  | banana\n | carrot\ncherry\n |  | date\n | egg\n |  | fig\n | grape\n",
+        );
+    new_ucmd!()
+        .args(&["-1", "--output-delimiter=:", "sorted_1", "sorted_2"])
+        .succeeds()
+        .stdout_only(":banana\ncarrot\n:date\negg\n:fig\ngrape\n");
 }
 
 // even though (info) documentation suggests this is an option
@@ -82,17 +111,15 @@ fn zero_terminated() {
     }
 }
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn check_order() {
     new_ucmd!()
         .args(&["--check-order", "bad_order_1", "bad_order_2"])
         .fails()
         .stdout_is_fixture("bad_order12.check_order.expected")
-        .stderr_is("error to be defined");
+        .stderr_is("comm: file 1 is not in sorted order");
 }
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn nocheck_order() {
     new_ucmd!()
@@ -104,13 +131,13 @@ fn nocheck_order() {
 // when neither --check-order nor --no-check-order is provided,
 // stderr and the error code behaves like check order, but stdout
 // behaves like nocheck_order. However with some quirks detailed below.
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn defaultcheck_order() {
     new_ucmd!()
         .args(&["a", "bad_order_1"])
         .fails()
-        .stderr_only("error to be defined");
+        .stdout_is("a\n\te\n\td\n\tb\n\ta\nz\n")
+        .stderr_is("comm: file 2 is not in sorted order\ncomm: input is not in sorted order");
 }
 
 // * the first: if both files are not in order, the default behavior is the only
@@ -128,14 +155,17 @@ fn defaultcheck_order_identical_bad_order_files() {
         .stdout_only_fixture("bad_order11.defaultcheck_order.expected");
 }
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn defaultcheck_order_two_different_bad_order_files() {
     new_ucmd!()
         .args(&["bad_order_1", "bad_order_2"])
         .fails()
         .stdout_is_fixture("bad_order12.nocheck_order.expected")
-        .stderr_is("error to be defined");
+        .stderr_is(
+            "comm: file 2 is not in sorted order\n\
+             comm: file 1 is not in sorted order\n\
+             comm: input is not in sorted order",
+        );
 }
 
 // * the third: (it is not know whether this is a bug or not)
@@ -148,7 +178,6 @@ fn defaultcheck_order_two_different_bad_order_files() {
 // there are additional, not-yet-understood circumstances where an out-of-order
 // pair is ignored and is not counted against the 1 maximum out-of-order line.
 
-#[cfg_attr(not(feature = "test_unimplemented"), ignore)]
 #[test]
 fn unintuitive_default_behavior_1() {
     new_ucmd!()
@@ -102,6 +102,49 @@ fn test_output_delimiter() {
         .stdout_only_fixture("output_delimiter.expected");
 }
 
+#[test]
+fn test_output_delimiter_field_order() {
+    // Fields are printed in input order, and only once
+    new_ucmd!()
+        .args(&["-d,", "--output-delimiter=;", "-f3,1,1"])
+        .pipe_in("a,b,c,d\n1,2,3,4\n")
+        .succeeds()
+        .stdout_only("a;c\n1;3\n");
+    new_ucmd!()
+        .args(&["-d,", "--output-delimiter=::", "-f2-3,1"])
+        .pipe_in("a,b,c,d\n1,2,3,4\n")
+        .succeeds()
+        .stdout_only("a::b::c\n1::2::3\n");
+    // Without --output-delimiter, the input delimiter is used
+    new_ucmd!()
+        .args(&["-d,", "-f4,2"])
+        .pipe_in("a,b,c,d\n")
+        .succeeds()
+        .stdout_only("b,d\n");
+}
+
+#[test]
+fn test_output_delimiter_lines_without_delimiter() {
+    new_ucmd!()
+        .args(&["-d,", "--output-delimiter=;", "-f2"])
+        .pipe_in("a,b\nnodelim\nc,d")
+        .succeeds()
+        .stdout_only("b\nnodelim\nd\n");
+    new_ucmd!()
+        .args(&["-d,", "--output-delimiter=;", "-s", "-f2"])
+        .pipe_in("a,b\nnodelim\nc,d")
+        .succeeds()
+        .stdout_only("b\nd\n");
+}
+
+#[test]
+fn test_delimiter_must_be_single_character() {
+    new_ucmd!()
+        .args(&["-d,,", "-f1"])
+        .fails()
+        .code_is(1);
+}
+
 #[test]
 fn test_complement() {
     new_ucmd!()
@@ -111,6 +154,45 @@ fn test_complement() {
         .stdout_only("9\n8\n7\n");
 }
 
+#[test]
+fn test_complement_fields() {
+    new_ucmd!()
+        .args(&["-d:", "--complement", "-f2,4"])
+        .pipe_in("a:b:c:d:e\n")
+        .succeeds()
+        .stdout_only("a:c:e\n");
+    new_ucmd!()
+        .args(&["-d:", "--complement", "-f2-", "--output-delimiter=@"])
+        .pipe_in("a:b:c\n")
+        .succeeds()
+        .stdout_only("a\n");
+    // Lines without delimiter are kept, unless -s is given
+    new_ucmd!()
+        .args(&["-d:", "--complement", "-f1"])
+        .pipe_in("a:b\nnone\n")
+        .succeeds()
+        .stdout_only("b\nnone\n");
+    new_ucmd!()
+        .args(&["-d:", "--complement", "-s", "-f1"])
+        .pipe_in("a:b\nnone\n")
+        .succeeds()
+        .stdout_only("b\n");
+}
+
+#[test]
+fn test_complement_bytes_and_characters() {
+    new_ucmd!()
+        .args(&["--complement", "-b3-"])
+        .pipe_in("abcdef\n")
+        .succeeds()
+        .stdout_only("ab\n");
+    new_ucmd!()
+        .args(&["--complement", "-c-2,5"])
+        .pipe_in("abcdef\n")
+        .succeeds()
+        .stdout_only("cdf\n");
+}
+
 #[test]
 fn test_zero_terminated() {
     new_ucmd!()
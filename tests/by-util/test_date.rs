@@ -181,14 +181,113 @@ fn test_date_set_valid_3() {
 
 #[test]
 #[cfg(all(unix, not(target_os = "macos")))]
-/// TODO: expected to fail currently; change to succeeds() when required.
 fn test_date_set_valid_4() {
     if get_effective_uid() == 0 {
-        let result = new_ucmd!()
+        new_ucmd!()
             .arg("--set")
             .arg("2020-03-11 21:45:00") // Local timezone
-            .fails();
-        result.no_stdout();
-        assert!(result.stderr_str().starts_with("date: invalid date "));
+            .succeeds()
+            .no_stdout()
+            .no_stderr();
     }
 }
+
+#[test]
+fn test_date_string_epoch() {
+    new_ucmd!()
+        .args(&["-u", "-d", "@1700000000", "+%F %T"])
+        .succeeds()
+        .stdout_only("2023-11-14 22:13:20\n");
+}
+
+#[test]
+fn test_date_string_iso() {
+    new_ucmd!()
+        .args(&["-u", "-d", "2024-01-15T12:30:05", "+%F %T"])
+        .succeeds()
+        .stdout_only("2024-01-15 12:30:05\n");
+    new_ucmd!()
+        .args(&["-u", "-d", "2024-01-15 12:00", "+%F %T"])
+        .succeeds()
+        .stdout_only("2024-01-15 12:00:00\n");
+}
+
+#[test]
+fn test_date_string_relative() {
+    new_ucmd!()
+        .args(&["-u", "-d", "2024-01-15 12:00 tomorrow", "+%F %T"])
+        .succeeds()
+        .stdout_only("2024-01-16 12:00:00\n");
+    new_ucmd!()
+        .args(&["-u", "-d", "2024-01-15 3 days ago", "+%F %T"])
+        .succeeds()
+        .stdout_only("2024-01-12 00:00:00\n");
+    new_ucmd!()
+        .args(&["-u", "-d", "2024-01-15T23:00:00 2 hours", "+%F %T"])
+        .succeeds()
+        .stdout_only("2024-01-16 01:00:00\n");
+}
+
+#[test]
+fn test_date_string_relative_negative() {
+    let result = new_ucmd!().args(&["-d", "-1 week", "+%s"]).succeeds();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+    let week_ago: i64 = result.stdout_str().trim().parse().unwrap();
+    assert!((now - 7 * 24 * 3600 - week_ago).abs() < 10);
+}
+
+#[test]
+fn test_date_string_invalid() {
+    new_ucmd!()
+        .args(&["-d", "foo"])
+        .fails()
+        .no_stdout()
+        .stderr_is("date: invalid date 'foo'");
+}
+
+#[test]
+fn test_date_format_conversions() {
+    new_ucmd!()
+        .args(&["-u", "-d", "@1700000000", "+%Y-%m-%dT%H:%M:%S%z"])
+        .succeeds()
+        .stdout_only("2023-11-14T22:13:20+0000\n");
+    new_ucmd!()
+        .args(&[
+            "-u",
+            "-d",
+            "@1700000000",
+            "+%p|%A %a|%B %b|%j|%e|%Z|%s|%%|%-d|%-m|%_H|%N",
+        ])
+        .succeeds()
+        .stdout_only("PM|Tuesday Tue|November Nov|318|14|UTC|1700000000|%|14|11|22|000000000\n");
+    new_ucmd!()
+        .args(&[
+            "-u",
+            "-d",
+            "2024-01-05T03:04:05",
+            "+%d %e %-d %H %-H %_H %I %j",
+        ])
+        .succeeds()
+        .stdout_only("05  5 5 03 3  3 03 005\n");
+}
+
+#[test]
+fn test_date_format_unknown_conversion() {
+    new_ucmd!()
+        .args(&["-u", "-d", "@0", "+%Q %Y %"])
+        .succeeds()
+        .stdout_only("%Q 1970 %\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_date_format_tz() {
+    new_ucmd!()
+        .env("TZ", "EST5")
+        .args(&["-d", "@1700000000", "+%F %T %z %Z %:z"])
+        .succeeds()
+        .stdout_only("2023-11-14 17:13:20 -0500 EST -05:00\n");
+}
@@ -20,4 +20,131 @@ fn test_df_compatible_si() {
     new_ucmd!().arg("-aH").succeeds();
 }
 
+#[test]
+fn test_df_human_readable_columns() {
+    for arg in &["-h", "-H"] {
+        let result = new_ucmd!().arg(arg).succeeds();
+        let mut lines = result.stdout_str().lines();
+        let header = lines.next().unwrap();
+        assert!(header.contains(" Size "));
+
+        // The Use% column is right aligned under its title
+        let end = header.find("Use%").unwrap() + "Use%".len();
+        for line in lines {
+            let c = line.as_bytes()[end - 1];
+            assert!(c == b'%' || c == b'-', "misaligned line {:?}", line);
+        }
+    }
+}
+
+#[test]
+fn test_df_output() {
+    let result = new_ucmd!()
+        .arg("--output=source,fstype,size,pcent")
+        .succeeds();
+    let mut lines = result.stdout_str().lines();
+    assert_eq!(
+        lines.next().unwrap().split_whitespace().collect::<Vec<_>>(),
+        ["Filesystem", "Type", "1K-blocks", "Use%"]
+    );
+    for line in lines {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(fields.len(), 4, "unexpected line {:?}", line);
+        assert!(fields[2].parse::<u64>().is_ok());
+        assert!(fields[3].ends_with('%') || fields[3] == "-");
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_df_output_file() {
+    new_ucmd!()
+        .args(&["--output=file,target", "/"])
+        .succeeds()
+        .stdout_only("File Mounted on\n/    /\n");
+}
+
+#[test]
+fn test_df_output_file_not_mount_point() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    ucmd.args(&["--output=file", "dir", "dir"])
+        .succeeds()
+        .stdout_only("File\ndir\ndir\n");
+}
+
+#[test]
+fn test_df_default_header() {
+    let result = new_ucmd!().succeeds();
+    let header = result.stdout_str().lines().next().unwrap();
+    assert!(header.contains(" Available "));
+}
+
+#[test]
+fn test_df_output_all_fields() {
+    let result = new_ucmd!().arg("--output").succeeds();
+    assert_eq!(
+        result
+            .stdout_str()
+            .lines()
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .collect::<Vec<_>>(),
+        [
+            "Filesystem",
+            "Type",
+            "Inodes",
+            "IUsed",
+            "IFree",
+            "IUse%",
+            "1K-blocks",
+            "Used",
+            "Avail",
+            "Use%",
+            "File",
+            "Mounted",
+            "on"
+        ]
+    );
+}
+
+#[test]
+fn test_df_output_invalid() {
+    new_ucmd!()
+        .arg("--output=source,foo")
+        .fails()
+        .stderr_is("df: error: option --output: field 'foo' unknown");
+    new_ucmd!()
+        .arg("--output=size,size")
+        .fails()
+        .stderr_is("df: error: option --output: field 'size' used more than once");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_df_inodes() {
+    for args in &[&["-i"][..], &["-i", "-h"][..]] {
+        let result = new_ucmd!().args(args).succeeds();
+        assert_eq!(
+            result
+                .stdout_str()
+                .lines()
+                .next()
+                .unwrap()
+                .split_whitespace()
+                .collect::<Vec<_>>(),
+            [
+                "Filesystem",
+                "Inodes",
+                "IUsed",
+                "IFree",
+                "IUse%",
+                "Mounted",
+                "on"
+            ]
+        );
+    }
+}
+
 // ToDO: more tests...
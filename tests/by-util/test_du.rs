@@ -261,3 +261,153 @@ fn _du_no_permission(s: &str) {
 fn _du_no_permission(s: &str) {
     assert_eq!(s, "4\tsubdir/links\n");
 }
+
+fn du_paths(s: &str) -> Vec<&str> {
+    let mut paths: Vec<&str> = s
+        .lines()
+        .map(|line| line.splitn(2, '\t').nth(1).unwrap())
+        .collect();
+    paths.sort_unstable();
+    paths
+}
+
+#[test]
+fn test_du_max_depth() {
+    let scene = TestScenario::new(util_name!());
+
+    let result = scene.ucmd().arg("--max-depth=1").arg("subdir").succeeds();
+    assert_eq!(
+        du_paths(result.stdout_str()),
+        ["subdir", "subdir/deeper", "subdir/links"]
+    );
+
+    // The sizes still include everything below the maximum depth
+    let full = scene.ucmd().arg("subdir").succeeds();
+    let result = scene.ucmd().arg("--max-depth=0").arg("subdir").succeeds();
+    assert_eq!(
+        result.stdout_str(),
+        full.stdout_str().lines().last().unwrap().to_owned() + "\n"
+    );
+}
+
+#[test]
+fn test_du_max_depth_all() {
+    let scene = TestScenario::new(util_name!());
+
+    let result = scene.ucmd().args(&["-a", "-d0", "subdir"]).succeeds();
+    assert_eq!(du_paths(result.stdout_str()), ["subdir"]);
+
+    let result = scene.ucmd().args(&["-a", "-d1", "subdir"]).succeeds();
+    assert_eq!(
+        du_paths(result.stdout_str()),
+        ["subdir", "subdir/deeper", "subdir/links"]
+    );
+
+    let result = scene.ucmd().args(&["-a", "-d2", "subdir"]).succeeds();
+    assert_eq!(
+        du_paths(result.stdout_str()),
+        [
+            "subdir",
+            "subdir/deeper",
+            "subdir/deeper/words.txt",
+            "subdir/links",
+            "subdir/links/subwords.txt",
+            "subdir/links/subwords2.txt"
+        ]
+    );
+}
+
+#[test]
+fn test_du_max_depth_invalid() {
+    new_ucmd!()
+        .arg("--max-depth=x")
+        .fails()
+        .stderr_is("du: error: invalid maximum depth 'x'");
+    new_ucmd!()
+        .args(&["-s", "-d1"])
+        .fails()
+        .stderr_is("du: error: summarizing conflicts with --max-depth=1");
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_du_apparent_size_sparse_file() {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let scene = TestScenario::new(util_name!());
+    let mut file = scene.fixtures.make_file("sparse");
+    file.seek(SeekFrom::Start(1024 * 1024)).unwrap();
+    file.write_all(b"x").unwrap();
+    drop(file);
+
+    scene
+        .ucmd()
+        .args(&["-b", "sparse"])
+        .succeeds()
+        .stdout_only("1048577\tsparse\n");
+    scene
+        .ucmd()
+        .args(&["--apparent-size", "sparse"])
+        .succeeds()
+        .stdout_only("1025\tsparse\n");
+
+    // Only the block with the byte is really used
+    let result = scene.ucmd().arg("sparse").succeeds();
+    let usage: u64 = result
+        .stdout_str()
+        .split('\t')
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(usage < 1025);
+}
+
+#[test]
+fn test_du_threshold() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir_all("sizes/sub");
+    at.write("sizes/small", &"x".repeat(10));
+    at.write("sizes/sub/big", &"x".repeat(100_000));
+
+    let result = scene
+        .ucmd()
+        .args(&["-a", "-b", "--threshold=50K", "sizes"])
+        .succeeds();
+    assert_eq!(
+        du_paths(result.stdout_str()),
+        ["sizes", "sizes/sub", "sizes/sub/big"]
+    );
+
+    scene
+        .ucmd()
+        .args(&["-a", "-b", "-t", "-50K", "sizes"])
+        .succeeds()
+        .stdout_only("10\tsizes/small\n");
+
+    // The threshold applies to the entries that --max-depth allows
+    scene
+        .ucmd()
+        .args(&["-a", "-b", "-d1", "-t", "-50K", "sizes"])
+        .succeeds()
+        .stdout_only("10\tsizes/small\n");
+    let result = scene
+        .ucmd()
+        .args(&["-a", "-b", "-d1", "-t", "50K", "sizes"])
+        .succeeds();
+    assert_eq!(du_paths(result.stdout_str()), ["sizes", "sizes/sub"]);
+}
+
+#[test]
+fn test_du_threshold_invalid() {
+    for threshold in &["x", "-0"] {
+        new_ucmd!()
+            .arg(format!("--threshold={}", threshold))
+            .fails()
+            .stderr_is(format!(
+                "du: error: invalid --threshold argument '{}'",
+                threshold
+            ));
+    }
+}
@@ -207,3 +207,109 @@ fn test_fail_change_directory() {
         .stderr_move_str();
     assert!(out.contains("env: cannot change directory to "));
 }
+
+#[cfg(not(windows))]
+#[test]
+fn test_split_string_quotes() {
+    new_ucmd!()
+        .arg("-S")
+        .arg("printf '[%s]' 'a b' \"c\\\"d\" '' e")
+        .succeeds()
+        .stdout_only("[a b][c\"d][][e]");
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_split_string_escaped_spaces() {
+    // like a shebang line, with the string in the same argument as -S, and arguments after it
+    new_ucmd!()
+        .arg("-S printf [%s] a\\_b \"c\\_d\"\\t")
+        .arg("e f")
+        .succeeds()
+        .stdout_only("[a][b][c d\t][e f]");
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_split_string_variables() {
+    new_ucmd!()
+        .env("SPLIT_VAR", "x y")
+        .arg("--split-string=FOO=${SPLIT_VAR} printf [%s] ${SPLIT_VAR} '${SPLIT_VAR}' ${UNSET_VAR}")
+        .succeeds()
+        .stdout_only("[x y][${SPLIT_VAR}]");
+}
+
+#[test]
+fn test_split_string_options() {
+    let out = new_ucmd!()
+        .env("BAR", "baz")
+        .args(&["-S", "-i FOO=bar"])
+        .succeeds()
+        .stdout_move_str();
+    assert_eq!(out.trim(), "FOO=bar");
+}
+
+#[test]
+fn test_split_string_invalid() {
+    new_ucmd!()
+        .args(&["-S", "echo 'a"])
+        .fails()
+        .code_is(125)
+        .stderr_is("env: no terminating quote in -S string");
+    new_ucmd!()
+        .args(&["-S", "echo $HOME"])
+        .fails()
+        .code_is(125)
+        .stderr_is("env: only ${VARNAME} expansion is supported, error at: $HOME");
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_change_directory_with_environment() {
+    let scene = TestScenario::new(util_name!());
+    let temporary_directory = tempdir().unwrap();
+    let temporary_path = fs::canonicalize(temporary_directory.path()).unwrap();
+
+    let out = scene
+        .ucmd()
+        .arg("-i")
+        .arg("-C")
+        .arg(&temporary_path)
+        .arg("FOO=bar")
+        .arg("/bin/sh")
+        .arg("-c")
+        .arg("echo $FOO; pwd")
+        .succeeds()
+        .stdout_move_str();
+    assert_eq!(out, format!("bar\n{}\n", temporary_path.to_str().unwrap()));
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_change_directory_after_reading_files() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("vars.conf", "FOO=bar\n");
+    let temporary_directory = tempdir().unwrap();
+
+    let out = ucmd
+        .arg("-f")
+        .arg("vars.conf")
+        .arg("-C")
+        .arg(temporary_directory.path())
+        .arg("/bin/sh")
+        .arg("-c")
+        .arg("echo $FOO")
+        .succeeds()
+        .stdout_move_str();
+    assert_eq!(out, "bar\n");
+}
+
+#[test]
+fn test_fail_change_directory_short() {
+    new_ucmd!()
+        .args(&["-C", "some_nonexistent_path", "pwd"])
+        .fails()
+        .code_is(125)
+        .no_stdout()
+        .stderr_contains("env: cannot change directory to \"some_nonexistent_path\": ");
+}
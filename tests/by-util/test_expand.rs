@@ -53,3 +53,63 @@ fn test_with_multiple_files() {
         .stdout_contains("    return")
         .stdout_contains("        ");
 }
+
+#[test]
+fn test_tab_size_single_width() {
+    new_ucmd!()
+        .args(&["-t", "4"])
+        .pipe_in("a\tbc\tdefgh\ti\n")
+        .succeeds()
+        .stdout_is("a   bc  defgh   i\n");
+}
+
+#[test]
+fn test_tab_stop_list() {
+    // tabs after the last explicit stop become single spaces
+    for list in &["2,8,16", "2 8 16", "2,8 16"] {
+        new_ucmd!()
+            .args(&["-t", list])
+            .pipe_in("a\tb\tc\td\te\tf\n")
+            .succeeds()
+            .stdout_is("a b     c       d e f\n");
+    }
+}
+
+#[test]
+fn test_tab_stop_list_with_repeat() {
+    new_ucmd!()
+        .args(&["-t", "2,8,/5"])
+        .pipe_in("a\tb\tc\td\te\n")
+        .succeeds()
+        .stdout_is("a b     c d    e\n");
+    new_ucmd!()
+        .args(&["-t", "2,8,+5"])
+        .pipe_in("a\tb\tc\td\te\n")
+        .succeeds()
+        .stdout_is("a b     c    d    e\n");
+}
+
+#[test]
+fn test_tab_stop_list_initial() {
+    new_ucmd!()
+        .args(&["-i", "-t", "2,5"])
+        .pipe_in("\t \tx\ty\n")
+        .succeeds()
+        .stdout_is("     x\ty\n");
+}
+
+#[test]
+fn test_invalid_tab_stops() {
+    for (list, error) in &[
+        ("4,2", "tab sizes must be ascending"),
+        ("4,4", "tab sizes must be ascending"),
+        ("0", "tab size cannot be 0"),
+        ("4x", "tab size contains invalid character(s): 'x'"),
+        ("2,/3,5", "'/' specifier only allowed with the last value"),
+    ] {
+        new_ucmd!()
+            .args(&["-t", list])
+            .fails()
+            .stderr_is(format!("expand: error: {}", error));
+    }
+}
@@ -192,6 +192,126 @@ fn test_big_primes() {
     run(instring.as_bytes(), outstring.as_bytes());
 }
 
+#[test]
+fn test_u128() {
+    // (2³¹ - 1) × (2⁶¹ - 1) and 2¹²⁷ - 1 do not fit in 64 bits
+    run(
+        b"4951760154835678088235319297 170141183460469231731687303715884105727",
+        b"4951760154835678088235319297: 2147483647 2305843009213693951\n\
+          170141183460469231731687303715884105727: 170141183460469231731687303715884105727\n",
+    );
+}
+
+#[test]
+fn test_stdin_lines() {
+    // Blank lines, repeated whitespace, invalid tokens and a final line
+    // without a trailing newline
+    new_ucmd!()
+        .pipe_in("\n 6   10\n\nfoo 15\n\t21")
+        .run()
+        .stdout_is("6: 2 3\n10: 2 5\n15: 3 5\n21: 3 7\n")
+        .stderr_contains("foo");
+}
+
+#[test]
+fn test_invalid_operands() {
+    // Valid operands are still factored, but the exit status is nonzero
+    new_ucmd!()
+        .args(&["12", "foo", "0x1g", "13"])
+        .fails()
+        .stdout_is("12: 2 2 3\n13: 13\n")
+        .stderr_contains("'foo' is not a valid positive integer")
+        .stderr_contains("'0x1g' is not a valid positive integer");
+    new_ucmd!()
+        .args(&["-j", "2", "12", "foo", "13"])
+        .fails()
+        .stdout_is("12: 2 2 3\n13: 13\n")
+        .stderr_contains("'foo' is not a valid positive integer");
+    new_ucmd!()
+        .pipe_in("4 bar\n")
+        .fails()
+        .stdout_is("4: 2 2\n")
+        .stderr_contains("'bar' is not a valid positive integer");
+    new_ucmd!().args(&["12", "13"]).succeeds();
+}
+
+#[test]
+fn test_parallel_jobs() {
+    let mut instring = String::new();
+    for i in (1..2_000).chain((1..100).map(|i| u64::MAX - 2 * i)) {
+        instring.push_str(&format!("{} ", i));
+    }
+
+    let serial = new_ucmd!().pipe_in(instring.as_bytes()).run();
+    new_ucmd!()
+        .args(&["-j", "4"])
+        .pipe_in(instring.as_bytes())
+        .run()
+        .stdout_is(serial.stdout_str());
+}
+
+#[test]
+fn test_parallel_jobs_invalid() {
+    new_ucmd!().args(&["-j", "0", "12"]).fails();
+}
+
+#[test]
+fn test_radix_prefixes() {
+    new_ucmd!()
+        .args(&["0x1F", "0XfF", "0o17", "0b1100", "010", "0xZZ", "0b2"])
+        .run()
+        .stdout_is("31: 31\n255: 3 5 17\n15: 3 5\n12: 2 2 3\n10: 2 5\n")
+        .stderr_contains("0xZZ")
+        .stderr_contains("0b2");
+    new_ucmd!()
+        .arg("0xffffffffffffffffffffffffffffffff")
+        .run()
+        .stdout_is(
+            "340282366920938463463374607431768211455: \
+             3 5 17 257 641 65537 274177 6700417 67280421310721\n",
+        );
+}
+
+#[test]
+fn test_exponents() {
+    new_ucmd!()
+        .arg("--exponents")
+        .pipe_in("1 7 120 1024")
+        .run()
+        .stdout_is("1:\n7: 7\n120: 2^3 3 5\n1024: 2^10\n");
+    new_ucmd!()
+        .args(&["-h", "18"])
+        .run()
+        .stdout_is("18: 2 3^2\n");
+}
+
+#[test]
+fn test_count() {
+    new_ucmd!()
+        .arg("--count")
+        .pipe_in("1 2 8 12 720\n")
+        .run()
+        .stdout_is("0\n1\n3\n3\n7\n");
+    new_ucmd!()
+        .args(&["--count-distinct", "1", "2", "8", "12", "720"])
+        .run()
+        .stdout_is("0\n1\n1\n2\n3\n");
+}
+
+#[test]
+fn test_json() {
+    new_ucmd!()
+        .arg("--json")
+        .pipe_in("1 7 120\n18446744073709551617\n")
+        .run()
+        .stdout_is(concat!(
+            "{\"n\":1,\"factors\":[]}\n",
+            "{\"n\":7,\"factors\":[[7,1]]}\n",
+            "{\"n\":120,\"factors\":[[2,3],[3,1],[5,1]]}\n",
+            "{\"n\":18446744073709551617,\"factors\":[[274177,1],[67280421310721,1]]}\n",
+        ));
+}
+
 fn run(instring: &[u8], outstring: &[u8]) {
     println!("STDIN='{}'", String::from_utf8_lossy(instring));
     println!("STDOUT(expected)='{}'", String::from_utf8_lossy(outstring));
@@ -543,3 +543,46 @@ fn test_obsolete_syntax() {
         .succeeds()
         .stdout_is("test1\n \ntest2\n \ntest3\n \ntest4\n \ntest5\n \ntest6\n ");
 }
+
+#[test]
+fn test_word_boundary_breaks_on_spaces() {
+    let words = "the quick brown fox jumps over the lazy dog ".repeat(10);
+    let result = new_ucmd!()
+        .args(&["-s", "-w", "72"])
+        .pipe_in(words.clone())
+        .succeeds();
+    let lines: Vec<&str> = result.stdout_str().split('\n').collect();
+    assert!(lines.len() > 1);
+    for line in &lines {
+        assert!(line.len() <= 72);
+        assert!(line.ends_with(' '));
+    }
+    assert_eq!(lines.concat(), words);
+}
+
+#[test]
+fn test_word_boundary_long_word_hard_wraps() {
+    new_ucmd!()
+        .args(&["-s", "-w", "10"])
+        .pipe_in("short abcdefghijklmnopqrstuvwxyz end\n")
+        .succeeds()
+        .stdout_is("short \nabcdefghij\nklmnopqrst\nuvwxyz end\n");
+}
+
+#[test]
+fn test_word_boundary_with_tabs() {
+    new_ucmd!()
+        .args(&["-s", "-w", "12"])
+        .pipe_in("ab\tcd ef\tgh ij\n")
+        .succeeds()
+        .stdout_is("ab\tcd \nef\tgh \nij\n");
+}
+
+#[test]
+fn test_tab_after_fold_counts_from_start_of_line() {
+    new_ucmd!()
+        .args(&["-w", "10"])
+        .pipe_in("abcdefghi\tx\n")
+        .succeeds()
+        .stdout_is("abcdefghi\n\tx\n");
+}
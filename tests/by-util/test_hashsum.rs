@@ -45,3 +45,135 @@ test_digest! {
     shake256_512 shake256 512
     b2sum b2sum 512
 }
+
+mod check {
+    use crate::common::util::*;
+
+    fn scene_with_sums() -> TestScenario {
+        let ts = TestScenario::new("hashsum");
+        let sum = ts.fixtures.read("sha256.expected");
+        ts.fixtures.write("tampered.txt", "not the input\n");
+        ts.fixtures.write(
+            "SHA256SUMS",
+            &format!(
+                "{0}  input.txt\n{0}  tampered.txt\n{0}  missing.txt\n",
+                sum.trim()
+            ),
+        );
+        ts
+    }
+
+    #[test]
+    fn test_check_good() {
+        let ts = TestScenario::new("hashsum");
+        let sum = ts.fixtures.read("sha256.expected");
+        ts.fixtures
+            .write("good", &format!("{}  input.txt\n", sum.trim()));
+        ts.ucmd()
+            .args(&["--sha256", "-c", "good"])
+            .succeeds()
+            .stdout_only("input.txt: OK\n");
+        ts.ucmd()
+            .args(&["--sha256", "--check", "--quiet", "good"])
+            .succeeds()
+            .no_stdout();
+    }
+
+    #[test]
+    fn test_check_bsd_format() {
+        let ts = TestScenario::new("hashsum");
+        let sum = ts.fixtures.read("sha256.expected");
+        ts.fixtures
+            .write("good", &format!("SHA256 (input.txt) = {}\n", sum.trim()));
+        ts.ucmd()
+            .args(&["--sha256", "-c", "good"])
+            .succeeds()
+            .stdout_only("input.txt: OK\n");
+    }
+
+    #[test]
+    fn test_check_tampered_and_missing() {
+        let ts = scene_with_sums();
+        ts.ucmd()
+            .args(&["--sha256", "-c", "SHA256SUMS"])
+            .fails()
+            .stdout_is("input.txt: OK\ntampered.txt: FAILED\nmissing.txt: FAILED open or read\n")
+            .stderr_contains("missing.txt: No such file or directory")
+            .stderr_contains("1 listed file could not be read")
+            .stderr_contains("1 computed checksum did NOT match");
+    }
+
+    #[test]
+    fn test_check_quiet() {
+        let ts = scene_with_sums();
+        ts.ucmd()
+            .args(&["--sha256", "-c", "--quiet", "SHA256SUMS"])
+            .fails()
+            .stdout_is("tampered.txt: FAILED\nmissing.txt: FAILED open or read\n");
+    }
+
+    #[test]
+    fn test_check_status() {
+        let ts = scene_with_sums();
+        ts.ucmd()
+            .args(&["--sha256", "-c", "--status", "SHA256SUMS"])
+            .fails()
+            .no_stdout()
+            .no_stderr();
+    }
+}
+
+mod tag {
+    use crate::common::util::*;
+
+    fn assert_tag_round_trip(digest_args: &[&str], algoname: &str, expected_file: &str) {
+        let ts = TestScenario::new("hashsum");
+        let expected = ts.fixtures.read(expected_file);
+        let tagged = ts
+            .ucmd()
+            .args(digest_args)
+            .args(&["--tag", "input.txt"])
+            .succeeds()
+            .stdout_move_str();
+        assert_eq!(
+            tagged,
+            format!("{} (input.txt) = {}\n", algoname, expected.trim())
+        );
+
+        ts.fixtures.write("tagged", &tagged);
+        ts.ucmd()
+            .args(digest_args)
+            .args(&["-c", "tagged"])
+            .succeeds()
+            .stdout_only("input.txt: OK\n");
+    }
+
+    #[test]
+    fn test_tag_md5() {
+        assert_tag_round_trip(&["--md5"], "MD5", "md5.expected");
+    }
+
+    #[test]
+    fn test_tag_sha256() {
+        assert_tag_round_trip(&["--sha256"], "SHA256", "sha256.expected");
+    }
+
+    #[test]
+    fn test_tag_sha3() {
+        assert_tag_round_trip(&["--sha3", "--bits=256"], "SHA3-256", "sha3_256.expected");
+    }
+
+    #[test]
+    fn test_tag_shake() {
+        assert_tag_round_trip(
+            &["--shake128", "--bits=256"],
+            "SHAKE128",
+            "shake128_256.expected",
+        );
+    }
+
+    #[test]
+    fn test_tag_b2sum() {
+        assert_tag_round_trip(&["--b2sum"], "BLAKE2b", "b2sum.expected");
+    }
+}
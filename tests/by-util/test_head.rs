@@ -154,6 +154,53 @@ fn test_negative_zero_bytes() {
         .succeeds()
         .stdout_is("qwerty");
 }
+#[test]
+fn test_all_but_last_lines() {
+    for (n, expected) in &[("-1", "a\nb\n"), ("-2", "a\n"), ("-3", ""), ("-4", "")] {
+        new_ucmd!()
+            .args(&["-n", *n])
+            .pipe_in("a\nb\nc\n")
+            .succeeds()
+            .stdout_only(*expected);
+        // An unterminated last line is still a line
+        new_ucmd!()
+            .args(&["-n", *n])
+            .pipe_in("a\nb\nc")
+            .succeeds()
+            .stdout_only(*expected);
+    }
+}
+
+#[test]
+fn test_all_but_last_lines_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("unterminated", "a\nb\nc");
+    ucmd.args(&["-n", "-1", "unterminated"])
+        .succeeds()
+        .stdout_only("a\nb\n");
+}
+
+#[test]
+fn test_all_but_last_lines_large_file() {
+    // Larger than the buffer used to look for lines from the end
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("large", &"x\n".repeat(50_000));
+    ucmd.args(&["-n", "-49999", "large"])
+        .succeeds()
+        .stdout_only("x\n");
+}
+
+#[test]
+fn test_all_but_last_bytes() {
+    for (n, expected) in &[("-1", "abcd"), ("-4", "a"), ("-5", ""), ("-6", "")] {
+        new_ucmd!()
+            .args(&["-c", *n])
+            .pipe_in("abcde")
+            .succeeds()
+            .stdout_only(*expected);
+    }
+}
+
 #[test]
 fn test_no_such_file_or_directory() {
     new_ucmd!()
@@ -5,6 +5,8 @@ use crate::common::util::*;
 extern crate regex;
 use self::regex::Regex;
 
+use filetime::FileTime;
+
 use std::path::Path;
 use std::thread::sleep;
 use std::time::Duration;
@@ -540,6 +542,30 @@ fn test_ls_order_size() {
     result.stdout_only("test-1  test-2  test-3  test-4\n");
 }
 
+#[test]
+fn test_ls_order_size_ties() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    // Sizes are compared as numbers, and equal sizes are sorted by name
+    at.write("nine", &"x".repeat(9));
+    at.write("hundred", &"x".repeat(100));
+    at.write("ten-b", &"x".repeat(10));
+    at.write("ten-a", &"x".repeat(10));
+
+    let result = scene.ucmd().arg("-S").succeeds();
+    #[cfg(not(windows))]
+    result.stdout_only("hundred\nten-a\nten-b\nnine\n");
+    #[cfg(windows)]
+    result.stdout_only("hundred  ten-a  ten-b  nine\n");
+
+    let result = scene.ucmd().arg("-Sr").succeeds();
+    #[cfg(not(windows))]
+    result.stdout_only("nine\nten-b\nten-a\nhundred\n");
+    #[cfg(windows)]
+    result.stdout_only("nine  ten-b  ten-a  hundred\n");
+}
+
 #[test]
 fn test_ls_long_ctime() {
     let scene = TestScenario::new(util_name!());
@@ -648,6 +674,36 @@ fn test_ls_order_time() {
     }
 }
 
+#[test]
+fn test_ls_order_time_ties() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    for (name, mtime) in &[
+        ("tie-b", 2000),
+        ("old", 1000),
+        ("tie-a", 2000),
+        ("new", 3000),
+    ] {
+        at.touch(name);
+        filetime::set_file_mtime(at.plus_as_string(name), FileTime::from_unix_time(*mtime, 0))
+            .unwrap();
+    }
+
+    // Files with the same mtime are sorted by name
+    let result = scene.ucmd().arg("-t").succeeds();
+    #[cfg(not(windows))]
+    result.stdout_only("new\ntie-a\ntie-b\nold\n");
+    #[cfg(windows)]
+    result.stdout_only("new  tie-a  tie-b  old\n");
+
+    let result = scene.ucmd().arg("-t").arg("-r").succeeds();
+    #[cfg(not(windows))]
+    result.stdout_only("old\ntie-b\ntie-a\nnew\n");
+    #[cfg(windows)]
+    result.stdout_only("old  tie-b  tie-a  new\n");
+}
+
 #[test]
 fn test_ls_non_existing() {
     new_ucmd!().arg("doesntexist").fails();
@@ -712,6 +768,63 @@ fn test_ls_recursive() {
     result.stdout_contains(&"a\\b:\nb");
 }
 
+#[test]
+#[cfg(not(windows))]
+fn test_ls_recursive_layout() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("a");
+    at.mkdir("a/b");
+    at.mkdir("a/c");
+    at.touch(&at.plus_as_string("a/z"));
+    at.touch(&at.plus_as_string("a/b/y"));
+    at.touch(&at.plus_as_string("a/b/x"));
+    at.touch(&at.plus_as_string("a/c/w"));
+
+    scene
+        .ucmd()
+        .args(&["-1", "-R", "a"])
+        .succeeds()
+        .stdout_only("a:\nb\nc\nz\n\na/b:\nx\ny\n\na/c:\nw\n");
+
+    // Every level is sorted, and subdirectories are visited in that order
+    scene
+        .ucmd()
+        .args(&["-1", "-R", "-r", "a"])
+        .succeeds()
+        .stdout_only("a:\nz\nc\nb\n\na/c:\nw\n\na/b:\ny\nx\n");
+
+    // Filters apply at every level
+    scene
+        .ucmd()
+        .args(&["-1", "-R", "--ignore=x", "a"])
+        .succeeds()
+        .stdout_only("a:\nb\nc\nz\n\na/b:\ny\n\na/c:\nw\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_ls_recursive_symlink_cycle() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("a");
+    at.symlink_dir("a", "a/loop");
+
+    // Symlinks to directories are not followed by default
+    scene
+        .ucmd()
+        .args(&["-1", "-R", "a"])
+        .succeeds()
+        .stdout_only("a:\nloop\n");
+
+    scene
+        .ucmd()
+        .args(&["-1", "-R", "-L", "a"])
+        .fails()
+        .stdout_is("a:\nloop\n\na/loop:\n")
+        .stderr_is("ls: error: a/loop: not listing already-listed directory");
+}
+
 #[test]
 fn test_ls_color() {
     let scene = TestScenario::new(util_name!());
@@ -789,6 +902,51 @@ fn test_ls_color() {
         ));
 }
 
+#[cfg(unix)]
+#[test]
+fn test_ls_color_ls_colors() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("dir");
+    at.touch("plain");
+    at.touch("note.txt");
+    at.touch("exec");
+    std::fs::set_permissions(
+        at.plus_as_string("exec"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    at.symlink_file("plain", "link");
+    at.symlink_file("missing", "dangling");
+
+    let ls_colors = "di=01;31:ln=01;36:ex=01;32:fi=00;33:or=05;31:*.txt=04;35";
+    scene
+        .ucmd()
+        .env("LS_COLORS", ls_colors)
+        .arg("--color=always")
+        .succeeds()
+        .stdout_only(concat!(
+            "\x1b[5;31mdangling\x1b[0m\n",
+            "\x1b[1;31mdir\x1b[0m\n",
+            "\x1b[1;32mexec\x1b[0m\n",
+            "\x1b[1;36mlink\x1b[0m\n",
+            "\x1b[4;35mnote.txt\x1b[0m\n",
+            "\x1b[33mplain\x1b[0m\n",
+        ));
+
+    // stdout is not a terminal here
+    scene
+        .ucmd()
+        .env("LS_COLORS", ls_colors)
+        .arg("--color=auto")
+        .succeeds()
+        .stdout_only("dangling\ndir\nexec\nlink\nnote.txt\nplain\n");
+
+    scene.ucmd().arg("--color=sometimes").fails();
+}
+
 #[cfg(unix)]
 #[test]
 fn test_ls_inode() {
@@ -1159,17 +1317,17 @@ fn test_ls_quoting_style() {
     {
         at.touch("one\ntwo");
         at.touch("one\\two");
-        // Default is shell-escape
+        // Default is literal when not writing to a terminal, control characters included
         scene
             .ucmd()
             .arg("one\ntwo")
             .succeeds()
-            .stdout_only("'one'$'\\n''two'\n");
+            .stdout_only("one\ntwo\n");
 
         for (arg, correct) in &[
-            ("--quoting-style=literal", "one?two"),
-            ("-N", "one?two"),
-            ("--literal", "one?two"),
+            ("--quoting-style=literal", "one\ntwo"),
+            ("-N", "one\ntwo"),
+            ("--literal", "one\ntwo"),
             ("--quoting-style=c", "\"one\\ntwo\""),
             ("-Q", "\"one\\ntwo\""),
             ("--quote-name", "\"one\\ntwo\""),
@@ -1178,13 +1336,45 @@ fn test_ls_quoting_style() {
             ("--escape", "one\\ntwo"),
             ("--quoting-style=shell-escape", "'one'$'\\n''two'"),
             ("--quoting-style=shell-escape-always", "'one'$'\\n''two'"),
+            ("--quoting-style=shell", "one\ntwo"),
+            ("--quoting-style=shell-always", "'one\ntwo'"),
+        ] {
+            scene
+                .ucmd()
+                .arg(arg)
+                .arg("one\ntwo")
+                .succeeds()
+                .stdout_only(format!("{}\n", correct));
+        }
+
+        at.touch("one\ttwo");
+        for (arg, correct) in &[
+            ("--quoting-style=literal", "one\ttwo"),
+            ("-N", "one\ttwo"),
+            ("-Q", "\"one\\ttwo\""),
+            ("-b", "one\\ttwo"),
+            ("--quoting-style=shell-escape", "'one'$'\\t''two'"),
+            ("--quoting-style=shell-always", "'one\ttwo'"),
+        ] {
+            scene
+                .ucmd()
+                .arg(arg)
+                .arg("one\ttwo")
+                .succeeds()
+                .stdout_only(format!("{}\n", correct));
+        }
+
+        for (arg, correct) in &[
+            ("--quoting-style=literal", "one?two"),
+            ("-N", "one?two"),
             ("--quoting-style=shell", "one?two"),
             ("--quoting-style=shell-always", "'one?two'"),
         ] {
             scene
                 .ucmd()
                 .arg(arg)
-                .arg("one\ntwo")
+                .arg("--hide-control-chars")
+                .arg("one\ttwo")
                 .succeeds()
                 .stdout_only(format!("{}\n", correct));
         }
@@ -1262,7 +1452,7 @@ fn test_ls_quoting_style() {
         .ucmd()
         .arg("one two")
         .succeeds()
-        .stdout_only("'one two'\n");
+        .stdout_only("one two\n");
 
     for (arg, correct) in &[
         ("--quoting-style=literal", "one two"),
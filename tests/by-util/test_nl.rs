@@ -44,15 +44,15 @@ fn test_sections_and_styles() {
     for &(fixture, output) in &[
         (
             "section.txt",
-            "\nHEADER1\nHEADER2\n\n1  |BODY1\n2  \
-             |BODY2\n\nFOOTER1\nFOOTER2\n\nNEXTHEADER1\nNEXTHEADER2\n\n1  \
-             |NEXTBODY1\n2  |NEXTBODY2\n\nNEXTFOOTER1\nNEXTFOOTER2\n",
+            "\n    HEADER1\n    HEADER2\n\n1  |BODY1\n2  \
+             |BODY2\n\n    FOOTER1\n    FOOTER2\n\n    NEXTHEADER1\n    NEXTHEADER2\n\n1  \
+             |NEXTBODY1\n2  |NEXTBODY2\n\n    NEXTFOOTER1\n    NEXTFOOTER2\n",
         ),
         (
             "joinblanklines.txt",
-            "1  |Nonempty\n2  |Nonempty\n3  |Followed by 10x empty\n\n\n\n\n4  \
-             |\n\n\n\n\n5  |\n6  |Followed by 5x empty\n\n\n\n\n7  |\n8  \
-             |Followed by 4x empty\n\n\n\n\n9  |Nonempty\n10 |Nonempty\n11 \
+            "1  |Nonempty\n2  |Nonempty\n3  |Followed by 10x empty\n    \n    \n    \n    \n4  \
+             |\n    \n    \n    \n    \n5  |\n6  |Followed by 5x empty\n    \n    \n    \n    \n7  \
+             |\n8  |Followed by 4x empty\n    \n    \n    \n    \n9  |Nonempty\n10 |Nonempty\n11 \
              |Nonempty.\n",
         ),
     ] {
@@ -64,3 +64,73 @@ fn test_sections_and_styles() {
             .stdout_is(output);
     }
 }
+
+#[test]
+fn test_body_numbering_styles() {
+    let input = "foo\nbar\n\nfoo2\n";
+    for &(style, output) in &[
+        ("a", "1|foo\n2|bar\n3|\n4|foo2\n"),
+        ("t", "1|foo\n2|bar\n  \n3|foo2\n"),
+        ("n", "  foo\n  bar\n  \n  foo2\n"),
+        ("p^foo", "1|foo\n  bar\n  \n2|foo2\n"),
+    ] {
+        new_ucmd!()
+            .args(&["-s", "|", "-w", "1", "-b", style])
+            .pipe_in(input)
+            .succeeds()
+            .stdout_is(output);
+    }
+}
+
+#[test]
+fn test_body_numbering_default_skips_empty_lines() {
+    new_ucmd!()
+        .pipe_in("a\n\nb\n")
+        .succeeds()
+        .stdout_is("     1\ta\n       \n     2\tb\n");
+}
+
+#[test]
+fn test_number_format_and_width() {
+    for &(format, output) in &[
+        ("ln", "1   |a\n2   |b\n"),
+        ("rn", "   1|a\n   2|b\n"),
+        ("rz", "0001|a\n0002|b\n"),
+    ] {
+        new_ucmd!()
+            .args(&["-s", "|", "-w", "4", "-n", format])
+            .pipe_in("a\nb\n")
+            .succeeds()
+            .stdout_is(output);
+    }
+}
+
+#[test]
+fn test_section_delimiters() {
+    let input = "\\:\\:\\:\nh1\nh2\n\\:\\:\nb1\nb2\n\\:\nf1\n\\:\\:\\:\nh3\n\\:\\:\nb3\n";
+    new_ucmd!()
+        .args(&["-s", "|", "-w", "1", "-h", "a", "-f", "n"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("\n1|h1\n2|h2\n\n1|b1\n2|b2\n\n  f1\n\n1|h3\n\n1|b3\n");
+    new_ucmd!()
+        .args(&["-s", "|", "-w", "1", "-h", "a", "-f", "a", "-p"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("\n1|h1\n2|h2\n\n3|b1\n4|b2\n\n5|f1\n\n6|h3\n\n7|b3\n");
+}
+
+#[test]
+fn test_section_delimiter_option() {
+    // a single character is followed by ':'
+    new_ucmd!()
+        .args(&["-s", "|", "-w", "1", "-h", "a", "-d", "#"])
+        .pipe_in("#:#:#:\nh\n#:#:\nb\n##\n")
+        .succeeds()
+        .stdout_is("\n1|h\n\n1|b\n2|##\n");
+    new_ucmd!()
+        .args(&["-s", "|", "-w", "1", "-h", "a", "-d", "@@"])
+        .pipe_in("@@@@@@\nh\n@@@@\nb\n\\:\\:\n")
+        .succeeds()
+        .stdout_is("\n1|h\n\n1|b\n2|\\:\\:\n");
+}
@@ -72,6 +72,33 @@ fn test_to_iec_i() {
         .stdout_is("1.0Ki\n1.2Mi\n103Mi\n");
 }
 
+#[test]
+fn test_to_si_boundaries() {
+    new_ucmd!()
+        .args(&["--to=si"])
+        .pipe_in("0\n999\n1000\n1001\n1023\n1024\n9999\n10000\n999999\n-1024\n")
+        .succeeds()
+        .stdout_is("0\n999\n1.0K\n1.1K\n1.1K\n1.1K\n10K\n10K\n1.0M\n-1.1K\n");
+}
+
+#[test]
+fn test_to_iec_boundaries() {
+    new_ucmd!()
+        .args(&["--to=iec"])
+        .pipe_in("0\n999\n1000\n1023\n1024\n1025\n10239\n1048575\n1048576\n-1024\n")
+        .succeeds()
+        .stdout_is("0\n999\n1000\n1023\n1.0K\n1.1K\n10K\n1.0M\n1.0M\n-1.0K\n");
+}
+
+#[test]
+fn test_to_iec_i_boundaries() {
+    new_ucmd!()
+        .args(&["--to=iec-i"])
+        .pipe_in("0\n1023\n1024\n9999\n999999\n1048576\n")
+        .succeeds()
+        .stdout_is("0\n1023\n1.0Ki\n9.8Ki\n977Ki\n1.0Mi\n");
+}
+
 #[test]
 fn test_input_from_free_arguments() {
     new_ucmd!()
@@ -205,12 +232,11 @@ fn test_should_report_invalid_suffix_on_stdin() {
         .run()
         .stderr_is("numfmt: invalid suffix in input: ‘1k’\n");
 
-    // GNU numfmt reports this one as “invalid number”
     new_ucmd!()
         .args(&["--from=auto"])
         .pipe_in("NaN")
         .run()
-        .stderr_is("numfmt: invalid suffix in input: ‘NaN’\n");
+        .stderr_is("numfmt: invalid number: ‘NaN’\n");
 }
 
 #[test]
@@ -374,6 +400,72 @@ fn test_implied_initial_field_value() {
         .stdout_only("1000 2000 3K\n");
 }
 
+#[test]
+fn test_format_second_column() {
+    new_ucmd!()
+        .args(&["--from=iec", "--field=2"])
+        .pipe_in("a 2K 3K\nbb 1M c\n")
+        .succeeds()
+        .stdout_only("a 2048 3K\nbb 1048576 c\n");
+    new_ucmd!()
+        .args(&["--from=si", "--field", "2-"])
+        .pipe_in("a 2K 3K\n")
+        .succeeds()
+        .stdout_only("a 2000 3000\n");
+}
+
+#[test]
+fn test_invalid_field_aborts() {
+    new_ucmd!()
+        .args(&["--field", "2"])
+        .pipe_in("x 1x0 z\n1 2 3\n")
+        .fails()
+        .code_is(2)
+        .stdout_is("x ")
+        .stderr_is("numfmt: invalid number: ‘1x0’");
+}
+
+#[test]
+fn test_invalid_fail() {
+    new_ucmd!()
+        .args(&["--field", "2", "--from=si", "--invalid=fail"])
+        .pipe_in("x 1x0 z\n1 2K 3\n")
+        .fails()
+        .code_is(2)
+        .stdout_is("x 1x0 z\n1 2000 3\n")
+        .stderr_is("numfmt: invalid number: ‘1x0’");
+}
+
+#[test]
+fn test_invalid_exit_code() {
+    for mode in &["--invalid=abort", "--invalid=fail"] {
+        new_ucmd!()
+            .args(&[mode, "abc"])
+            .fails()
+            .code_is(2)
+            .stderr_contains("invalid number: ‘abc’");
+    }
+}
+
+#[test]
+fn test_invalid_warn() {
+    new_ucmd!()
+        .args(&["--field", "2", "--from=si", "--invalid=warn"])
+        .pipe_in("x 1x0 z\n1 2K 3\n")
+        .succeeds()
+        .stdout_is("x 1x0 z\n1 2000 3\n")
+        .stderr_is("numfmt: invalid number: ‘1x0’");
+}
+
+#[test]
+fn test_invalid_ignore() {
+    new_ucmd!()
+        .args(&["--field", "2", "--from=si", "--invalid=ignore"])
+        .pipe_in("x 1x0 z\n1 2K 3\n")
+        .succeeds()
+        .stdout_only("x 1x0 z\n1 2000 3\n");
+}
+
 #[test]
 fn test_field_df_example() {
     // df -B1 | numfmt --header --field 2-4 --to=si
@@ -436,6 +436,100 @@ fn test_big_endian() {
         .stdout_is(expected_output);
 }
 
+#[test]
+fn test_endian_multibyte_words() {
+    let input: [u8; 4] = [0x01, 0x02, 0x03, 0x04];
+
+    new_ucmd!()
+        .arg("--endian=little")
+        .arg("-t")
+        .arg("x4")
+        .arg("-t")
+        .arg("d4")
+        .arg("-t")
+        .arg("x2")
+        .run_piped_stdin(&input[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000    04030201
+                       67305985
+                     0201  0403
+            0000004
+            ",
+        ));
+
+    new_ucmd!()
+        .arg("--endian=big")
+        .arg("-t")
+        .arg("x4")
+        .arg("-t")
+        .arg("d4")
+        .arg("-t")
+        .arg("x2")
+        .run_piped_stdin(&input[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000    01020304
+                       16909060
+                     0102  0304
+            0000004
+            ",
+        ));
+}
+
+#[test]
+fn test_endian_u8() {
+    let input: [u8; 8] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+    new_ucmd!()
+        .arg("--endian=little")
+        .arg("-t")
+        .arg("u8")
+        .run_piped_stdin(&input[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000   578437695752307201
+            0000010
+            ",
+        ));
+    new_ucmd!()
+        .arg("--endian=big")
+        .arg("-t")
+        .arg("u8")
+        .run_piped_stdin(&input[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000    72623859790382856
+            0000010
+            ",
+        ));
+}
+
+#[test]
+fn test_invalid_endian() {
+    new_ucmd!()
+        .arg("--endian=middle")
+        .fails()
+        .no_stdout()
+        .stderr_is(unindent(
+            "
+            od: invalid argument 'middle' for '--endian'
+            Valid arguments are:
+              - 'little'
+              - 'big'
+            Try 'od --help' for more information.
+            ",
+        ));
+}
+
 #[test]
 #[allow(non_snake_case)]
 fn test_alignment_Xxa() {
@@ -643,6 +737,75 @@ fn test_ascii_dump() {
         ));
 }
 
+#[test]
+fn test_type_hex1() {
+    new_ucmd!()
+        .arg("-t")
+        .arg("x1")
+        .run_piped_stdin(&b"Hello, World!\x01\xff"[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64 21 01 ff
+            0000017
+            ",
+        ));
+}
+
+#[test]
+fn test_type_dec2() {
+    // the odd last byte is padded with zeros
+    new_ucmd!()
+        .arg("--endian=little")
+        .arg("-t")
+        .arg("d2")
+        .run_piped_stdin(&b"Hello, World!\x01\xff"[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000  25928  27756  11375  22304  29295  25708    289    255
+            0000017
+            ",
+        ));
+}
+
+#[test]
+fn test_type_hex1_with_ascii_dump() {
+    new_ucmd!()
+        .arg("-t")
+        .arg("x1z")
+        .run_piped_stdin(&b"Hello, World!\x01\xff"[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            "
+            0000000 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64 21 01 ff     >Hello, World!..<
+            0000017
+            ",
+        ));
+}
+
+#[test]
+fn test_type_stacked() {
+    new_ucmd!()
+        .arg("-t")
+        .arg("x1")
+        .arg("-t")
+        .arg("c")
+        .run_piped_stdin(&b"Hello, World!\x01\xff"[..])
+        .no_stderr()
+        .success()
+        .stdout_is(unindent(
+            r"
+            0000000  48  65  6c  6c  6f  2c  20  57  6f  72  6c  64  21  01  ff
+                      H   e   l   l   o   ,       W   o   r   l   d   ! 001 377
+            0000017
+            ",
+        ));
+}
+
 #[test]
 fn test_filename_parsing() {
     // files "a" and "x" both exists, but are no filenames in the commandline below
@@ -60,6 +60,63 @@ static EXAMPLE_DATA: &'static [TestData<'static>] = &[
         ins: &["1\na\n", "2\nb\n"],
         out: "1 2\na b\n",
     },
+    // Serial pasting joins the lines of each file, each file on its own line.
+    TestData {
+        name: "serial-1",
+        args: &["-s"],
+        ins: &["a\nb\nc\n"],
+        out: "a\tb\tc\n",
+    },
+    TestData {
+        name: "serial-2",
+        args: &["-s"],
+        ins: &["a\nb\nc\n", "1\n2"],
+        out: "a\tb\tc\n1\t2\n",
+    },
+    // The delimiters are cycled, starting over for each file.
+    TestData {
+        name: "serial-delimiters",
+        args: &["-s", "-d", ",;"],
+        ins: &["a\nb\nc\nd\n", "1\n2\n3\n"],
+        out: "a,b;c,d\n1,2;3\n",
+    },
+    TestData {
+        name: "serial-empty-delimiter",
+        args: &["-s", "-d", "\\0-"],
+        ins: &["a\nb\nc\n"],
+        out: "ab-c\n",
+    },
+    TestData {
+        name: "serial-empty-file",
+        args: &["-s"],
+        ins: &["", "a \nb\n"],
+        out: "\na \tb\n",
+    },
+    // The delimiters are used in turn between the columns.
+    TestData {
+        name: "delimiter-list",
+        args: &["-d", ",;"],
+        ins: &["a\nb\n", "1\n2\n", "x\ny\n"],
+        out: "a,1;x\nb,2;y\n",
+    },
+    TestData {
+        name: "delimiter-list-wraps",
+        args: &["-d", ",;"],
+        ins: &["a\n", "1\n", "x\n", "a\n"],
+        out: "a,1;x,a\n",
+    },
+    TestData {
+        name: "escaped-delimiters",
+        args: &["-d", "\\t\\\\"],
+        ins: &["a\nb\n", "1\n2\n", "x\ny\n"],
+        out: "a\t1\\x\nb\t2\\y\n",
+    },
+    TestData {
+        name: "no-delimiter",
+        args: &["-d", "\\0,"],
+        ins: &["a\nb\n", "1\n2\n", "x\ny\n"],
+        out: "a1,x\nb2,y\n",
+    },
 ];
 
 #[test]
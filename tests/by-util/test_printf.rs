@@ -108,6 +108,67 @@ fn sub_bstring_handle_escapes() {
         .stdout_only("hello \tworld");
 }
 
+#[test]
+fn sub_bstring_tab() {
+    new_ucmd!()
+        .args(&["%b", "a\\tb"])
+        .succeeds()
+        .stdout_only("a\tb");
+}
+
+#[test]
+fn sub_bstring_octal() {
+    new_ucmd!()
+        .args(&["%b", "\\0101\\0102\\n"])
+        .succeeds()
+        .stdout_only("AB\n");
+}
+
+#[test]
+fn sub_qstring_plain() {
+    new_ucmd!()
+        .args(&["%q", "abc"])
+        .succeeds()
+        .stdout_only("abc");
+}
+
+#[test]
+fn sub_qstring_spaces() {
+    new_ucmd!()
+        .args(&["%q", "a b"])
+        .succeeds()
+        .stdout_only("'a b'");
+}
+
+#[test]
+fn sub_qstring_quotes() {
+    new_ucmd!()
+        .args(&["%q %q %q", "it's", "a\"b", "a'b\"c"])
+        .succeeds()
+        .stdout_only("\"it's\" 'a\"b' 'a'\\''b\"c'");
+}
+
+#[test]
+fn sub_qstring_empty() {
+    new_ucmd!().args(&["%q", ""]).succeeds().stdout_only("''");
+}
+
+#[test]
+fn sub_qstring_control_chars() {
+    new_ucmd!()
+        .args(&["%q", "a\tb\n"])
+        .succeeds()
+        .stdout_only("'a'$'\\t''b'$'\\n'");
+}
+
+#[test]
+fn sub_qstring_width() {
+    new_ucmd!()
+        .args(&["%6q|%-6q|", "a b", "c"])
+        .succeeds()
+        .stdout_only(" 'a b'|c     |");
+}
+
 #[test]
 fn sub_bstring_ignore_subs() {
     new_ucmd!()
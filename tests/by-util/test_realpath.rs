@@ -106,3 +106,140 @@ fn test_realpath_file_and_links_strip_zero() {
         .succeeds()
         .stdout_contains("bar\u{0}");
 }
+
+#[test]
+fn test_realpath_strip_relative_to() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b");
+    at.touch("a/e");
+    at.symlink_file("a/e", "link");
+    ucmd.args(&["-s", "--relative-to=x", "a/e", "a/b/../../link"])
+        .succeeds()
+        .stdout_only("../a/e\n../link\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_realpath_logical_and_physical() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir_all("a/b");
+    at.symlink_dir("a/b", "link");
+    let root = at.root_dir_resolved();
+
+    // `..` goes to the parent of the link's target unless it's resolved first
+    for args in &[
+        &["link/.."][..],
+        &["-P", "link/.."],
+        &["-L", "-P", "link/.."],
+    ] {
+        scene
+            .ucmd()
+            .args(args)
+            .succeeds()
+            .stdout_only(format!("{}/a\n", root));
+    }
+    for args in &[&["-L", "link/.."][..], &["-P", "-L", "link/.."]] {
+        scene
+            .ucmd()
+            .args(args)
+            .succeeds()
+            .stdout_only(format!("{}\n", root));
+    }
+}
+
+#[test]
+fn test_realpath_relative_to_descendant() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c/d");
+    ucmd.args(&["--relative-to=a/b", "a/b/c/d", "a/b"])
+        .succeeds()
+        .stdout_only("c/d\n.\n");
+}
+
+#[test]
+fn test_realpath_relative_to_sibling() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    at.mkdir_all("a/e");
+    ucmd.args(&["--relative-to", "a/b/c", "a/e", "a"])
+        .succeeds()
+        .stdout_only("../../e\n../..\n");
+}
+
+#[test]
+fn test_realpath_relative_base() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    at.mkdir("e");
+    let outside = at.root_dir_resolved() + "/e";
+    ucmd.args(&["--relative-base=a", "a/b/c", "e"])
+        .succeeds()
+        .stdout_only(format!("b/c\n{}\n", outside));
+}
+
+#[test]
+fn test_realpath_relative_base_and_to() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b/c");
+    at.mkdir("e");
+    let root = at.root_dir_resolved();
+    ucmd.args(&["--relative-base=a", "--relative-to=a/b", "a/b/c", "a", "e"])
+        .succeeds()
+        .stdout_only(format!("c\n..\n{}/e\n", root));
+}
+
+#[test]
+fn test_realpath_relative_to_outside_base() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b");
+    let root = at.root_dir_resolved();
+    ucmd.args(&["--relative-base=a/b", "--relative-to=a", "a/b"])
+        .succeeds()
+        .stdout_only(format!("{}/a/b\n", root));
+}
+
+#[test]
+fn test_realpath_missing_tail_default() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("a");
+    scene
+        .ucmd()
+        .arg("a/x")
+        .succeeds()
+        .stdout_only(at.root_dir_resolved() + "/a/x\n");
+    scene.ucmd().arg("a/x/y").fails().code_is(1).no_stdout();
+}
+
+#[test]
+fn test_realpath_missing_tail_existing() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("a");
+    ucmd.args(&["-e", "a", "a/x"])
+        .fails()
+        .code_is(1)
+        .stdout_is(at.root_dir_resolved() + "/a\n")
+        .stderr_contains("a/x: ");
+}
+
+#[test]
+fn test_realpath_missing_tail_missing() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("a");
+    at.symlink_dir("a", "lnk");
+    let root = at.root_dir_resolved();
+    ucmd.args(&["-m", "a/x/y", "lnk/x/../y/./z"])
+        .succeeds()
+        .stdout_only(format!("{0}/a/x/y\n{0}/a/y/z\n", root));
+}
+
+#[test]
+fn test_realpath_missing_tail_quiet() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("a");
+    ucmd.args(&["-q", "-e", "a/x"])
+        .fails()
+        .code_is(1)
+        .no_stderr();
+}
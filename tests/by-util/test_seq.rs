@@ -45,3 +45,165 @@ fn test_seq_wrong_arg() {
 fn test_zero_step() {
     new_ucmd!().args(&["10", "0", "32"]).fails();
 }
+
+#[test]
+fn test_zero_step_error() {
+    new_ucmd!()
+        .args(&["1", "0", "3"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("invalid Zero increment value: '0'");
+}
+
+#[test]
+fn test_invalid_float() {
+    new_ucmd!()
+        .arg("x")
+        .fails()
+        .no_stdout()
+        .stderr_contains("invalid floating point argument: 'x'");
+}
+
+#[test]
+fn test_float_increment() {
+    new_ucmd!()
+        .args(&["1", "0.1", "2"])
+        .succeeds()
+        .stdout_only("1.0\n1.1\n1.2\n1.3\n1.4\n1.5\n1.6\n1.7\n1.8\n1.9\n2.0\n");
+}
+
+#[test]
+fn test_float_last_reached_despite_rounding() {
+    // 0.1 * 3 is slightly more than 0.3 as a float
+    new_ucmd!()
+        .args(&["0", "0.1", "0.3"])
+        .succeeds()
+        .stdout_only("0.0\n0.1\n0.2\n0.3\n");
+    new_ucmd!()
+        .args(&["0.1", "0.1", "0.9"])
+        .succeeds()
+        .stdout_only("0.1\n0.2\n0.3\n0.4\n0.5\n0.6\n0.7\n0.8\n0.9\n");
+}
+
+#[test]
+fn test_float_count_down() {
+    new_ucmd!()
+        .args(&["3", "-0.5", "1"])
+        .succeeds()
+        .stdout_only("3.0\n2.5\n2.0\n1.5\n1.0\n");
+}
+
+#[test]
+fn test_float_precision_from_first_and_increment() {
+    // The precision of LAST doesn't matter
+    new_ucmd!()
+        .args(&["1", "1.50"])
+        .succeeds()
+        .stdout_only("1\n");
+    new_ucmd!()
+        .args(&["1", "0.5", "2.75"])
+        .succeeds()
+        .stdout_only("1.0\n1.5\n2.0\n2.5\n");
+    new_ucmd!()
+        .args(&["0.5", "3"])
+        .succeeds()
+        .stdout_only("0.5\n1.5\n2.5\n");
+}
+
+#[test]
+fn test_empty_sequence() {
+    new_ucmd!().args(&["2", "1"]).succeeds().no_stdout();
+    new_ucmd!().args(&["1", "-0.5", "2"]).succeeds().no_stdout();
+}
+
+#[test]
+fn test_equalize_widths_digit_count_increase() {
+    new_ucmd!()
+        .args(&["-w", "8", "12"])
+        .succeeds()
+        .stdout_only("08\n09\n10\n11\n12\n");
+    new_ucmd!()
+        .args(&["--equal-width", "1", "10"])
+        .succeeds()
+        .stdout_only("01\n02\n03\n04\n05\n06\n07\n08\n09\n10\n");
+}
+
+#[test]
+fn test_equalize_widths_negative() {
+    new_ucmd!()
+        .args(&["-w", "-1", "1"])
+        .succeeds()
+        .stdout_only("-1\n00\n01\n");
+    new_ucmd!()
+        .args(&["-w", "-10", "2", "-5"])
+        .succeeds()
+        .stdout_only("-10\n-08\n-06\n");
+}
+
+#[test]
+fn test_equalize_widths_float() {
+    new_ucmd!()
+        .args(&["-w", "9.5", "0.5", "10.5"])
+        .succeeds()
+        .stdout_only("09.5\n10.0\n10.5\n");
+    new_ucmd!()
+        .args(&["-w", "-1.5", "1", "1"])
+        .succeeds()
+        .stdout_only("-1.5\n-0.5\n00.5\n");
+}
+
+#[test]
+fn test_format() {
+    new_ucmd!()
+        .args(&["-f", "%03.0f", "8", "11"])
+        .succeeds()
+        .stdout_only("008\n009\n010\n011\n");
+    new_ucmd!()
+        .args(&["--format", "img_%03g.png", "1", "3"])
+        .succeeds()
+        .stdout_only("img_001.png\nimg_002.png\nimg_003.png\n");
+    new_ucmd!()
+        .args(&["-f", "%%%.2e%%", "1", "2"])
+        .succeeds()
+        .stdout_only("%1.00e+00%\n%2.00e+00%\n");
+}
+
+#[test]
+fn test_format_with_separator() {
+    // The terminator is still a newline
+    new_ucmd!()
+        .args(&["-f", "%g", "-s", ",", "0.5", "0.5", "2"])
+        .succeeds()
+        .stdout_only("0.5,1,1.5,2\n");
+    new_ucmd!()
+        .args(&["-s", ", ", "3"])
+        .succeeds()
+        .stdout_only("1, 2, 3\n");
+}
+
+#[test]
+fn test_invalid_format() {
+    new_ucmd!()
+        .args(&["-f", "%g %g", "1"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("format '%g %g' has too many % directives");
+    new_ucmd!()
+        .args(&["-f", "%d", "1"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("format '%d' has unknown %d directive");
+    new_ucmd!()
+        .args(&["-f", "abc", "1"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("format 'abc' has no % directive");
+    new_ucmd!()
+        .args(&["-w", "-f", "%g", "1"])
+        .fails()
+        .no_stdout()
+        .stderr_is(
+            "seq: format string may not be specified when printing equal width strings\n\
+             Try 'seq --help' for more information.",
+        );
+}
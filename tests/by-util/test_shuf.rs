@@ -94,6 +94,43 @@ fn test_head_count() {
     )
 }
 
+#[test]
+fn test_head_count_sample_is_distinct() {
+    let input = (1..=1000)
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let result = new_ucmd!()
+        .args(&["-n", "5"])
+        .pipe_in(input.as_bytes())
+        .succeeds();
+    result.no_stderr();
+
+    let mut result_seq: Vec<i32> = result
+        .stdout_str()
+        .split("\n")
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert!(result_seq.iter().all(|x| (1..=1000).contains(x)));
+    result_seq.sort();
+    result_seq.dedup();
+    assert_eq!(result_seq.len(), 5, "Output is not 5 distinct lines");
+}
+
+#[test]
+fn test_head_count_random_source() {
+    // With only zero bytes as a source of randomness, every line after the third replaces the
+    // first chosen one, and the sample is written starting from its first line.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("zeros", &"\0".repeat(1024));
+    ucmd.args(&["-n", "3", "--random-source", "zeros"])
+        .pipe_in("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n")
+        .succeeds()
+        .stdout_only("10\n3\n2\n");
+}
+
 #[test]
 fn test_repeat() {
     let repeat_limit = 15000;
@@ -191,6 +228,94 @@ fn test_shuf_invalid_input_range_three() {
         .stderr_contains("invalid input range: 'b'");
 }
 
+#[test]
+fn test_shuf_invalid_input_range_reversed() {
+    new_ucmd!()
+        .args(&["-i", "5-1"])
+        .fails()
+        .stderr_contains("invalid input range: '5-1'");
+}
+
+#[test]
+fn test_input_range_permutation() {
+    let result = new_ucmd!().args(&["-i", "1-100"]).succeeds();
+    result.no_stderr();
+
+    let mut result_seq: Vec<i32> = result
+        .stdout_str()
+        .split("\n")
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().unwrap())
+        .collect();
+    result_seq.sort();
+    assert_eq!(
+        result_seq,
+        (1..=100).collect::<Vec<i32>>(),
+        "Output is not a permutation"
+    );
+}
+
+#[test]
+fn test_input_range_head_count() {
+    let result = new_ucmd!().args(&["-i", "1-5", "-n", "3"]).succeeds();
+    result.no_stderr();
+
+    let mut result_seq: Vec<i32> = result
+        .stdout_str()
+        .split("\n")
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert!(
+        result_seq.iter().all(|x| (1..=5).contains(x)),
+        "Output includes element not from input: {}",
+        result.stdout_str()
+    );
+    result_seq.sort();
+    result_seq.dedup();
+    assert_eq!(result_seq.len(), 3, "Output is not 3 distinct values");
+}
+
+#[test]
+fn test_input_range_huge() {
+    // Only the chosen numbers are stored, so this is fast
+    let result = new_ucmd!()
+        .args(&["-i", "1-1000000000", "-n", "3"])
+        .succeeds();
+    result.no_stderr();
+
+    let result_seq: Vec<u64> = result
+        .stdout_str()
+        .split("\n")
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert_eq!(result_seq.len(), 3, "Output is not limited");
+    assert!(result_seq.iter().all(|x| (1..=1000000000).contains(x)));
+}
+
+#[test]
+fn test_input_range_empty() {
+    new_ucmd!().args(&["-i", "5-4"]).succeeds().no_stdout();
+}
+
+#[test]
+fn test_input_range_repeat() {
+    let result = new_ucmd!()
+        .args(&["-r", "-i", "1-3", "-n", "50"])
+        .succeeds();
+    result.no_stderr();
+
+    let result_seq: Vec<i32> = result
+        .stdout_str()
+        .split("\n")
+        .filter(|x| !x.is_empty())
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert_eq!(result_seq.len(), 50, "Output is not repeating");
+    assert!(result_seq.iter().all(|x| (1..=3).contains(x)));
+}
+
 #[test]
 fn test_shuf_invalid_input_line_count() {
     new_ucmd!()
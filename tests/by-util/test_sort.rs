@@ -51,7 +51,7 @@ fn test_version_empty_lines() {
         .arg("-V")
         .arg("version-empty-lines.txt")
         .succeeds()
-        .stdout_is("\n\n\n\n\n\n\n1.2.3-alpha\n1.2.3-alpha2\n\t\t\t1.12.4\n11.2.3\n");
+        .stdout_is("\n\n\n\n\n\n\n1.2.3-alpha\n1.2.3-alpha2\n11.2.3\n\t\t\t1.12.4\n");
 }
 
 #[test]
@@ -88,7 +88,8 @@ fn test_check_zero_terminated_failure() {
         .arg("-c")
         .arg("zero-terminated.txt")
         .fails()
-        .stdout_is("sort: disorder in line 0\n");
+        .stdout_is("")
+        .stderr_is("sort: zero-terminated.txt:2: disorder: ../../fixtures/du\n");
 }
 
 #[test]
@@ -155,6 +156,55 @@ fn test_random_shuffle_contains_two_runs_not_the_same() {
     assert_ne!(result, unexpected);
 }
 
+#[test]
+fn test_random_source_is_reproducible() {
+    const FILE: &str = "default_unsorted_ints.expected";
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write("seed", "0123456789abcdef");
+    let first = scene
+        .ucmd()
+        .args(&["-R", "--random-source=seed", FILE])
+        .succeeds()
+        .stdout_move_str();
+    let second = scene
+        .ucmd()
+        .args(&["-R", "--random-source", "seed", FILE])
+        .succeeds()
+        .stdout_move_str();
+
+    assert_eq!(first, second);
+    assert_ne!(first, scene.fixtures.read(FILE));
+}
+
+#[test]
+fn test_random_keeps_equal_lines_together() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write("seed", "some random bytes");
+    let result = scene
+        .ucmd()
+        .args(&["-R", "--random-source=seed"])
+        .pipe_in("a\nb\nc\na\nb\nc\na\nb\nc\n")
+        .succeeds()
+        .stdout_move_str();
+    let lines: Vec<&str> = result.lines().collect();
+
+    assert_eq!(lines.len(), 9);
+    for group in lines.chunks(3) {
+        assert!(group.iter().all(|line| *line == group[0]), "{:?}", lines);
+    }
+}
+
+#[test]
+fn test_random_source_too_short() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write("seed", "short");
+    scene
+        .ucmd()
+        .args(&["-R", "--random-source=seed"])
+        .fails()
+        .stderr_contains("seed");
+}
+
 #[test]
 fn test_numeric_floats_and_ints() {
     test_helper("numeric_floats_and_ints", "-n");
@@ -215,6 +265,24 @@ fn test_version() {
     test_helper("version", "-V");
 }
 
+#[test]
+fn test_version_segments() {
+    new_ucmd!()
+        .arg("-V")
+        .pipe_in("pkg-1.2.10\npkg-1.2.9\n1.0.1\n01.0\n1.00\na\nfoo.txt\nfoo-1.10.tar.gz\nfoo-1.2.tar.gz\n")
+        .succeeds()
+        .stdout_is("01.0\n1.00\n1.0.1\na\nfoo.txt\nfoo-1.2.tar.gz\nfoo-1.10.tar.gz\npkg-1.2.9\npkg-1.2.10\n");
+}
+
+#[test]
+fn test_version_tilde() {
+    new_ucmd!()
+        .arg("--version-sort")
+        .pipe_in("1.0+\n1.0a\n1.0\n1.0~rc1\n~\n1.0~~\n")
+        .succeeds()
+        .stdout_is("~\n1.0~~\n1.0~rc1\n1.0\n1.0a\n1.0+\n");
+}
+
 #[test]
 fn test_ignore_case() {
     test_helper("ignore_case", "-f");
@@ -333,6 +401,29 @@ fn test_human_block_sizes2() {
     }
 }
 
+#[test]
+fn test_human_numeric_mixed_units() {
+    new_ucmd!()
+        .arg("-h")
+        .pipe_in("2M\n1G\n1K\n0\n1023\n-1K\n1.5K\n999KB\n512B\n1MiB\n")
+        .succeeds()
+        .stdout_only("-1K\n0\n512B\n1023\n1K\n1.5K\n999KB\n1MiB\n2M\n1G\n");
+}
+
+#[test]
+fn test_human_numeric_ties() {
+    new_ucmd!()
+        .arg("-h")
+        .pipe_in("1M\n1024K\n1Gi\n1G\n1.0K\n1024\n")
+        .succeeds()
+        .stdout_only("1.0K\n1024\n1024K\n1M\n1G\n1Gi\n");
+    new_ucmd!()
+        .args(&["-h", "-s"])
+        .pipe_in("1M\n1024K\n1Gi\n1G\n")
+        .succeeds()
+        .stdout_only("1M\n1024K\n1Gi\n1G\n");
+}
+
 #[test]
 fn test_month_default2() {
     for month_sort_param in vec!["-M", "--month-sort"] {
@@ -556,6 +647,61 @@ fn test_merge_reversed() {
         .stdout_only_fixture("merge_ints_reversed.expected");
 }
 
+#[test]
+fn test_merge_unique_at_seam() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("a.txt", "1\n3\n5\n");
+    at.write("b.txt", "5\n6\n7\n");
+    at.write("c.txt", "2\n4\n8\n");
+    scene
+        .ucmd()
+        .args(&["-m", "a.txt", "b.txt", "c.txt"])
+        .succeeds()
+        .stdout_only("1\n2\n3\n4\n5\n5\n6\n7\n8\n");
+    scene
+        .ucmd()
+        .args(&["-m", "-u", "a.txt", "b.txt", "c.txt"])
+        .succeeds()
+        .stdout_only("1\n2\n3\n4\n5\n6\n7\n8\n");
+}
+
+#[test]
+fn test_merge_keys() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("a.txt", "b 1\na 1\n");
+    at.write("b.txt", "c 1\na 2\n");
+    at.write("c.txt", "x 2\nw 1\n");
+    // Equal lines are output in the order of the files they come from.
+    scene
+        .ucmd()
+        .args(&["-m", "-s", "-k2,2n", "a.txt", "b.txt"])
+        .succeeds()
+        .stdout_only("b 1\na 1\nc 1\na 2\n");
+    scene
+        .ucmd()
+        .args(&["-m", "-u", "-k2,2n", "a.txt", "b.txt"])
+        .succeeds()
+        .stdout_only("b 1\na 2\n");
+    scene
+        .ucmd()
+        .args(&["-m", "-k2,2nr", "-", "c.txt"])
+        .pipe_in("z 3\ny 0\n")
+        .succeeds()
+        .stdout_only("z 3\nx 2\nw 1\ny 0\n");
+}
+
+#[test]
+fn test_merge_zero_terminated() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a.txt", "a\0c\0");
+    at.write("b.txt", "b\0");
+    ucmd.args(&["-m", "-z", "a.txt", "b.txt"])
+        .succeeds()
+        .stdout_only("a\0b\0c\0");
+}
+
 #[test]
 fn test_pipe() {
     // TODO: issue 1608 reports a panic when we attempt to read from stdin,
@@ -575,7 +721,9 @@ fn test_check() {
         .arg("-c")
         .arg("check_fail.txt")
         .fails()
-        .stdout_is("sort: disorder in line 4\n");
+        .code_is(1)
+        .stdout_is("")
+        .stderr_is("sort: check_fail.txt:6: disorder: 5\n");
 
     new_ucmd!()
         .arg("-c")
@@ -584,13 +732,84 @@ fn test_check() {
         .stdout_is("");
 }
 
+#[test]
+fn test_check_stdin() {
+    new_ucmd!()
+        .arg("--check")
+        .pipe_in("a\nb\nb\nc\n")
+        .succeeds()
+        .no_stderr();
+
+    new_ucmd!()
+        .arg("--check=diagnose-first")
+        .pipe_in("a\nc\nb\n")
+        .fails()
+        .stderr_is("sort: -:3: disorder: b\n");
+}
+
+#[test]
+fn test_check_with_keys() {
+    new_ucmd!()
+        .args(&["-c", "-n"])
+        .pipe_in("2\n10\n")
+        .succeeds();
+    new_ucmd!()
+        .arg("-c")
+        .pipe_in("2\n10\n")
+        .fails()
+        .stderr_is("sort: -:2: disorder: 10\n");
+    new_ucmd!()
+        .args(&["-c", "-k2n"])
+        .pipe_in("a 2\nb 10\n")
+        .succeeds();
+    new_ucmd!()
+        .args(&["-c", "-k2nr"])
+        .pipe_in("a 10\nb 2\nc 3\n")
+        .fails()
+        .stderr_is("sort: -:3: disorder: c 3\n");
+}
+
+#[test]
+fn test_check_unique() {
+    // Like GNU sort, a repeated line is reported as disorder when checking with -u.
+    new_ucmd!().arg("-c").pipe_in("a\na\nb\n").succeeds();
+    new_ucmd!()
+        .args(&["-c", "-u"])
+        .pipe_in("a\na\nb\n")
+        .fails()
+        .stderr_is("sort: -:2: disorder: a\n");
+    new_ucmd!().args(&["-c", "-u"]).pipe_in("a\nb\n").succeeds();
+}
+
 #[test]
 fn test_check_silent() {
+    for silent_param in &["-C", "--check=quiet", "--check=silent"] {
+        new_ucmd!()
+            .arg(silent_param)
+            .arg("check_fail.txt")
+            .fails()
+            .stdout_is("")
+            .no_stderr();
+    }
+}
+
+#[test]
+fn test_check_extra_operand() {
     new_ucmd!()
-        .arg("-C")
-        .arg("check_fail.txt")
+        .args(&["-c", "check_fail.txt", "multiple_files.expected"])
         .fails()
-        .stdout_is("");
+        .code_is(2)
+        .stderr_contains("extra operand 'multiple_files.expected' not allowed with -c");
+    new_ucmd!()
+        .args(&["--check=quiet", "check_fail.txt", "multiple_files.expected"])
+        .fails()
+        .code_is(2)
+        .stderr_contains("extra operand 'multiple_files.expected' not allowed with -C");
+}
+
+#[test]
+fn test_check_invalid_mode() {
+    new_ucmd!().arg("--check=foo").arg("check_fail.txt").fails();
 }
 
 #[test]
@@ -231,3 +231,170 @@ fn test_filter_command_fails() {
     ucmd.args(&["--filter=/a/path/that/totally/does/not/exist", name])
         .fails();
 }
+
+#[test]
+#[cfg(unix)]
+fn test_filter_bytes() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let name = "filtered";
+    let glob = Glob::new(&at, ".", r"x[[:alpha:]][[:alpha:]]$");
+    RandomFile::new(&at, name).add_bytes(1000);
+
+    ucmd.args(&["-b", "300", "--filter=cat > $FILE", name])
+        .succeeds()
+        .no_stdout();
+    assert_eq!(glob.count(), 4);
+    assert_eq!(glob.collate(), at.read(name).into_bytes());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_filter_exit_status() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let name = "filter-will-fail";
+    RandomFile::new(&at, name).add_lines(4);
+
+    ucmd.args(&["--filter=exit 3", name])
+        .fails()
+        .code_is(3)
+        .stderr_is("split: error: with FILE=xaa, exit 3 from command: exit 3");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_filter_not_reading_input() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let name = "filter-ignores-input";
+    RandomFile::new(&at, name).add_bytes(1 << 20);
+
+    ucmd.args(&["-b", "512k", "--filter=true", name]).succeeds();
+}
+
+fn assert_chunk_sizes(size: &str, expected: &[usize]) {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let name = "input";
+    RandomFile::new(&at, name).add_bytes(2500);
+
+    ucmd.args(&["-b", size, name]).succeeds();
+    let glob = Glob::new(&at, ".", r"x[[:alpha:]][[:alpha:]]$");
+    let mut files = glob.collect();
+    files.sort();
+    let sizes: Vec<usize> = files.iter().map(|file| at.read(file).len()).collect();
+    assert_eq!(sizes, expected, "sizes with -b {}", size);
+}
+
+#[test]
+fn test_split_bytes_suffixes() {
+    assert_chunk_sizes("1K", &[1024, 1024, 452]);
+    assert_chunk_sizes("1k", &[1024, 1024, 452]);
+    assert_chunk_sizes("1KiB", &[1024, 1024, 452]);
+    assert_chunk_sizes("1KB", &[1000, 1000, 500]);
+    assert_chunk_sizes("2b", &[1024, 1024, 452]);
+    assert_chunk_sizes("1M", &[2500]);
+}
+
+#[test]
+fn test_split_bytes_invalid_size() {
+    for size in &["1X", "1KX", "K", "0", "99999999999999999999"] {
+        new_ucmd!()
+            .args(&["-b", size])
+            .fails()
+            .stderr_is(format!("split: error: invalid number of bytes: '{}'", size));
+    }
+}
+
+const CHUNKS_INPUT: &str = "a\nbbbbbbbbbb\nc\ndd\neeeeee\nf\nggg\nhhhhhhhhhhhh\ni\n";
+
+#[test]
+fn test_split_number_of_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("input", CHUNKS_INPUT);
+    ucmd.args(&["--number=4", "input"]).succeeds().no_stdout();
+
+    // 46 bytes in chunks of 11 bytes, with the rest in the last one
+    let sizes: Vec<usize> = ["xaa", "xab", "xac", "xad"]
+        .iter()
+        .map(|name| at.read(name).len())
+        .collect();
+    assert_eq!(sizes, [11, 11, 11, 13]);
+    assert_eq!(
+        Glob::new(&at, ".", r"x[[:alpha:]][[:alpha:]]$").collate(),
+        CHUNKS_INPUT.as_bytes()
+    );
+}
+
+#[test]
+fn test_split_number_of_chunks_more_than_bytes() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("input", "a\nb\n");
+    ucmd.args(&["-n", "5", "input"]).succeeds();
+    assert_eq!(at.read("xaa"), "a");
+    assert_eq!(at.read("xad"), "\n");
+    assert_eq!(at.read("xae"), "");
+}
+
+#[test]
+fn test_split_number_of_line_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("input", CHUNKS_INPUT);
+    ucmd.args(&["-n", "l/4", "input"]).succeeds();
+    assert_eq!(at.read("xaa"), "a\nbbbbbbbbbb\n");
+    assert_eq!(at.read("xab"), "c\ndd\neeeeee\n");
+    assert_eq!(at.read("xac"), "f\nggg\nhhhhhhhhhhhh\n");
+    assert_eq!(at.read("xad"), "i\n");
+}
+
+#[test]
+fn test_split_number_of_round_robin_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["-n", "r/3"]).pipe_in(CHUNKS_INPUT).succeeds();
+    assert_eq!(at.read("xaa"), "a\ndd\nggg\n");
+    assert_eq!(at.read("xab"), "bbbbbbbbbb\neeeeee\nhhhhhhhhhhhh\n");
+    assert_eq!(at.read("xac"), "c\nf\ni\n");
+    assert!(!at.file_exists("xad"));
+}
+
+#[test]
+fn test_split_number_of_chunks_from_stdin() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["-n", "l/4"]).pipe_in(CHUNKS_INPUT).succeeds();
+    assert_eq!(at.read("xaa"), "a\nbbbbbbbbbb\n");
+    assert_eq!(at.read("xad"), "i\n");
+}
+
+#[test]
+fn test_split_number_of_chunks_suffix_length() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("input", "a\n");
+    // the default suffix length is widened to name all the chunks
+    ucmd.args(&["-n", "700", "input"]).succeeds();
+    assert!(at.file_exists("xaaa"));
+    assert!(at.file_exists("xbax"));
+    assert!(!at.file_exists("xaa"));
+
+    // checked before the input is opened
+    new_ucmd!()
+        .args(&["-a", "2", "-n", "700", "nonexistent"])
+        .fails()
+        .stderr_is("split: error: the suffix length needs to be at least 3");
+}
+
+#[test]
+fn test_split_number_of_chunks_invalid() {
+    new_ucmd!()
+        .args(&["-n", "0"])
+        .fails()
+        .stderr_is("split: error: invalid number of chunks: '0'");
+    new_ucmd!()
+        .args(&["-n", "0", "nonexistent"])
+        .fails()
+        .stderr_is("split: error: invalid number of chunks: '0'");
+    new_ucmd!()
+        .args(&["-n", "x/2"])
+        .fails()
+        .stderr_is("split: error: invalid number of chunks: 'x/2'");
+    new_ucmd!()
+        .args(&["-n", "2", "-l", "3"])
+        .fails()
+        .stderr_is("split: error: cannot split in more than one way");
+}
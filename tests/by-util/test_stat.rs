@@ -66,6 +66,26 @@ mod test_fsext {
         assert_eq!("fat", pretty_fstype(0x4006));
         assert_eq!("UNKNOWN (0x1234)", pretty_fstype(0x1234));
     }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_device_numbers() {
+        // /dev/null and /dev/tty
+        assert_eq!((1, 3), (major(0x103), minor(0x103)));
+        assert_eq!((5, 0), (major(0x500), minor(0x500)));
+        // numbers that don't fit in the old 8-bit encoding
+        assert_eq!((259, 1), (major(0x10301), minor(0x10301)));
+        assert_eq!((8, 256), (major(0x100800), minor(0x100800)));
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!("'f'", pretty_quote("f"));
+        assert_eq!("'a b'", pretty_quote("a b"));
+        assert_eq!("\"it's\"", pretty_quote("it's"));
+        assert_eq!("'it'\\''s $x'", pretty_quote("it's $x"));
+        assert_eq!("'a'$'\\n''b'", pretty_quote("a\nb"));
+    }
 }
 
 #[test]
@@ -188,6 +208,31 @@ fn test_fs_format() {
         .stdout_is(expected_result(&args));
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_fs_block_sizes() {
+    let path = std::ffi::CString::new("/").unwrap();
+    let mut buf: libc::statvfs = unsafe { std::mem::zeroed() };
+    assert_eq!(unsafe { libc::statvfs(path.as_ptr(), &mut buf) }, 0);
+    new_ucmd!()
+        .args(&["-f", "-c", "%s %S %b %l %n", "/"])
+        .succeeds()
+        .stdout_only(format!(
+            "{} {} {} {} /\n",
+            buf.f_bsize, buf.f_frsize, buf.f_blocks, buf.f_namemax
+        ));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_fs_default_format() {
+    new_ucmd!()
+        .args(&["-f", "/dev/shm"])
+        .succeeds()
+        .stdout_contains("  File: \"/dev/shm\"\n")
+        .stdout_contains("Type: tmpfs\n");
+}
+
 #[test]
 #[cfg(target_os = "linux")]
 fn test_terse_normal_format() {
@@ -330,6 +375,72 @@ fn test_printf() {
         .stdout_is(expected_result(&args));
 }
 
+#[test]
+#[cfg(unix)]
+fn test_format_regular_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("f", "hello\n");
+    std::fs::set_permissions(at.plus("f"), std::fs::Permissions::from_mode(0o644)).unwrap();
+    ucmd.args(&["-c", "%s %a %A %F %h %n", "f"])
+        .succeeds()
+        .stdout_only("6 644 -rw-r--r-- regular file 1 f\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_format_quoted_name() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.touch("f");
+    at.touch("it's");
+    at.touch("a b");
+    // a relative target, which %N prints as it is
+    std::os::unix::fs::symlink("f", at.plus("lnk")).unwrap();
+    ucmd.args(&["-c", "%N", "f", "it's", "a b", "lnk"])
+        .succeeds()
+        .stdout_only("'f'\n\"it's\"\n'a b'\n'lnk' -> 'f'\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_format_ids_and_times() {
+    use std::os::unix::fs::MetadataExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.touch("f");
+    let meta = std::fs::metadata(at.plus("f")).unwrap();
+    ucmd.args(&["-c", "%i %u %g %Y %12Y|", "f"])
+        .succeeds()
+        .stdout_only(format!(
+            "{} {} {} {} {:>12}|\n",
+            meta.ino(),
+            meta.uid(),
+            meta.gid(),
+            meta.mtime(),
+            meta.mtime()
+        ));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_printf_escapes() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("f", "hello\n");
+    ucmd.args(&["--printf", "%s\\t%n\\n", "f"])
+        .succeeds()
+        .stdout_only("6\tf\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_device_format() {
+    new_ucmd!()
+        .args(&["-c", "%t %T", "/dev/null"])
+        .succeeds()
+        .stdout_only("1 3\n");
+}
+
 #[cfg(target_os = "linux")]
 fn expected_result(args: &[&str]) -> String {
     TestScenario::new(util_name!())
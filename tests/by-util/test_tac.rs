@@ -23,7 +23,59 @@ fn test_stdin_non_newline_separator_before() {
         .args(&["-b", "-s", ":"])
         .pipe_in("100:200:300:400:500")
         .run()
-        .stdout_is("500:400:300:200:100");
+        .stdout_is(":500:400:300:200100");
+}
+
+#[test]
+fn test_before_default_separator() {
+    new_ucmd!()
+        .arg("-b")
+        .pipe_in("a\nb\nc\n")
+        .succeeds()
+        .stdout_only("\n\nc\nba");
+}
+
+#[test]
+fn test_multi_character_separator() {
+    // separators are searched for from the end
+    new_ucmd!()
+        .args(&["-s", "aa"])
+        .pipe_in("xaaay")
+        .succeeds()
+        .stdout_only("yxaaa");
+    new_ucmd!()
+        .args(&["--before", "--separator=aa"])
+        .pipe_in("xaaay")
+        .succeeds()
+        .stdout_only("aayxa");
+}
+
+#[test]
+fn test_regex_separator() {
+    new_ucmd!()
+        .args(&["-r", "-s", "\\n\\n+"])
+        .pipe_in("a\nb\n\n\nc\n\nd")
+        .succeeds()
+        .stdout_only("dc\n\na\nb\n\n\n");
+    new_ucmd!()
+        .args(&["-r", "-s", "[0-9]+"])
+        .pipe_in("a12b345c6")
+        .succeeds()
+        .stdout_only("c654b32a1");
+    new_ucmd!()
+        .args(&["-r", "-b", "-s", "[0-9]+"])
+        .pipe_in("a12b345c6")
+        .succeeds()
+        .stdout_only("65c432b1a");
+}
+
+#[test]
+fn test_invalid_regex() {
+    new_ucmd!()
+        .args(&["-r", "-s", "[0-9"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("invalid regular expression");
 }
 
 #[test]
@@ -86,6 +86,91 @@ fn test_follow_multiple() {
     child.kill().unwrap();
 }
 
+#[test]
+fn test_follow_appended_in_background() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let path = at.plus(FOOBAR_TXT);
+
+    let mut child = ucmd.args(&["-f", "-s", "0.1", FOOBAR_TXT]).run_no_wait();
+
+    let expected = at.read("foobar_single_default.expected");
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    let writer = std::thread::spawn(move || {
+        let mut file = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+        for line in &["first\n", "second\n", "third\n"] {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            file.write_all(line.as_bytes()).unwrap();
+        }
+    });
+    writer.join().unwrap();
+
+    let expected = "first\nsecond\nthird\n";
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    child.kill().unwrap();
+}
+
+#[test]
+fn test_follow_truncated() {
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    let mut child = ucmd.args(&["-f", "-s", "0.1", FOOBAR_TXT]).run_no_wait();
+
+    let expected = at.read("foobar_single_default.expected");
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    // Once the file is shorter, it is followed from its new beginning
+    let expected = "new\n";
+    at.write(FOOBAR_TXT, expected);
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    child.kill().unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_follow_name_replaced() {
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    let mut child = ucmd.args(&["-F", "-s", "0.1", FOOBAR_TXT]).run_no_wait();
+
+    let expected = at.read("foobar_single_default.expected");
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    // Like a log rotation: the followed file is renamed, and a new one created
+    std::fs::rename(at.plus(FOOBAR_TXT), at.plus("foobar.txt.1")).unwrap();
+    at.write(FOOBAR_TXT, "fresh\n");
+    at.append("foobar.txt.1", "ignored\n");
+
+    let expected = "fresh\n";
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    child.kill().unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(String::from_utf8_lossy(&output.stderr).contains("following new file"));
+}
+
+#[test]
+fn test_follow_name_retry_missing() {
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    let mut child = ucmd
+        .args(&["--follow=name", "--retry", "-s", "0.1", "missing"])
+        .run_no_wait();
+
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let expected = "appeared\n";
+    at.write("missing", expected);
+    assert_eq!(read_size(&mut child, expected.len()), expected);
+
+    child.kill().unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot open 'missing' for reading"));
+    assert!(stderr.contains("'missing' has appeared;  following new file"));
+}
+
 #[test]
 fn test_follow_stdin() {
     new_ucmd!()
@@ -348,3 +433,12 @@ fn test_negative_indexing() {
 fn test_sleep_interval() {
     new_ucmd!().arg("-s").arg("10").arg(FOOBAR_TXT).succeeds();
 }
+
+#[test]
+fn test_sleep_interval_fraction() {
+    new_ucmd!().args(&["-s", "0.5", FOOBAR_TXT]).succeeds();
+    new_ucmd!()
+        .args(&["-s", "abc", FOOBAR_TXT])
+        .fails()
+        .stderr_contains("invalid number of seconds: 'abc'");
+}
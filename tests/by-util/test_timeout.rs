@@ -9,3 +9,99 @@ fn test_subcommand_retcode() {
 
     new_ucmd!().arg("1").arg("false").run().status_code(1);
 }
+
+#[test]
+fn test_kill_after() {
+    // the command ignores TERM, so it's only stopped by the KILL a second later
+    let start = std::time::Instant::now();
+    new_ucmd!()
+        .args(&["-k", "1", "0.5", "sh", "-c", "trap '' TERM; exec sleep 10"])
+        .fails()
+        .code_is(137);
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+    new_ucmd!()
+        .args(&[
+            "--kill-after=1s",
+            "0.5",
+            "sh",
+            "-c",
+            "trap '' TERM; exec sleep 10",
+        ])
+        .fails()
+        .code_is(137);
+}
+
+#[test]
+fn test_wait_after_signal() {
+    // without -k, the command is left to finish after ignoring TERM
+    new_ucmd!()
+        .args(&["0.5", "sh", "-c", "trap '' TERM; sleep 1; echo done"])
+        .fails()
+        .code_is(124)
+        .stdout_is("done\n");
+}
+
+#[test]
+fn test_invalid_kill_after() {
+    new_ucmd!()
+        .args(&["-k", "1x", "1", "true"])
+        .fails()
+        .code_is(125)
+        .stderr_contains("invalid time interval '1x'");
+}
+
+#[test]
+fn test_signal() {
+    // the command says which signal it got
+    let script = "trap 'echo INT; exit 3' INT; trap 'echo TERM; exit 4' TERM; \
+                  while :; do sleep 0.1; done";
+    new_ucmd!()
+        .args(&["-s", "INT", "0.5", "sh", "-c", script])
+        .fails()
+        .code_is(124)
+        .stdout_is("INT\n");
+    new_ucmd!()
+        .args(&["--signal=2", "--preserve-status", "0.5", "sh", "-c", script])
+        .fails()
+        .code_is(3)
+        .stdout_is("INT\n");
+    new_ucmd!()
+        .args(&["--preserve-status", "0.5", "sh", "-c", script])
+        .fails()
+        .code_is(4)
+        .stdout_is("TERM\n");
+}
+
+#[test]
+fn test_preserve_status_of_signal() {
+    new_ucmd!()
+        .args(&["--preserve-status", "0.5", "sleep", "5"])
+        .fails()
+        .code_is(128 + 15);
+    new_ucmd!()
+        .args(&["-s", "sigint", "--preserve-status", "0.5", "sleep", "5"])
+        .fails()
+        .code_is(128 + 2);
+}
+
+#[test]
+fn test_kill_signal() {
+    new_ucmd!()
+        .args(&["-s", "KILL", "0.5", "sleep", "5"])
+        .fails()
+        .code_is(128 + 9);
+    new_ucmd!()
+        .args(&["-s", "9", "0.5", "sleep", "5"])
+        .fails()
+        .code_is(128 + 9);
+}
+
+#[test]
+fn test_invalid_signal() {
+    new_ucmd!()
+        .args(&["-s", "FOO", "1", "true"])
+        .fails()
+        .code_is(125)
+        .stderr_contains("'FOO': invalid signal");
+}
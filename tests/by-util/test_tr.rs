@@ -81,6 +81,113 @@ fn test_delete_and_squeeze_complement() {
         .stdout_is("abc");
 }
 
+#[test]
+fn test_squeeze_across_lines() {
+    new_ucmd!()
+        .args(&["-s", "\\n"])
+        .pipe_in("a\n\n\nb\n")
+        .run()
+        .stdout_is("a\nb\n");
+}
+
+#[test]
+fn test_translate_and_squeeze() {
+    new_ucmd!()
+        .args(&["-s", "a-z", "A-Z"])
+        .pipe_in("aabbccd\n\n\nx")
+        .run()
+        .stdout_is("ABCD\n\n\nX");
+}
+
+#[test]
+fn test_translate_and_squeeze_translated_runs() {
+    // Runs are squeezed in the translated output, even if they were different characters
+    new_ucmd!()
+        .args(&["-s", "ab", "xx"])
+        .pipe_in("abBBba")
+        .run()
+        .stdout_is("xBBx");
+    new_ucmd!()
+        .args(&["-s", "X", "a"])
+        .pipe_in("aXXa")
+        .run()
+        .stdout_is("a");
+}
+
+#[test]
+fn test_delete_then_squeeze() {
+    // Squeezing applies to what is left after deleting
+    new_ucmd!()
+        .args(&["-ds", "c", "ab"])
+        .pipe_in("abcaAAbcb")
+        .run()
+        .stdout_is("abaAAb");
+}
+
+#[test]
+fn test_delete_complement_class() {
+    new_ucmd!()
+        .args(&["-cd", "[:print:]\\n"])
+        .pipe_in("hello\tw\x01rld!\n")
+        .run()
+        .stdout_is("hellowrld!\n");
+}
+
+#[test]
+fn test_translate_complement() {
+    new_ucmd!()
+        .args(&["-c", "a-z\\n", "_"])
+        .pipe_in("ab1-c\n")
+        .run()
+        .stdout_is("ab__c\n");
+}
+
+#[test]
+fn test_translate_complement_ascending() {
+    // The complement starts at \0, so only \0 is translated to `x`
+    new_ucmd!()
+        .args(&["-c", "a-z\\n", "xy"])
+        .pipe_in("ab\x00\x01\x02d\n")
+        .run()
+        .stdout_is("abxyyd\n");
+}
+
+#[test]
+fn test_translate_and_squeeze_complement() {
+    new_ucmd!()
+        .args(&["-cs", "a-z\\n", "X"])
+        .pipe_in("ab12 c\n")
+        .run()
+        .stdout_is("abXc\n");
+}
+
+#[test]
+fn test_octal_range() {
+    new_ucmd!()
+        .args(&["\\141-\\143", "A-C"])
+        .pipe_in("abcxyz\n")
+        .run()
+        .stdout_is("ABCxyz\n");
+}
+
+#[test]
+fn test_escaped_dash_is_not_a_range() {
+    new_ucmd!()
+        .args(&["a\\-z", "xyz"])
+        .pipe_in("abc-z\n")
+        .run()
+        .stdout_is("xbcyz\n");
+}
+
+#[test]
+fn test_escaped_backslash() {
+    new_ucmd!()
+        .args(&["\\\\", "/"])
+        .pipe_in("a\\b\n")
+        .run()
+        .stdout_is("a/b\n");
+}
+
 #[test]
 fn test_set1_longer_than_set2() {
     new_ucmd!()
@@ -193,3 +300,45 @@ fn test_interpret_backslash_at_eol_literally() {
         .succeeds()
         .stdout_is("\\");
 }
+
+#[test]
+fn test_class_case_folding() {
+    new_ucmd!()
+        .args(&["[:upper:]", "[:lower:]"])
+        .pipe_in("Hello World 123")
+        .run()
+        .stdout_is("hello world 123");
+    new_ucmd!()
+        .args(&["[:lower:][:upper:]", "[:upper:][:lower:]"])
+        .pipe_in("Hello")
+        .run()
+        .stdout_is("hELLO");
+}
+
+#[test]
+fn test_delete_class() {
+    new_ucmd!()
+        .args(&["-d", "[:space:]"])
+        .pipe_in("a b\tc\nd\x0be\x0cf\rg")
+        .run()
+        .stdout_is("abcdefg");
+    new_ucmd!()
+        .args(&["-d", "[:punct:][:digit:]"])
+        .pipe_in("a1-b2_c3!")
+        .run()
+        .stdout_is("abc");
+    new_ucmd!()
+        .args(&["-cd", "[:alpha:]"])
+        .pipe_in("ab12")
+        .run()
+        .stdout_is("ab");
+}
+
+#[test]
+fn test_squeeze_class() {
+    new_ucmd!()
+        .args(&["-s", "[:blank:]"])
+        .pipe_in("aaa \t  bbb")
+        .run()
+        .stdout_is("aaa \t bbb");
+}
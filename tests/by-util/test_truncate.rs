@@ -206,7 +206,7 @@ fn test_round_up() {
     let (at, mut ucmd) = at_and_ucmd!();
     let mut file = at.make_file(TFILE2);
     file.write_all(b"1234567890").unwrap();
-    ucmd.args(&["--size", "*4", TFILE2]).succeeds();
+    ucmd.args(&["--size", "%4", TFILE2]).succeeds();
     file.seek(SeekFrom::End(0)).unwrap();
     let actual = file.seek(SeekFrom::Current(0)).unwrap();
     assert!(
@@ -229,3 +229,114 @@ fn test_size_and_reference() {
     let actual = file2.seek(SeekFrom::Current(0)).unwrap();
     assert!(expected == actual, "expected '{}' got '{}'", expected, actual);
 }
+
+#[test]
+fn test_relative_sizes() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write(TFILE1, "1234567890");
+    ucmd.args(&["-s", "+1K", TFILE1]).succeeds();
+    assert_eq!(at.read(TFILE1).len(), 1034);
+
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write(TFILE1, "1234567890");
+    scene.ucmd().args(&["-s", "-4", TFILE1]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE1), "123456");
+    // the size can't go below 0
+    scene.ucmd().args(&["-s", "-512", TFILE1]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE1), "");
+}
+
+#[test]
+fn test_round_up_to_multiple() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write(TFILE1, "1234567890");
+    scene.ucmd().args(&["-s", "%4096", TFILE1]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE1).len(), 4096);
+    // a multiple is left alone
+    scene.ucmd().args(&["-s", "%1K", TFILE1]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE1).len(), 4096);
+    scene.ucmd().args(&["-s", "/1000", TFILE1]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE1).len(), 4000);
+}
+
+#[test]
+fn test_round_by_zero() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write(TFILE1, "1234567890");
+    ucmd.args(&["-s", "%0", TFILE1])
+        .fails()
+        .stderr_is("truncate: error: division by zero");
+}
+
+#[test]
+fn test_relative_size_of_new_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["-s", "+5", TFILE1]).succeeds();
+    assert_eq!(at.read(TFILE1).len(), 5);
+}
+
+#[test]
+fn test_no_create() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["-c", "-s", "+5", TFILE1])
+        .succeeds()
+        .no_stderr();
+    assert!(!at.file_exists(TFILE1));
+}
+
+#[test]
+fn test_invalid_size() {
+    for size in &["5A", "x", "1Y"] {
+        new_ucmd!()
+            .args(&["-s", size, TFILE1])
+            .fails()
+            .stderr_contains(&format!("Invalid number: ‘{}’", size));
+    }
+}
+
+#[test]
+fn test_reference_size() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write(TFILE1, "1234567890");
+    scene.fixtures.write(TFILE2, "abc");
+    // extended to the size of the reference
+    scene.ucmd().args(&["-r", TFILE1, TFILE2]).succeeds();
+    assert_eq!(scene.fixtures.read(TFILE2).len(), 10);
+    assert!(scene.fixtures.read(TFILE2).starts_with("abc"));
+
+    // and truncated to it
+    scene.fixtures.write(TFILE2, "abcdefghijklmnopqrstuvwxyz");
+    scene
+        .ucmd()
+        .args(&["--reference", TFILE1, TFILE2])
+        .succeeds();
+    assert_eq!(scene.fixtures.read(TFILE2), "abcdefghij");
+}
+
+#[test]
+fn test_reference_with_relative_sizes() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write(TFILE1, "1234567890");
+    for &(size, expected) in &[("+1K", 1034), ("-4", 6), ("%4", 12), ("<4", 4)] {
+        scene.fixtures.write(TFILE2, "");
+        scene
+            .ucmd()
+            .args(&["-r", TFILE1, "-s", size, TFILE2])
+            .succeeds();
+        assert_eq!(scene.fixtures.read(TFILE2).len(), expected);
+    }
+    scene
+        .ucmd()
+        .args(&["-r", TFILE1, "-s", "4", TFILE2])
+        .fails()
+        .stderr_contains("you must specify a relative ‘--size’ with ‘--reference’");
+}
+
+#[test]
+fn test_reference_missing() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["-r", "missing", TFILE1])
+        .fails()
+        .stderr_contains("cannot stat ‘missing’");
+    assert!(!at.file_exists(TFILE1));
+}
@@ -155,3 +155,41 @@ fn unexpand_read_from_two_file() {
         .run()
         .success();
 }
+
+#[test]
+fn unexpand_leading_only_by_default() {
+    new_ucmd!()
+        .pipe_in("        a       b  c   d\n")
+        .succeeds()
+        .stdout_is("\ta       b  c   d\n");
+}
+
+#[test]
+fn unexpand_all_blanks_with_aflag() {
+    new_ucmd!()
+        .arg("-a")
+        .pipe_in("        a       b  c   d\nabcdefg  x\n")
+        .succeeds()
+        .stdout_is("\ta\tb  c   d\nabcdefg\t x\n");
+}
+
+#[test]
+fn unexpand_tab_size_4() {
+    // -t implies -a, but a single space reaching a tab stop stays a space,
+    // and tabs that are already there are kept
+    new_ucmd!()
+        .args(&["-t", "4"])
+        .pipe_in("        a       b  c   d\nabc d\nabc  \tx\n")
+        .succeeds()
+        .stdout_is("\t\ta\t\tb  c   d\nabc d\nabc\t\tx\n");
+}
+
+#[test]
+fn unexpand_multiple_files() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a.txt", "    a\n");
+    at.write("b.txt", "        b\n");
+    ucmd.args(&["-t4", "a.txt", "b.txt"])
+        .succeeds()
+        .stdout_is("\ta\n\t\tb\n");
+}
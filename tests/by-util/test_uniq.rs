@@ -192,3 +192,99 @@ fn test_group_separate() {
         .run()
         .stdout_is_fixture("group.expected");
 }
+
+#[test]
+fn test_counts_with_runs() {
+    let input = "a\nb\nb\nc\nC\nc\nd\nd\nd\n";
+    for &(args, output) in &[
+        (
+            &["-c"][..],
+            "      1 a\n      2 b\n      1 c\n      1 C\n      1 c\n      3 d\n",
+        ),
+        (&["-c", "-d"][..], "      2 b\n      3 d\n"),
+        (
+            &["-c", "-u"][..],
+            "      1 a\n      1 c\n      1 C\n      1 c\n",
+        ),
+        (
+            &["-c", "-i"][..],
+            "      1 a\n      2 b\n      3 c\n      3 d\n",
+        ),
+        (&["-c", "-d", "-i"][..], "      2 b\n      3 c\n      3 d\n"),
+    ] {
+        new_ucmd!()
+            .args(args)
+            .pipe_in(input)
+            .succeeds()
+            .stdout_is(output);
+    }
+}
+
+#[test]
+fn test_counts_with_all_repeated() {
+    new_ucmd!()
+        .args(&["-c", "-D"])
+        .fails()
+        .no_stdout()
+        .stderr_contains("printing all duplicated lines and repeat counts is meaningless");
+}
+
+#[test]
+fn test_skip_leading_timestamp() {
+    let input =
+        "10:00 start job\n10:01 start job\n10:02 stop job\n10:03 START job\n10:04 start job\n";
+    // the first line of each group is printed in full
+    new_ucmd!()
+        .args(&["-f", "1"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("10:00 start job\n10:02 stop job\n10:03 START job\n10:04 start job\n");
+    new_ucmd!()
+        .args(&["-c", "-i", "--skip-fields=1"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("      2 10:00 start job\n      1 10:02 stop job\n      2 10:03 START job\n");
+    new_ucmd!()
+        .args(&["-s", "6", "-w", "5"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("10:00 start job\n10:02 stop job\n10:03 START job\n10:04 start job\n");
+}
+
+#[test]
+fn test_skip_fields_keeps_blanks() {
+    // the blanks before a field are part of the key
+    new_ucmd!()
+        .args(&["-f", "1"])
+        .pipe_in("a  x\nb x\nc x\n")
+        .succeeds()
+        .stdout_is("a  x\nb x\n");
+}
+
+#[test]
+fn test_group_two_groups() {
+    for &(method, output) in &[
+        ("--group", "a\na\n\nb\n"),
+        ("--group=separate", "a\na\n\nb\n"),
+        ("--group=prepend", "\na\na\n\nb\n"),
+        ("--group=append", "a\na\n\nb\n\n"),
+        ("--group=both", "\na\na\n\nb\n\n"),
+    ] {
+        new_ucmd!()
+            .arg(method)
+            .pipe_in("a\na\nb\n")
+            .succeeds()
+            .stdout_is(output);
+    }
+}
+
+#[test]
+fn test_group_is_exclusive() {
+    for option in &["-c", "-d", "-D", "-u"] {
+        new_ucmd!()
+            .args(&["--group", option])
+            .fails()
+            .no_stdout()
+            .stderr_contains("cannot be used with '--group <group-method>'");
+    }
+}
@@ -56,6 +56,50 @@ fn test_stdin_line_len_regression() {
         .stdout_is("6\n");
 }
 
+#[test]
+fn test_max_line_length_tabs() {
+    // Tabs advance to the next multiple of 8 columns
+    new_ucmd!()
+        .args(&["-L"])
+        .pipe_in("a\tb\n\t\t\n1234567\t\n")
+        .run()
+        .stdout_is("16\n");
+    new_ucmd!()
+        .args(&["-L"])
+        .pipe_in("12345678\tx\n")
+        .run()
+        .stdout_is("17\n");
+}
+
+#[test]
+fn test_max_line_length_multibyte() {
+    // Wide characters take two columns, combining characters none
+    new_ucmd!()
+        .args(&["-L"])
+        .pipe_in("\u{65e5}\u{672c}\u{8a9e}\n")
+        .run()
+        .stdout_is("6\n");
+    new_ucmd!()
+        .args(&["-L"])
+        .pipe_in("e\u{301}te\u{301}\n")
+        .run()
+        .stdout_is("3\n");
+    new_ucmd!()
+        .args(&["-L", "-m"])
+        .pipe_in("\u{e9}\t\u{4e2d}\n")
+        .run()
+        .stdout_is("  4 10\n");
+}
+
+#[test]
+fn test_max_line_length_with_other_counts() {
+    new_ucmd!()
+        .args(&["-lwcL", "-"])
+        .pipe_in("one\ttwo\nthree\n")
+        .run()
+        .stdout_is("  2  3 14 11\n");
+}
+
 #[test]
 fn test_stdin_only_bytes() {
     new_ucmd!()
@@ -112,3 +156,40 @@ fn test_multiple_default() {
              alice_in_wonderland.txt\n   36  370 2189 total\n",
         );
 }
+
+#[test]
+fn test_files0_from() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("a", "one two\n");
+    at.write("b", "three\n");
+    at.write("names", "a\0nonexistent\0\0b\0");
+
+    scene
+        .ucmd()
+        .arg("--files0-from=names")
+        .fails()
+        .stdout_is(" 1 2 8 a\n 1 1 6 b\n 2 3 14 total\n")
+        .stderr_contains("nonexistent: No such file or directory")
+        .stderr_contains("names:3: invalid zero-length file name");
+}
+
+#[test]
+fn test_files0_from_stdin() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("a", "one two\n");
+
+    scene
+        .ucmd()
+        .args(&["-l", "--files0-from=-"])
+        .pipe_in("a\0a")
+        .succeeds()
+        .stdout_only("1 a\n1 a\n2 total\n");
+
+    scene
+        .ucmd()
+        .args(&["--files0-from=-", "a"])
+        .fails()
+        .no_stdout();
+}